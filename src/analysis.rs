@@ -0,0 +1,9 @@
+pub mod classification;
+pub mod coverability;
+pub mod deadlock;
+pub mod invariants;
+pub mod lint;
+pub mod markov;
+pub mod reachability;
+pub mod reduction;
+pub mod siphons;
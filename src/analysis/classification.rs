@@ -0,0 +1,450 @@
+//! # Structural classification
+//!
+//! Checks which of the classic structural subclasses of Petri nets a net belongs to.
+//! Many analysis results only hold for some of these classes.
+//! For every class that the net does not belong to, the places and transitions
+//! that break the definition of the class are reported.
+//!
+//! The classes are defined on the flow relation of the net.
+//! A read arc counts as an arc from the place to the transition and another one back.
+
+use crate::error::Error;
+use crate::petri_net::{ArcKind, PetriNet, PlaceRef, TransitionRef};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A structural subclass of Petri nets.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum NetClass {
+    /// Every transition has exactly one input place and exactly one output place.
+    StateMachine,
+    /// Every place has exactly one input transition and exactly one output transition.
+    MarkedGraph,
+    /// Every place with several output transitions is the only input place of each of them.
+    FreeChoice,
+    /// Any two places that share an output transition have the same output transitions.
+    ExtendedFreeChoice,
+    /// Of any two places that share an output transition,
+    /// the output transitions of one are included in the output transitions of the other.
+    AsymmetricChoice,
+    /// Every arc has weight one.
+    Ordinary,
+    /// No place is both an input and an output place of the same transition.
+    Pure,
+}
+
+impl NetClass {
+    /// All the classes, in the order in which `Classification` reports them.
+    pub const ALL: [Self; 7] = [
+        Self::StateMachine,
+        Self::MarkedGraph,
+        Self::FreeChoice,
+        Self::ExtendedFreeChoice,
+        Self::AsymmetricChoice,
+        Self::Ordinary,
+        Self::Pure,
+    ];
+}
+
+impl std::fmt::Display for NetClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::StateMachine => write!(f, "state machine"),
+            Self::MarkedGraph => write!(f, "marked graph"),
+            Self::FreeChoice => write!(f, "free-choice"),
+            Self::ExtendedFreeChoice => write!(f, "extended free-choice"),
+            Self::AsymmetricChoice => write!(f, "asymmetric choice"),
+            Self::Ordinary => write!(f, "ordinary"),
+            Self::Pure => write!(f, "pure"),
+        }
+    }
+}
+
+/// The places and transitions that prevent a net from belonging to a class.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ClassViolation {
+    /// The places that break the definition of the class.
+    pub places: BTreeSet<PlaceRef>,
+    /// The transitions that break the definition of the class.
+    pub transitions: BTreeSet<TransitionRef>,
+}
+
+impl ClassViolation {
+    fn is_empty(&self) -> bool {
+        self.places.is_empty() && self.transitions.is_empty()
+    }
+}
+
+/// The classes a net belongs to, and the reasons why it does not belong to the others.
+#[derive(Debug)]
+pub struct Classification {
+    violations: BTreeMap<NetClass, ClassViolation>,
+}
+
+impl Classification {
+    /// Checks whether the net belongs to the class.
+    #[must_use]
+    pub fn belongs_to(&self, class: NetClass) -> bool {
+        !self.violations.contains_key(&class)
+    }
+
+    /// Gets the places and transitions that prevent the net from belonging to the class.
+    /// Returns `None` if the net belongs to the class.
+    #[must_use]
+    pub fn violation(&self, class: NetClass) -> Option<&ClassViolation> {
+        self.violations.get(&class)
+    }
+
+    /// Returns an iterator over the classes that the net belongs to.
+    pub fn classes_iter(&self) -> impl Iterator<Item = NetClass> + '_ {
+        NetClass::ALL
+            .into_iter()
+            .filter(|class| self.belongs_to(*class))
+    }
+}
+
+/// The flow relation of the net with the weight of every arc,
+/// seen from the places and from the transitions.
+struct FlowRelation {
+    inputs: BTreeMap<TransitionRef, BTreeMap<PlaceRef, usize>>,
+    outputs: BTreeMap<TransitionRef, BTreeMap<PlaceRef, usize>>,
+    place_inputs: BTreeMap<PlaceRef, BTreeSet<TransitionRef>>,
+    place_outputs: BTreeMap<PlaceRef, BTreeSet<TransitionRef>>,
+}
+
+impl FlowRelation {
+    fn new(net: &PetriNet) -> Self {
+        let mut flow = Self {
+            inputs: BTreeMap::new(),
+            outputs: BTreeMap::new(),
+            place_inputs: net
+                .places_iter()
+                .map(|(place_ref, _)| (place_ref.clone(), BTreeSet::new()))
+                .collect(),
+            place_outputs: BTreeMap::new(),
+        };
+        flow.place_outputs.clone_from(&flow.place_inputs);
+
+        for (transition_ref, transition) in net.transitions_iter() {
+            let mut inputs = transition.get_preset().clone();
+            let mut outputs = transition.get_postset().clone();
+            for (place_ref, (kind, weight)) in transition.get_extended_preset() {
+                if *kind == ArcKind::Read {
+                    inputs.insert(place_ref.clone(), *weight);
+                    outputs.insert(place_ref.clone(), *weight);
+                }
+            }
+            for place_ref in inputs.keys() {
+                if let Some(transitions) = flow.place_outputs.get_mut(place_ref) {
+                    transitions.insert(transition_ref.clone());
+                }
+            }
+            for place_ref in outputs.keys() {
+                if let Some(transitions) = flow.place_inputs.get_mut(place_ref) {
+                    transitions.insert(transition_ref.clone());
+                }
+            }
+            flow.inputs.insert(transition_ref.clone(), inputs);
+            flow.outputs.insert(transition_ref.clone(), outputs);
+        }
+        flow
+    }
+
+    fn state_machine_violation(&self) -> ClassViolation {
+        let transitions = self
+            .inputs
+            .iter()
+            .filter(|(transition_ref, inputs)| {
+                inputs.len() != 1 || self.outputs[*transition_ref].len() != 1
+            })
+            .map(|(transition_ref, _)| transition_ref.clone())
+            .collect();
+        ClassViolation {
+            places: BTreeSet::new(),
+            transitions,
+        }
+    }
+
+    fn marked_graph_violation(&self) -> ClassViolation {
+        let places = self
+            .place_inputs
+            .iter()
+            .filter(|(place_ref, place_inputs)| {
+                place_inputs.len() != 1 || self.place_outputs[*place_ref].len() != 1
+            })
+            .map(|(place_ref, _)| place_ref.clone())
+            .collect();
+        ClassViolation {
+            places,
+            transitions: BTreeSet::new(),
+        }
+    }
+
+    fn free_choice_violation(&self) -> ClassViolation {
+        let mut violation = ClassViolation::default();
+        for (place_ref, place_outputs) in &self.place_outputs {
+            if place_outputs.len() <= 1 {
+                continue;
+            }
+            for transition_ref in place_outputs {
+                let inputs = &self.inputs[transition_ref];
+                if inputs.len() != 1 || !inputs.contains_key(place_ref) {
+                    violation.places.insert(place_ref.clone());
+                    violation.transitions.insert(transition_ref.clone());
+                }
+            }
+        }
+        violation
+    }
+
+    /// Checks every pair of places that share an output transition.
+    /// The pair breaks the class if `is_allowed` returns `false` for their output transitions.
+    /// The transitions reported are those that only one of the two places has as output.
+    fn shared_choice_violation<F>(&self, is_allowed: F) -> ClassViolation
+    where
+        F: Fn(&BTreeSet<TransitionRef>, &BTreeSet<TransitionRef>) -> bool,
+    {
+        let mut violation = ClassViolation::default();
+        for (place_ref, place_outputs) in &self.place_outputs {
+            let others = self.place_outputs.range::<PlaceRef, _>((
+                std::ops::Bound::Excluded(place_ref),
+                std::ops::Bound::Unbounded,
+            ));
+            for (other_ref, other_outputs) in others {
+                if place_outputs.is_disjoint(other_outputs)
+                    || is_allowed(place_outputs, other_outputs)
+                {
+                    continue;
+                }
+                violation.places.insert(place_ref.clone());
+                violation.places.insert(other_ref.clone());
+                violation
+                    .transitions
+                    .extend(place_outputs.symmetric_difference(other_outputs).cloned());
+            }
+        }
+        violation
+    }
+
+    fn ordinary_violation(&self) -> ClassViolation {
+        let mut violation = ClassViolation::default();
+        for arcs in [&self.inputs, &self.outputs] {
+            for (transition_ref, places) in arcs {
+                for (place_ref, weight) in places {
+                    if *weight != 1 {
+                        violation.places.insert(place_ref.clone());
+                        violation.transitions.insert(transition_ref.clone());
+                    }
+                }
+            }
+        }
+        violation
+    }
+
+    fn pure_violation(&self) -> ClassViolation {
+        let mut violation = ClassViolation::default();
+        for (transition_ref, inputs) in &self.inputs {
+            for place_ref in inputs.keys() {
+                if self.outputs[transition_ref].contains_key(place_ref) {
+                    violation.places.insert(place_ref.clone());
+                    violation.transitions.insert(transition_ref.clone());
+                }
+            }
+        }
+        violation
+    }
+}
+
+impl PetriNet {
+    /// Classifies the net into the structural subclasses of `NetClass`.
+    ///
+    /// # Errors
+    ///
+    /// If the net contains an inhibitor or a reset arc, then an error is returned.
+    pub fn classify(&self) -> Result<Classification, Error> {
+        self.check_no_arc_kinds(&[ArcKind::Inhibitor, ArcKind::Reset])?;
+        let flow = FlowRelation::new(self);
+
+        let violations = NetClass::ALL
+            .into_iter()
+            .map(|class| {
+                let violation = match class {
+                    NetClass::StateMachine => flow.state_machine_violation(),
+                    NetClass::MarkedGraph => flow.marked_graph_violation(),
+                    NetClass::FreeChoice => flow.free_choice_violation(),
+                    NetClass::ExtendedFreeChoice => flow.shared_choice_violation(|a, b| a == b),
+                    NetClass::AsymmetricChoice => {
+                        flow.shared_choice_violation(|a, b| a.is_subset(b) || b.is_subset(a))
+                    }
+                    NetClass::Ordinary => flow.ordinary_violation(),
+                    NetClass::Pure => flow.pure_violation(),
+                };
+                (class, violation)
+            })
+            .filter(|(_, violation)| !violation.is_empty())
+            .collect();
+        Ok(Classification { violations })
+    }
+}
+
+#[cfg(test)]
+mod classification_tests {
+    use super::*;
+    use crate::net_creator::*;
+
+    /// Creates the net `P1 -> T1`, `P1 -> T2`, `P2 -> T2`, which is asymmetric choice
+    /// but not extended free-choice.
+    fn create_asymmetric_choice_net() -> (PetriNet, Vec<PlaceRef>, Vec<TransitionRef>) {
+        let (mut net, place_refs, transition_refs) = create_basic_unconnected_net(2, 2);
+        assert!(
+            net.add_arc_place_transition(&place_refs[0], &transition_refs[0])
+                .is_ok()
+        );
+        assert!(
+            net.add_arc_place_transition(&place_refs[0], &transition_refs[1])
+                .is_ok()
+        );
+        assert!(
+            net.add_arc_place_transition(&place_refs[1], &transition_refs[1])
+                .is_ok()
+        );
+        (net, place_refs, transition_refs)
+    }
+
+    #[test]
+    fn classify_empty_net_belongs_to_every_class() {
+        let net = PetriNet::new();
+        let classification = net.classify().unwrap();
+
+        assert_eq!(classification.classes_iter().count(), NetClass::ALL.len());
+    }
+
+    #[test]
+    fn classify_loop_topology_is_not_pure() {
+        let (net, place_ref, transition_ref) = create_net_loop_topology();
+        let classification = net.classify().unwrap();
+
+        assert!(classification.belongs_to(NetClass::StateMachine));
+        assert!(classification.belongs_to(NetClass::MarkedGraph));
+        assert!(classification.belongs_to(NetClass::FreeChoice));
+        assert!(classification.belongs_to(NetClass::Ordinary));
+        assert_eq!(
+            classification.violation(NetClass::Pure),
+            Some(&ClassViolation {
+                places: BTreeSet::from([place_ref]),
+                transitions: BTreeSet::from([transition_ref]),
+            })
+        );
+    }
+
+    #[test]
+    fn classify_chain_topology_is_not_a_marked_graph() {
+        let (net, place_refs, _) = create_net_chain_topology(3);
+        let classification = net.classify().unwrap();
+
+        assert!(classification.belongs_to(NetClass::StateMachine));
+        assert!(classification.belongs_to(NetClass::Pure));
+        let violation = classification.violation(NetClass::MarkedGraph).unwrap();
+        assert_eq!(
+            violation.places,
+            BTreeSet::from([place_refs[0].clone(), place_refs[2].clone()])
+        );
+        assert!(violation.transitions.is_empty());
+    }
+
+    #[test]
+    fn classify_asymmetric_choice_reports_offending_nodes() {
+        let (net, place_refs, transition_refs) = create_asymmetric_choice_net();
+        let classification = net.classify().unwrap();
+
+        assert!(classification.belongs_to(NetClass::AsymmetricChoice));
+        assert_eq!(
+            classification.violation(NetClass::FreeChoice),
+            Some(&ClassViolation {
+                places: BTreeSet::from([place_refs[0].clone()]),
+                transitions: BTreeSet::from([transition_refs[1].clone()]),
+            })
+        );
+        assert_eq!(
+            classification.violation(NetClass::ExtendedFreeChoice),
+            Some(&ClassViolation {
+                places: place_refs.iter().cloned().collect(),
+                transitions: BTreeSet::from([transition_refs[0].clone()]),
+            })
+        );
+        let violation = classification.violation(NetClass::StateMachine).unwrap();
+        assert_eq!(violation.transitions, transition_refs.into_iter().collect());
+    }
+
+    #[test]
+    fn classify_crossed_choice_is_not_asymmetric_choice() {
+        let (mut net, place_refs, transition_refs) = create_asymmetric_choice_net();
+        let transition_ref = net.add_transition("T3");
+        assert!(
+            net.add_arc_place_transition(&place_refs[1], &transition_ref)
+                .is_ok()
+        );
+        let classification = net.classify().unwrap();
+
+        let violation = classification
+            .violation(NetClass::AsymmetricChoice)
+            .unwrap();
+        assert_eq!(violation.places, place_refs.iter().cloned().collect());
+        assert_eq!(
+            violation.transitions,
+            BTreeSet::from([transition_refs[0].clone(), transition_ref])
+        );
+    }
+
+    #[test]
+    fn classify_weighted_arc_is_not_ordinary() {
+        let (mut net, place_refs, transition_refs) = create_net_chain_topology(2);
+        assert!(
+            net.set_arc_weight_transition_place(&transition_refs[0], &place_refs[1], 2)
+                .is_ok()
+        );
+        let classification = net.classify().unwrap();
+
+        assert_eq!(
+            classification.violation(NetClass::Ordinary),
+            Some(&ClassViolation {
+                places: BTreeSet::from([place_refs[1].clone()]),
+                transitions: BTreeSet::from([transition_refs[0].clone()]),
+            })
+        );
+    }
+
+    #[test]
+    fn classify_read_arc_is_not_pure() {
+        let (mut net, _, transition_refs) = create_net_chain_topology(2);
+        let place_ref = net.add_place("P3");
+        assert!(net.add_read_arc(&place_ref, &transition_refs[0], 1).is_ok());
+        let classification = net.classify().unwrap();
+
+        assert!(!classification.belongs_to(NetClass::Pure));
+        assert!(!classification.belongs_to(NetClass::StateMachine));
+    }
+
+    #[test]
+    fn classify_reset_arc_returns_error() {
+        let (mut net, place_refs, transition_refs) = create_net_chain_topology(2);
+        assert!(
+            net.add_reset_arc(&place_refs[1], &transition_refs[0])
+                .is_ok()
+        );
+
+        let result = net.classify();
+        assert!(matches!(
+            result,
+            Err(Error::UnsupportedArc(_, ArcKind::Reset))
+        ));
+    }
+
+    #[test]
+    fn net_class_display_trait() {
+        assert_eq!(
+            format!("{}", NetClass::ExtendedFreeChoice),
+            "extended free-choice"
+        );
+        assert_eq!(format!("{}", NetClass::StateMachine), "state machine");
+    }
+}
@@ -0,0 +1,450 @@
+//! # Coverability graph
+//!
+//! Builds the Karp–Miller coverability graph of a net.
+//! Places whose number of tokens can grow without bound are marked with ω,
+//! which guarantees that the construction terminates even for unbounded nets.
+//! The graph starts from the current marking of the net,
+//! `CoverabilityGraph::new_from` starts from another marking instead.
+
+use crate::error::Error;
+use crate::petri_net::{ArcKind, PetriNet, PlaceRef, Transition, TransitionRef};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+/// The number of tokens in a place of a coverability graph.
+///
+/// The ordering places every finite number of tokens below ω.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub enum Tokens {
+    /// A finite number of tokens.
+    Finite(usize),
+    /// An arbitrarily large number of tokens.
+    Omega,
+}
+
+impl std::fmt::Display for Tokens {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Finite(tokens) => write!(f, "{tokens}"),
+            Self::Omega => write!(f, "ω"),
+        }
+    }
+}
+
+/// The Karp–Miller coverability graph of a Petri net.
+///
+/// Every state is identified by its index, the initial marking is always the state `0`.
+/// Edges are labelled with the transition whose firing leads from one state to the other.
+pub struct CoverabilityGraph {
+    markings: Vec<BTreeMap<PlaceRef, Tokens>>,
+    state_index: BTreeMap<BTreeMap<PlaceRef, Tokens>, usize>,
+    edges: Vec<Vec<(TransitionRef, usize)>>,
+    parents: Vec<Option<usize>>,
+}
+
+impl CoverabilityGraph {
+    /// Builds the coverability graph of the net starting from its current marking.
+    ///
+    /// The construction relies on the monotonicity of the firing rule,
+    /// which does not hold for nets with inhibitor or reset arcs.
+    /// Read arcs are supported.
+    ///
+    /// # Errors
+    ///
+    /// If the net contains an inhibitor or a reset arc, then an error is returned.
+    /// If firing a transition causes an overflow in a place, then an error is returned.
+    pub fn new(net: &PetriNet) -> Result<Self, Error> {
+        Self::new_from(net, &net.marking_vector())
+    }
+
+    /// Builds the coverability graph of the net starting from the given marking,
+    /// e.g. from `PetriNet::initial_marking_vector`.
+    /// Places missing from the marking are considered to hold no tokens.
+    ///
+    /// # Errors
+    ///
+    /// See `new`.
+    pub fn new_from(net: &PetriNet, marking: &BTreeMap<PlaceRef, usize>) -> Result<Self, Error> {
+        net.check_no_arc_kinds(&[ArcKind::Inhibitor, ArcKind::Reset])?;
+        let mut graph = Self {
+            markings: Vec::new(),
+            state_index: BTreeMap::new(),
+            edges: Vec::new(),
+            parents: Vec::new(),
+        };
+        let initial_marking = net
+            .complete_marking(marking)
+            .into_iter()
+            .map(|(place_ref, tokens)| (place_ref, Tokens::Finite(tokens)))
+            .collect();
+
+        let mut queue: VecDeque<usize> = VecDeque::new();
+        queue.push_back(graph.insert_state(initial_marking, None));
+
+        while let Some(state) = queue.pop_front() {
+            for (transition_ref, transition) in net.transitions_iter() {
+                if !Self::is_enabled(net, transition, &graph.markings[state]) {
+                    continue;
+                }
+                let mut next_marking = Self::fire(transition, &graph.markings[state])?;
+                graph.accelerate(net, state, &mut next_marking);
+
+                if let Some(next_state) = graph.state_index.get(&next_marking).copied() {
+                    graph.edges[state].push((transition_ref.clone(), next_state));
+                    continue;
+                }
+                let next_state = graph.insert_state(next_marking, Some(state));
+                queue.push_back(next_state);
+                graph.edges[state].push((transition_ref.clone(), next_state));
+            }
+        }
+        Ok(graph)
+    }
+
+    /// Gets the number of states in the graph.
+    #[inline]
+    #[must_use]
+    pub const fn get_cardinality_states(&self) -> usize {
+        self.markings.len()
+    }
+
+    /// Gets the number of edges in the graph.
+    #[must_use]
+    pub fn get_cardinality_edges(&self) -> usize {
+        self.edges.iter().map(Vec::len).sum()
+    }
+
+    /// Gets the marking of a state in the graph.
+    /// Returns `None` if the state does not exist.
+    #[must_use]
+    pub fn marking(&self, state: usize) -> Option<&BTreeMap<PlaceRef, Tokens>> {
+        self.markings.get(state)
+    }
+
+    /// Gets the outgoing edges of a state as pairs of (transition fired, state reached).
+    /// Returns an empty slice if the state does not exist.
+    #[must_use]
+    pub fn successors(&self, state: usize) -> &[(TransitionRef, usize)] {
+        self.edges.get(state).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns an iterator over the states and their markings.
+    /// The states are visited in the order in which they were discovered.
+    pub fn states_iter(&self) -> impl Iterator<Item = (usize, &BTreeMap<PlaceRef, Tokens>)> {
+        self.markings.iter().enumerate()
+    }
+
+    /// Finds the places that are marked with ω in some state of the graph,
+    /// i.e. the places whose number of tokens is not bounded.
+    #[must_use]
+    pub fn find_unbounded_places(&self) -> BTreeSet<PlaceRef> {
+        let mut unbounded_places: BTreeSet<PlaceRef> = BTreeSet::new();
+        for marking in &self.markings {
+            for (place_ref, tokens) in marking {
+                if *tokens == Tokens::Omega {
+                    unbounded_places.insert(place_ref.clone());
+                }
+            }
+        }
+        unbounded_places
+    }
+
+    /// Gets the maximum number of tokens that a place holds over all states.
+    /// Returns `None` if the place is not in the graph.
+    #[must_use]
+    pub fn bound(&self, place_ref: &PlaceRef) -> Option<Tokens> {
+        self.markings
+            .iter()
+            .filter_map(|marking| marking.get(place_ref).copied())
+            .max()
+    }
+
+    /// Checks whether the transition is enabled in the marking,
+    /// including its read arcs and the capacities of the places in its postset.
+    /// The net is known to contain no other kind of extended arcs.
+    fn is_enabled(
+        net: &PetriNet,
+        transition: &Transition,
+        marking: &BTreeMap<PlaceRef, Tokens>,
+    ) -> bool {
+        let has_tokens = |(place_ref, weight): (&PlaceRef, &usize)| {
+            marking
+                .get(place_ref)
+                .is_some_and(|tokens| *tokens >= Tokens::Finite(*weight))
+        };
+        let has_tokens = transition.get_preset().iter().all(has_tokens)
+            && transition
+                .get_extended_preset()
+                .iter()
+                .all(|(place_ref, (_, weight))| has_tokens((place_ref, weight)));
+
+        has_tokens
+            && transition.get_postset().iter().all(|(place_ref, weight)| {
+                let Ok(Some(capacity)) = net.capacity(place_ref) else {
+                    return true;
+                };
+                let Some(Tokens::Finite(tokens)) = marking.get(place_ref) else {
+                    return true;
+                };
+                let consumed = transition.get_preset().get(place_ref).copied().unwrap_or(0);
+                (tokens - consumed)
+                    .checked_add(*weight)
+                    .is_some_and(|tokens| tokens <= capacity)
+            })
+    }
+
+    fn fire(
+        transition: &Transition,
+        marking: &BTreeMap<PlaceRef, Tokens>,
+    ) -> Result<BTreeMap<PlaceRef, Tokens>, Error> {
+        let mut next_marking = marking.clone();
+        for (place_ref, weight) in transition.get_preset() {
+            if let Some(Tokens::Finite(tokens)) = next_marking.get_mut(place_ref) {
+                *tokens -= weight;
+            }
+        }
+        for (place_ref, weight) in transition.get_postset() {
+            if let Some(Tokens::Finite(tokens)) = next_marking.get_mut(place_ref) {
+                let Some(value) = tokens.checked_add(*weight) else {
+                    return Err(Error::TokenOverflow(place_ref.clone()));
+                };
+                *tokens = value;
+            }
+        }
+        Ok(next_marking)
+    }
+
+    /// Replaces by ω the number of tokens of every place that strictly grows
+    /// with respect to an ancestor whose marking is covered by `marking`.
+    ///
+    /// Places with a capacity can disable transitions when they hold more tokens,
+    /// so an ancestor is only used if the places with a capacity hold the same tokens.
+    fn accelerate(&self, net: &PetriNet, state: usize, marking: &mut BTreeMap<PlaceRef, Tokens>) {
+        let mut ancestor = Some(state);
+        while let Some(current) = ancestor {
+            let ancestor_marking = &self.markings[current];
+            let is_covered = ancestor_marking.iter().all(|(place_ref, tokens)| {
+                let Some(next_tokens) = marking.get(place_ref) else {
+                    return false;
+                };
+                if matches!(net.capacity(place_ref), Ok(Some(_))) {
+                    return next_tokens == tokens;
+                }
+                next_tokens >= tokens
+            });
+            if is_covered && ancestor_marking != marking {
+                for (place_ref, tokens) in marking.iter_mut() {
+                    if ancestor_marking.get(place_ref).is_some_and(|t| t < tokens) {
+                        *tokens = Tokens::Omega;
+                    }
+                }
+            }
+            ancestor = self.parents[current];
+        }
+    }
+
+    fn insert_state(
+        &mut self,
+        marking: BTreeMap<PlaceRef, Tokens>,
+        parent: Option<usize>,
+    ) -> usize {
+        let state = self.markings.len();
+        self.state_index.insert(marking.clone(), state);
+        self.markings.push(marking);
+        self.edges.push(Vec::new());
+        self.parents.push(parent);
+        state
+    }
+}
+
+impl PetriNet {
+    /// Finds the places whose number of tokens is not bounded
+    /// when starting from the current marking of the net.
+    /// Returns a `BTreeSet` with the place references.
+    ///
+    /// # Errors
+    ///
+    /// If the net contains an inhibitor or a reset arc, then an error is returned.
+    /// If firing a transition causes an overflow in a place, then an error is returned.
+    pub fn find_unbounded_places(&self) -> Result<BTreeSet<PlaceRef>, Error> {
+        let graph = CoverabilityGraph::new(self)?;
+        Ok(graph.find_unbounded_places())
+    }
+
+    /// Checks whether every place of the net is bounded
+    /// when starting from the current marking of the net.
+    ///
+    /// # Errors
+    ///
+    /// If the net contains an inhibitor or a reset arc, then an error is returned.
+    /// If firing a transition causes an overflow in a place, then an error is returned.
+    pub fn is_bounded(&self) -> Result<bool, Error> {
+        let unbounded_places = self.find_unbounded_places()?;
+        Ok(unbounded_places.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod coverability_tests {
+    use super::*;
+    use crate::net_creator::*;
+
+    #[test]
+    fn tokens_finite_is_less_than_omega() {
+        assert!(Tokens::Finite(usize::MAX) < Tokens::Omega);
+        assert!(Tokens::Finite(1) < Tokens::Finite(2));
+    }
+
+    #[test]
+    fn tokens_display_trait_prints_omega() {
+        assert_eq!(format!("{}", Tokens::Omega), "ω");
+        assert_eq!(format!("{}", Tokens::Finite(3)), "3");
+    }
+
+    #[test]
+    fn coverability_graph_empty_net_has_one_state() {
+        let net = PetriNet::new();
+        let graph = CoverabilityGraph::new(&net).unwrap();
+
+        assert_eq!(graph.get_cardinality_states(), 1);
+        assert_eq!(graph.get_cardinality_edges(), 0);
+        assert!(graph.find_unbounded_places().is_empty());
+    }
+
+    #[test]
+    fn coverability_graph_bounded_net_matches_the_reachability_graph() {
+        let (mut net, place_refs, transition_refs) = create_net_chain_topology(3);
+        assert!(net.add_token(&place_refs[0], 1).is_ok());
+        let graph = CoverabilityGraph::new(&net).unwrap();
+
+        assert_eq!(graph.get_cardinality_states(), 3);
+        assert_eq!(graph.successors(0), &[(transition_refs[0].clone(), 1)]);
+        assert_eq!(graph.bound(&place_refs[2]), Some(Tokens::Finite(1)));
+        assert!(net.is_bounded().unwrap());
+    }
+
+    #[test]
+    fn coverability_graph_source_transition_makes_place_unbounded() {
+        let mut net = PetriNet::new();
+        let place_ref = net.add_place("P1");
+        let transition_ref = net.add_transition("T1");
+        assert!(
+            net.add_arc_transition_place(&transition_ref, &place_ref)
+                .is_ok()
+        );
+        let graph = CoverabilityGraph::new(&net).unwrap();
+
+        assert_eq!(graph.get_cardinality_states(), 2);
+        assert_eq!(
+            graph.marking(1).unwrap().get(&place_ref),
+            Some(&Tokens::Omega)
+        );
+        assert_eq!(graph.successors(1), &[(transition_ref, 1)]);
+        assert_eq!(graph.bound(&place_ref), Some(Tokens::Omega));
+    }
+
+    #[test]
+    fn find_unbounded_places_returns_only_the_growing_places() {
+        // P1 -> T1 -> (P1, P2): P1 keeps its token, P2 grows forever.
+        let mut net = PetriNet::new();
+        let p1 = net.add_place("P1");
+        let p2 = net.add_place("P2");
+        let p3 = net.add_place("P3");
+        let t1 = net.add_transition("T1");
+        assert!(net.add_arc_place_transition(&p1, &t1).is_ok());
+        assert!(net.add_arc_transition_place(&t1, &p1).is_ok());
+        assert!(net.add_arc_transition_place(&t1, &p2).is_ok());
+        assert!(net.add_token(&p1, 1).is_ok());
+        assert!(net.add_token(&p3, 4).is_ok());
+
+        let unbounded_places = net.find_unbounded_places().unwrap();
+        assert_eq!(unbounded_places.len(), 1);
+        assert!(unbounded_places.contains(&p2));
+        assert!(!net.is_bounded().unwrap());
+    }
+
+    #[test]
+    fn find_unbounded_places_with_weighted_arcs() {
+        // P1 -2-> T1 -3-> P1: the number of tokens in P1 grows by one each time.
+        let mut net = PetriNet::new();
+        let p1 = net.add_place("P1");
+        let t1 = net.add_transition("T1");
+        assert!(net.add_weighted_arc_place_transition(&p1, &t1, 2).is_ok());
+        assert!(net.add_weighted_arc_transition_place(&t1, &p1, 3).is_ok());
+
+        assert!(net.is_bounded().unwrap());
+        assert!(net.add_token(&p1, 2).is_ok());
+        let unbounded_places = net.find_unbounded_places().unwrap();
+        assert!(unbounded_places.contains(&p1));
+    }
+
+    #[test]
+    fn find_unbounded_places_omega_enables_transitions_downstream() {
+        // T0 -> P1 -> T1 -> P2: both places are unbounded.
+        let (mut net, place_refs, _) = create_net_chain_topology(2);
+        let t0 = net.add_transition("T0");
+        assert!(net.add_arc_transition_place(&t0, &place_refs[0]).is_ok());
+
+        let unbounded_places = net.find_unbounded_places().unwrap();
+        assert_eq!(unbounded_places.len(), 2);
+    }
+
+    #[test]
+    fn find_unbounded_places_capacity_bounds_the_place() {
+        // T0 -> P1 -> T1 -> P2 with a capacity of 3 in P1: only P2 is unbounded.
+        let (mut net, place_refs, _) = create_net_chain_topology(2);
+        let t0 = net.add_transition("T0");
+        assert!(net.add_arc_transition_place(&t0, &place_refs[0]).is_ok());
+        assert!(net.set_capacity(&place_refs[0], Some(3)).is_ok());
+
+        let graph = CoverabilityGraph::new(&net).unwrap();
+        assert_eq!(graph.bound(&place_refs[0]), Some(Tokens::Finite(3)));
+        let unbounded_places = graph.find_unbounded_places();
+        assert_eq!(unbounded_places.len(), 1);
+        assert!(unbounded_places.contains(&place_refs[1]));
+    }
+
+    #[test]
+    fn coverability_graph_returns_err_for_inhibitor_and_reset_arcs() {
+        let (mut net, place_refs, transition_refs) = create_net_chain_topology(2);
+        assert!(
+            net.add_inhibitor_arc(&place_refs[1], &transition_refs[0], 1)
+                .is_ok()
+        );
+        assert!(matches!(
+            CoverabilityGraph::new(&net),
+            Err(Error::UnsupportedArc(_, ArcKind::Inhibitor))
+        ));
+        assert!(net.is_bounded().is_err());
+
+        assert!(
+            net.remove_extended_arc(&place_refs[1], &transition_refs[0])
+                .is_ok()
+        );
+        assert!(
+            net.add_reset_arc(&place_refs[1], &transition_refs[0])
+                .is_ok()
+        );
+        assert!(matches!(
+            CoverabilityGraph::new(&net),
+            Err(Error::UnsupportedArc(_, ArcKind::Reset))
+        ));
+    }
+
+    #[test]
+    fn coverability_graph_read_arc_guards_the_transition() {
+        let mut net = PetriNet::new();
+        let guard = net.add_place("Guard");
+        let counter = net.add_place("Counter");
+        let transition_ref = net.add_transition("Increment");
+        assert!(net.add_read_arc(&guard, &transition_ref, 1).is_ok());
+        assert!(
+            net.add_arc_transition_place(&transition_ref, &counter)
+                .is_ok()
+        );
+        assert!(net.is_bounded().unwrap());
+
+        assert!(net.add_token(&guard, 1).is_ok());
+        let unbounded = net.find_unbounded_places().unwrap();
+        assert_eq!(unbounded, BTreeSet::from([counter]));
+    }
+}
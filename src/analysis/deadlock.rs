@@ -0,0 +1,188 @@
+//! # Deadlock detection
+//!
+//! Searches the reachability graph for a marking in which no transition is enabled.
+//! The search starts from the current marking of the net,
+//! `PetriNet::find_deadlock_from` starts from another marking instead.
+
+use crate::analysis::reachability::ReachabilityGraph;
+use crate::error::Error;
+use crate::petri_net::{PetriNet, PlaceRef, TransitionRef};
+use std::collections::BTreeMap;
+
+/// The outcome of a deadlock check on a Petri net.
+#[derive(Debug, PartialEq, Eq)]
+pub enum DeadlockCheck {
+    /// Every reachable marking enables at least one transition.
+    DeadlockFree,
+    /// A reachable marking enables no transition.
+    Deadlock {
+        /// The dead marking.
+        marking: BTreeMap<PlaceRef, usize>,
+        /// A shortest sequence of transitions that leads from the initial marking to the dead marking.
+        firing_sequence: Vec<TransitionRef>,
+    },
+}
+
+impl PetriNet {
+    /// Checks whether a dead marking is reachable from the current marking of the net.
+    ///
+    /// The reachable markings are explored in breadth-first order,
+    /// so the firing sequence returned for a deadlock is as short as possible.
+    /// At most `state_limit` markings are explored.
+    ///
+    /// # Errors
+    ///
+    /// If firing a transition causes an overflow in a place, then an error is returned.
+    /// If the state limit is reached before finding a deadlock, then an error is returned,
+    /// since the net cannot be proven to be deadlock-free.
+    pub fn find_deadlock(&self, state_limit: usize) -> Result<DeadlockCheck, Error> {
+        self.find_deadlock_from(&self.marking_vector(), state_limit)
+    }
+
+    /// Checks whether a dead marking is reachable from the given marking,
+    /// e.g. from `PetriNet::initial_marking_vector`.
+    /// Places missing from the marking are considered to hold no tokens.
+    ///
+    /// The firing sequence of a deadlock starts from the given marking.
+    ///
+    /// # Errors
+    ///
+    /// See `find_deadlock`.
+    pub fn find_deadlock_from(
+        &self,
+        marking: &BTreeMap<PlaceRef, usize>,
+        state_limit: usize,
+    ) -> Result<DeadlockCheck, Error> {
+        let graph = ReachabilityGraph::new_from(self, marking, state_limit)?;
+
+        for (state, marking) in graph.states_iter() {
+            if !self.enabled_transitions_at(marking).is_empty() {
+                continue;
+            }
+            let firing_sequence = graph.firing_sequence(state).unwrap_or_default();
+            return Ok(DeadlockCheck::Deadlock {
+                marking: marking.clone(),
+                firing_sequence,
+            });
+        }
+
+        if graph.state_limit_reached() {
+            return Err(Error::StateLimitReached(graph.state_limit()));
+        }
+        Ok(DeadlockCheck::DeadlockFree)
+    }
+}
+
+#[cfg(test)]
+mod deadlock_tests {
+    use super::*;
+    use crate::net_creator::*;
+
+    #[test]
+    fn find_deadlock_empty_net_is_dead() {
+        let net = PetriNet::new();
+        let result = net.find_deadlock(10);
+
+        assert_eq!(
+            result.unwrap(),
+            DeadlockCheck::Deadlock {
+                marking: BTreeMap::new(),
+                firing_sequence: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn find_deadlock_loop_topology_is_deadlock_free() {
+        let (mut net, place_ref, _) = create_net_loop_topology();
+        assert!(net.add_token(&place_ref, 1).is_ok());
+
+        assert_eq!(net.find_deadlock(10).unwrap(), DeadlockCheck::DeadlockFree);
+    }
+
+    #[test]
+    fn find_deadlock_chain_topology_returns_the_witness() {
+        let (mut net, place_refs, transition_refs) = create_net_chain_topology(3);
+        assert!(net.add_token(&place_refs[0], 1).is_ok());
+
+        let Ok(DeadlockCheck::Deadlock {
+            marking,
+            firing_sequence,
+        }) = net.find_deadlock(10)
+        else {
+            panic!("The chain topology should reach a deadlock");
+        };
+        assert_eq!(marking.get(&place_refs[0]), Some(&0));
+        assert_eq!(marking.get(&place_refs[2]), Some(&1));
+        assert_eq!(firing_sequence, transition_refs);
+    }
+
+    #[test]
+    fn find_deadlock_after_fire_returns_the_witness_from_the_chosen_marking() {
+        let (mut net, place_refs, transition_refs) = create_net_chain_topology(3);
+        assert!(net.add_token(&place_refs[0], 1).is_ok());
+        assert!(net.fire(&transition_refs[0]).is_ok());
+
+        let Ok(DeadlockCheck::Deadlock {
+            firing_sequence, ..
+        }) = net.find_deadlock(10)
+        else {
+            panic!("The chain topology should reach a deadlock");
+        };
+        assert_eq!(firing_sequence, &transition_refs[1..]);
+        let Ok(DeadlockCheck::Deadlock {
+            marking,
+            firing_sequence,
+        }) = net.find_deadlock_from(&net.initial_marking_vector(), 10)
+        else {
+            panic!("The chain topology should reach a deadlock");
+        };
+        assert_eq!(marking.get(&place_refs[2]), Some(&1));
+        assert_eq!(firing_sequence, transition_refs);
+    }
+
+    #[test]
+    fn find_deadlock_returns_the_shortest_witness() {
+        // P1 -> T_long1 -> P2 -> T_long2 -> P3
+        // P1 -> T_short -> P4
+        let mut net = PetriNet::new();
+        let p1 = net.add_place("P1");
+        let p2 = net.add_place("P2");
+        let p3 = net.add_place("P3");
+        let p4 = net.add_place("P4");
+        let long_1 = net.add_transition("T_long1");
+        let long_2 = net.add_transition("T_long2");
+        let short = net.add_transition("T_short");
+        assert!(net.add_arc_place_transition(&p1, &long_1).is_ok());
+        assert!(net.add_arc_transition_place(&long_1, &p2).is_ok());
+        assert!(net.add_arc_place_transition(&p2, &long_2).is_ok());
+        assert!(net.add_arc_transition_place(&long_2, &p3).is_ok());
+        assert!(net.add_arc_place_transition(&p1, &short).is_ok());
+        assert!(net.add_arc_transition_place(&short, &p4).is_ok());
+        assert!(net.add_token(&p1, 1).is_ok());
+
+        let Ok(DeadlockCheck::Deadlock {
+            marking,
+            firing_sequence,
+        }) = net.find_deadlock(10)
+        else {
+            panic!("The net should reach a deadlock");
+        };
+        assert_eq!(marking.get(&p4), Some(&1));
+        assert_eq!(firing_sequence, vec![short]);
+    }
+
+    #[test]
+    fn find_deadlock_returns_err_if_state_limit_reached() {
+        let mut net = PetriNet::new();
+        let place_ref = net.add_place("P1");
+        let transition_ref = net.add_transition("T1");
+        assert!(
+            net.add_arc_transition_place(&transition_ref, &place_ref)
+                .is_ok()
+        );
+
+        let result = net.find_deadlock(10);
+        assert!(matches!(result, Err(Error::StateLimitReached(10))));
+    }
+}
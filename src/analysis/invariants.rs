@@ -0,0 +1,476 @@
+//! # Incidence matrix and structural invariants
+//!
+//! Provides a linear-algebra view of the net and computes
+//! generator sets of minimal semi-positive P-invariants and T-invariants
+//! using the Farkas algorithm.
+
+use crate::error::Error;
+use crate::petri_net::{ArcKind, PetriNet, PlaceRef, TransitionRef};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The pre and post incidence matrices of a Petri net.
+///
+/// Rows correspond to places and columns to transitions,
+/// both in the order in which the net iterates over them.
+/// Only normal arcs are taken into account, since the other kinds of arcs
+/// do not move a fixed number of tokens.
+pub struct IncidenceMatrix {
+    places: Vec<PlaceRef>,
+    transitions: Vec<TransitionRef>,
+    place_index: BTreeMap<PlaceRef, usize>,
+    transition_index: BTreeMap<TransitionRef, usize>,
+    pre: Vec<Vec<usize>>,
+    post: Vec<Vec<usize>>,
+}
+
+impl IncidenceMatrix {
+    /// Builds the incidence matrices of the net.
+    #[must_use]
+    pub fn new(net: &PetriNet) -> Self {
+        let places: Vec<PlaceRef> = net
+            .places_iter()
+            .map(|(place_ref, _)| place_ref.clone())
+            .collect();
+        let transitions: Vec<TransitionRef> = net
+            .transitions_iter()
+            .map(|(transition_ref, _)| transition_ref.clone())
+            .collect();
+        let place_index: BTreeMap<PlaceRef, usize> = places
+            .iter()
+            .enumerate()
+            .map(|(i, place_ref)| (place_ref.clone(), i))
+            .collect();
+        let transition_index: BTreeMap<TransitionRef, usize> = transitions
+            .iter()
+            .enumerate()
+            .map(|(j, transition_ref)| (transition_ref.clone(), j))
+            .collect();
+
+        let mut pre = vec![vec![0; transitions.len()]; places.len()];
+        let mut post = vec![vec![0; transitions.len()]; places.len()];
+        for (j, (_, transition)) in net.transitions_iter().enumerate() {
+            for (place_ref, weight) in transition.get_preset() {
+                pre[place_index[place_ref]][j] = *weight;
+            }
+            for (place_ref, weight) in transition.get_postset() {
+                post[place_index[place_ref]][j] = *weight;
+            }
+        }
+
+        Self {
+            places,
+            transitions,
+            place_index,
+            transition_index,
+            pre,
+            post,
+        }
+    }
+
+    /// Gets the place references in the order of the rows.
+    #[must_use]
+    pub fn places(&self) -> &[PlaceRef] {
+        &self.places
+    }
+
+    /// Gets the transition references in the order of the columns.
+    #[must_use]
+    pub fn transitions(&self) -> &[TransitionRef] {
+        &self.transitions
+    }
+
+    /// Gets the pre incidence matrix, i.e. the weights of the arcs from places to transitions.
+    #[must_use]
+    pub fn pre_matrix(&self) -> &[Vec<usize>] {
+        &self.pre
+    }
+
+    /// Gets the post incidence matrix, i.e. the weights of the arcs from transitions to places.
+    #[must_use]
+    pub fn post_matrix(&self) -> &[Vec<usize>] {
+        &self.post
+    }
+
+    /// Gets the weight of the arc from the place to the transition, zero if there is no arc.
+    /// Returns `None` if one of the references is not in the matrix.
+    #[must_use]
+    pub fn pre(&self, place_ref: &PlaceRef, transition_ref: &TransitionRef) -> Option<usize> {
+        let (i, j) = self.indices(place_ref, transition_ref)?;
+        Some(self.pre[i][j])
+    }
+
+    /// Gets the weight of the arc from the transition to the place, zero if there is no arc.
+    /// Returns `None` if one of the references is not in the matrix.
+    #[must_use]
+    pub fn post(&self, place_ref: &PlaceRef, transition_ref: &TransitionRef) -> Option<usize> {
+        let (i, j) = self.indices(place_ref, transition_ref)?;
+        Some(self.post[i][j])
+    }
+
+    /// Gets the change in the number of tokens of the place when the transition fires,
+    /// i.e. the entry of the incidence matrix `post - pre`.
+    /// Returns `None` if one of the references is not in the matrix.
+    #[must_use]
+    pub fn incidence(&self, place_ref: &PlaceRef, transition_ref: &TransitionRef) -> Option<i128> {
+        let (i, j) = self.indices(place_ref, transition_ref)?;
+        Some(to_signed(self.post[i][j]) - to_signed(self.pre[i][j]))
+    }
+
+    /// Gets the incidence matrix `post - pre` with places as rows and transitions as columns.
+    #[must_use]
+    pub fn incidence_matrix(&self) -> Vec<Vec<i128>> {
+        self.pre
+            .iter()
+            .zip(&self.post)
+            .map(|(pre_row, post_row)| {
+                pre_row
+                    .iter()
+                    .zip(post_row)
+                    .map(|(pre, post)| to_signed(*post) - to_signed(*pre))
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn indices(
+        &self,
+        place_ref: &PlaceRef,
+        transition_ref: &TransitionRef,
+    ) -> Option<(usize, usize)> {
+        let i = self.place_index.get(place_ref)?;
+        let j = self.transition_index.get(transition_ref)?;
+        Some((*i, *j))
+    }
+}
+
+impl PetriNet {
+    /// Builds the pre and post incidence matrices of the net.
+    #[must_use]
+    pub fn incidence_matrix(&self) -> IncidenceMatrix {
+        IncidenceMatrix::new(self)
+    }
+
+    /// Finds a generator set of the minimal semi-positive P-invariants of the net.
+    /// Every invariant is a weighting of the places whose weighted sum of tokens
+    /// is the same in every reachable marking.
+    /// Returns a vector of `BTreeMap` with the places in the support of each invariant as keys.
+    ///
+    /// Inhibitor and read arcs do not change the marking and are ignored.
+    ///
+    /// # Errors
+    ///
+    /// If the net contains a reset arc, then an error is returned.
+    /// If the coefficients grow too large during the computation, then an error is returned.
+    pub fn find_p_invariants(&self) -> Result<Vec<BTreeMap<PlaceRef, usize>>, Error> {
+        self.check_no_arc_kinds(&[ArcKind::Reset])?;
+        let matrix = self.incidence_matrix();
+        let solutions = farkas(matrix.incidence_matrix())?;
+        Ok(label_solutions(&solutions, matrix.places()))
+    }
+
+    /// Finds a generator set of the minimal semi-positive T-invariants of the net.
+    /// Every invariant counts how many times each transition must fire
+    /// to reproduce the marking in which the firing sequence started.
+    /// Returns a vector of `BTreeMap` with the transitions in the support of each invariant as keys.
+    ///
+    /// Inhibitor and read arcs do not change the marking and are ignored.
+    ///
+    /// # Errors
+    ///
+    /// If the net contains a reset arc, then an error is returned.
+    /// If the coefficients grow too large during the computation, then an error is returned.
+    pub fn find_t_invariants(&self) -> Result<Vec<BTreeMap<TransitionRef, usize>>, Error> {
+        self.check_no_arc_kinds(&[ArcKind::Reset])?;
+        let matrix = self.incidence_matrix();
+        let incidence = matrix.incidence_matrix();
+        let transposed: Vec<Vec<i128>> = (0..matrix.transitions().len())
+            .map(|j| incidence.iter().map(|row| row[j]).collect())
+            .collect();
+        let solutions = farkas(transposed)?;
+        Ok(label_solutions(&solutions, matrix.transitions()))
+    }
+}
+
+/// A row of the Farkas tableau: the remaining constraints and the combination of variables.
+type Row = (Vec<i128>, Vec<i128>);
+
+/// Computes the minimal semi-positive solutions `y >= 0` of `y^T A = 0`,
+/// where every row of `matrix` corresponds to a variable and every column to a constraint.
+fn farkas(matrix: Vec<Vec<i128>>) -> Result<Vec<Vec<i128>>, Error> {
+    let variables = matrix.len();
+    let mut rows: Vec<Row> = matrix
+        .into_iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let mut identity = vec![0; variables];
+            identity[i] = 1;
+            (row, identity)
+        })
+        .collect();
+    let constraints = rows.first().map_or(0, |(row, _)| row.len());
+
+    for j in 0..constraints {
+        let mut next_rows: Vec<Row> = Vec::new();
+        let positive: Vec<&Row> = rows.iter().filter(|(row, _)| row[j] > 0).collect();
+        let negative: Vec<&Row> = rows.iter().filter(|(row, _)| row[j] < 0).collect();
+        for first in &positive {
+            for second in &negative {
+                next_rows.push(combine(first, second, j)?);
+            }
+        }
+        next_rows.extend(rows.iter().filter(|(row, _)| row[j] == 0).cloned());
+        rows = keep_minimal_support(next_rows);
+    }
+
+    let mut solutions: Vec<Vec<i128>> = rows.into_iter().map(|(_, solution)| solution).collect();
+    solutions.sort();
+    Ok(solutions)
+}
+
+/// Combines two rows with opposite signs in column `j` so that the column becomes zero.
+fn combine(first: &Row, second: &Row, j: usize) -> Result<Row, Error> {
+    let first_factor = second.0[j].abs();
+    let second_factor = first.0[j].abs();
+    let linear_combination = |a: &[i128], b: &[i128]| -> Option<Vec<i128>> {
+        a.iter()
+            .zip(b)
+            .map(|(x, y)| {
+                x.checked_mul(first_factor)?
+                    .checked_add(y.checked_mul(second_factor)?)
+            })
+            .collect()
+    };
+    let row = linear_combination(&first.0, &second.0).ok_or(Error::ArithmeticOverflow)?;
+    let solution = linear_combination(&first.1, &second.1).ok_or(Error::ArithmeticOverflow)?;
+
+    let divisor = row
+        .iter()
+        .chain(&solution)
+        .fold(0, |acc, value| gcd(acc, *value));
+    if divisor > 1 {
+        return Ok((
+            row.iter().map(|value| value / divisor).collect(),
+            solution.iter().map(|value| value / divisor).collect(),
+        ));
+    }
+    Ok((row, solution))
+}
+
+/// Removes the rows whose support is not minimal among all rows, including duplicates.
+fn keep_minimal_support(rows: Vec<Row>) -> Vec<Row> {
+    let supports: Vec<BTreeSet<usize>> = rows
+        .iter()
+        .map(|(_, solution)| {
+            solution
+                .iter()
+                .enumerate()
+                .filter(|(_, value)| **value != 0)
+                .map(|(i, _)| i)
+                .collect()
+        })
+        .collect();
+
+    rows.into_iter()
+        .enumerate()
+        .filter(|(i, _)| {
+            !supports.iter().enumerate().any(|(k, other)| {
+                k != *i
+                    && other.is_subset(&supports[*i])
+                    && (other.len() < supports[*i].len() || k < *i)
+            })
+        })
+        .map(|(_, row)| row)
+        .collect()
+}
+
+fn label_solutions<T: Clone + Ord>(
+    solutions: &[Vec<i128>],
+    labels: &[T],
+) -> Vec<BTreeMap<T, usize>> {
+    solutions
+        .iter()
+        .map(|solution| {
+            solution
+                .iter()
+                .zip(labels)
+                .filter(|(value, _)| **value != 0)
+                .map(|(value, label)| {
+                    (label.clone(), usize::try_from(*value).unwrap_or(usize::MAX))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+const fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+fn to_signed(value: usize) -> i128 {
+    i128::try_from(value).unwrap_or(i128::MAX)
+}
+
+#[cfg(test)]
+mod invariants_tests {
+    use super::*;
+    use crate::net_creator::*;
+
+    #[test]
+    fn incidence_matrix_empty_net() {
+        let net = PetriNet::new();
+        let matrix = net.incidence_matrix();
+
+        assert!(matrix.places().is_empty());
+        assert!(matrix.transitions().is_empty());
+        assert!(matrix.incidence_matrix().is_empty());
+    }
+
+    #[test]
+    fn incidence_matrix_chain_topology() {
+        let (net, place_refs, transition_refs) = create_net_chain_topology(3);
+        let matrix = net.incidence_matrix();
+
+        assert_eq!(matrix.places(), place_refs.as_slice());
+        assert_eq!(matrix.transitions(), transition_refs.as_slice());
+        assert_eq!(matrix.pre_matrix(), &[vec![1, 0], vec![0, 1], vec![0, 0]]);
+        assert_eq!(matrix.post_matrix(), &[vec![0, 0], vec![1, 0], vec![0, 1]]);
+        assert_eq!(
+            matrix.incidence_matrix(),
+            vec![vec![-1, 0], vec![1, -1], vec![0, 1]]
+        );
+    }
+
+    #[test]
+    fn incidence_matrix_uses_arc_weights() {
+        let mut net = PetriNet::new();
+        let p1 = net.add_place("P1");
+        let t1 = net.add_transition("T1");
+        assert!(net.add_weighted_arc_place_transition(&p1, &t1, 2).is_ok());
+        assert!(net.add_weighted_arc_transition_place(&t1, &p1, 5).is_ok());
+        let matrix = net.incidence_matrix();
+
+        assert_eq!(matrix.pre(&p1, &t1), Some(2));
+        assert_eq!(matrix.post(&p1, &t1), Some(5));
+        assert_eq!(matrix.incidence(&p1, &t1), Some(3));
+    }
+
+    #[test]
+    fn incidence_matrix_returns_none_for_invalid_refs() {
+        let (net, place_ref, _) = create_net_loop_topology();
+        let matrix = net.incidence_matrix();
+        let transition_ref = TransitionRef::new("Not in the net");
+
+        assert_eq!(matrix.pre(&place_ref, &transition_ref), None);
+        assert_eq!(matrix.incidence(&place_ref, &transition_ref), None);
+    }
+
+    #[test]
+    fn find_invariants_empty_net() {
+        let net = PetriNet::new();
+
+        assert_eq!(net.find_p_invariants().unwrap(), Vec::new());
+        assert_eq!(net.find_t_invariants().unwrap(), Vec::new());
+    }
+
+    #[test]
+    fn find_invariants_loop_topology() {
+        let (net, place_ref, transition_ref) = create_net_loop_topology();
+
+        assert_eq!(
+            net.find_p_invariants().unwrap(),
+            vec![BTreeMap::from([(place_ref, 1)])]
+        );
+        assert_eq!(
+            net.find_t_invariants().unwrap(),
+            vec![BTreeMap::from([(transition_ref, 1)])]
+        );
+    }
+
+    #[test]
+    fn find_invariants_chain_topology() {
+        let (net, place_refs, _) = create_net_chain_topology(3);
+        let expected: BTreeMap<PlaceRef, usize> = place_refs
+            .into_iter()
+            .map(|place_ref| (place_ref, 1))
+            .collect();
+
+        assert_eq!(net.find_p_invariants().unwrap(), vec![expected]);
+        assert_eq!(net.find_t_invariants().unwrap(), Vec::new());
+    }
+
+    #[test]
+    fn find_invariants_weighted_cycle() {
+        // P1 -2-> T1 -> P2 -> T2 -2-> P1
+        let mut net = PetriNet::new();
+        let p1 = net.add_place("P1");
+        let p2 = net.add_place("P2");
+        let t1 = net.add_transition("T1");
+        let t2 = net.add_transition("T2");
+        assert!(net.add_weighted_arc_place_transition(&p1, &t1, 2).is_ok());
+        assert!(net.add_arc_transition_place(&t1, &p2).is_ok());
+        assert!(net.add_arc_place_transition(&p2, &t2).is_ok());
+        assert!(net.add_weighted_arc_transition_place(&t2, &p1, 2).is_ok());
+
+        assert_eq!(
+            net.find_p_invariants().unwrap(),
+            vec![BTreeMap::from([(p1, 1), (p2, 2)])]
+        );
+        assert_eq!(
+            net.find_t_invariants().unwrap(),
+            vec![BTreeMap::from([(t1, 1), (t2, 1)])]
+        );
+    }
+
+    #[test]
+    fn find_p_invariants_mutual_exclusion_conserves_the_lock() {
+        // Two processes alternate between idle and critical, guarded by a shared lock.
+        let mut net = PetriNet::new();
+        let lock = net.add_place("lock");
+        let mut critical_places = Vec::new();
+        for process in ["A", "B"] {
+            let idle = net.add_place(&format!("idle_{process}"));
+            let critical = net.add_place(&format!("critical_{process}"));
+            let acquire = net.add_transition(&format!("acquire_{process}"));
+            let release = net.add_transition(&format!("release_{process}"));
+            assert!(net.add_arc_place_transition(&idle, &acquire).is_ok());
+            assert!(net.add_arc_place_transition(&lock, &acquire).is_ok());
+            assert!(net.add_arc_transition_place(&acquire, &critical).is_ok());
+            assert!(net.add_arc_place_transition(&critical, &release).is_ok());
+            assert!(net.add_arc_transition_place(&release, &idle).is_ok());
+            assert!(net.add_arc_transition_place(&release, &lock).is_ok());
+            critical_places.push(critical);
+        }
+
+        let invariants = net.find_p_invariants().unwrap();
+        assert_eq!(invariants.len(), 3);
+        let lock_invariant = BTreeMap::from([
+            (lock, 1),
+            (critical_places[0].clone(), 1),
+            (critical_places[1].clone(), 1),
+        ]);
+        assert!(invariants.contains(&lock_invariant));
+
+        let t_invariants = net.find_t_invariants().unwrap();
+        assert_eq!(t_invariants.len(), 2);
+    }
+
+    #[test]
+    fn find_invariants_returns_err_for_reset_arcs() {
+        let (mut net, place_refs, transition_refs) = create_net_chain_topology(2);
+        assert!(net.find_p_invariants().is_ok());
+
+        assert!(
+            net.add_reset_arc(&place_refs[1], &transition_refs[0])
+                .is_ok()
+        );
+        assert!(matches!(
+            net.find_p_invariants(),
+            Err(Error::UnsupportedArc(_, ArcKind::Reset))
+        ));
+        assert!(net.find_t_invariants().is_err());
+    }
+}
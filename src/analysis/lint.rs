@@ -0,0 +1,697 @@
+//! # Lint
+//!
+//! Sanity checks that find parts of a net that are most likely mistakes,
+//! such as nodes without arcs, duplicated nodes or transitions that can never fire.
+//! Every problem is reported as a `Diagnostic` with a severity and the nodes involved.
+//!
+//! The checks to run and their severities are chosen with a `LintConfig`.
+//! The structural checks only look at the arcs and the labels of the net.
+//! The checks for unreachable nodes explore the reachability graph from the initial marking,
+//! so they are skipped when the number of reachable markings exceeds the state limit.
+
+use crate::analysis::reachability::ReachabilityGraph;
+use crate::error::Error;
+use crate::export::ids::{IdFormat, sanitize_id};
+use crate::petri_net::{PetriNet, PlaceRef, TransitionRef};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// How serious a diagnostic is. Severities are ordered from the least to the most serious.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum Severity {
+    /// The net is valid, but the construct is unusual.
+    Info,
+    /// The construct is most likely a mistake.
+    Warning,
+    /// The construct is not allowed. No check reports errors by default,
+    /// but any check can be raised to this severity with `LintConfig::set_severity`.
+    Error,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Info => write!(f, "info"),
+            Self::Warning => write!(f, "warning"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A check run by the lint.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum LintCheck {
+    /// A place without any arc.
+    IsolatedPlace,
+    /// A transition without any arc.
+    IsolatedTransition,
+    /// A transition that produces tokens without consuming any.
+    SourceTransition,
+    /// A transition that consumes tokens without producing any.
+    SinkTransition,
+    /// Several places or transitions with the same label.
+    DuplicateLabel,
+    /// A label that the exporter for the format has to change to get a valid identifier.
+    InvalidIdentifier(IdFormat),
+    /// Several places with the same arcs.
+    ParallelPlaces,
+    /// Several transitions with the same arcs.
+    ParallelTransitions,
+    /// A place that is not marked in any marking reachable from the initial marking.
+    UnreachablePlace,
+    /// A transition that is not enabled in any marking reachable from the initial marking.
+    UnreachableTransition,
+}
+
+impl LintCheck {
+    /// All the checks, in the order in which `PetriNet::lint` reports them.
+    pub const ALL: [Self; 12] = [
+        Self::IsolatedPlace,
+        Self::IsolatedTransition,
+        Self::SourceTransition,
+        Self::SinkTransition,
+        Self::DuplicateLabel,
+        Self::InvalidIdentifier(IdFormat::Pnml),
+        Self::InvalidIdentifier(IdFormat::Dot),
+        Self::InvalidIdentifier(IdFormat::Lola),
+        Self::ParallelPlaces,
+        Self::ParallelTransitions,
+        Self::UnreachablePlace,
+        Self::UnreachableTransition,
+    ];
+
+    /// Gets the severity of the diagnostics of the check in the default configuration.
+    #[must_use]
+    pub const fn default_severity(self) -> Severity {
+        match self {
+            Self::SourceTransition
+            | Self::SinkTransition
+            | Self::InvalidIdentifier(_)
+            | Self::ParallelPlaces
+            | Self::ParallelTransitions => Severity::Info,
+            Self::IsolatedPlace
+            | Self::IsolatedTransition
+            | Self::DuplicateLabel
+            | Self::UnreachablePlace
+            | Self::UnreachableTransition => Severity::Warning,
+        }
+    }
+}
+
+impl std::fmt::Display for LintCheck {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::IsolatedPlace => write!(f, "isolated place"),
+            Self::IsolatedTransition => write!(f, "isolated transition"),
+            Self::SourceTransition => write!(f, "source transition"),
+            Self::SinkTransition => write!(f, "sink transition"),
+            Self::DuplicateLabel => write!(f, "duplicate label"),
+            Self::InvalidIdentifier(format) => write!(f, "invalid {format} identifier"),
+            Self::ParallelPlaces => write!(f, "parallel places"),
+            Self::ParallelTransitions => write!(f, "parallel transitions"),
+            Self::UnreachablePlace => write!(f, "unreachable place"),
+            Self::UnreachableTransition => write!(f, "unreachable transition"),
+        }
+    }
+}
+
+/// A problem found by the lint.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Diagnostic {
+    /// The check that found the problem.
+    pub check: LintCheck,
+    /// How serious the problem is.
+    pub severity: Severity,
+    /// The places involved.
+    pub places: BTreeSet<PlaceRef>,
+    /// The transitions involved.
+    pub transitions: BTreeSet<TransitionRef>,
+    /// A description of the problem.
+    pub message: String,
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} ({})", self.severity, self.message, self.check)
+    }
+}
+
+/// The checks run by the lint, the severity of each one,
+/// and the maximum number of markings explored by the checks for unreachable nodes.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LintConfig {
+    severities: BTreeMap<LintCheck, Severity>,
+    state_limit: usize,
+}
+
+impl Default for LintConfig {
+    /// Runs every check with its default severity.
+    fn default() -> Self {
+        Self {
+            severities: LintCheck::ALL
+                .into_iter()
+                .map(|check| (check, check.default_severity()))
+                .collect(),
+            state_limit: Self::DEFAULT_STATE_LIMIT,
+        }
+    }
+}
+
+impl LintConfig {
+    /// The state limit of the default configuration.
+    pub const DEFAULT_STATE_LIMIT: usize = 10_000;
+
+    /// Creates a configuration that runs every check with its default severity.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a configuration that runs no check.
+    #[must_use]
+    pub const fn empty() -> Self {
+        Self {
+            severities: BTreeMap::new(),
+            state_limit: Self::DEFAULT_STATE_LIMIT,
+        }
+    }
+
+    /// Runs the check with its default severity, unless it is already enabled.
+    pub fn enable(&mut self, check: LintCheck) {
+        self.severities
+            .entry(check)
+            .or_insert_with(|| check.default_severity());
+    }
+
+    /// Stops running the check.
+    pub fn disable(&mut self, check: LintCheck) {
+        self.severities.remove(&check);
+    }
+
+    /// Runs the check with the given severity.
+    pub fn set_severity(&mut self, check: LintCheck, severity: Severity) {
+        self.severities.insert(check, severity);
+    }
+
+    /// Gets the severity of the check.
+    /// Returns `None` if the check is not run.
+    #[must_use]
+    pub fn severity(&self, check: LintCheck) -> Option<Severity> {
+        self.severities.get(&check).copied()
+    }
+
+    /// Gets the maximum number of markings explored by the checks for unreachable nodes.
+    #[must_use]
+    pub const fn state_limit(&self) -> usize {
+        self.state_limit
+    }
+
+    /// Sets the maximum number of markings explored by the checks for unreachable nodes.
+    pub const fn set_state_limit(&mut self, state_limit: usize) {
+        self.state_limit = state_limit;
+    }
+}
+
+/// Collects the diagnostics of the enabled checks.
+struct Linter<'a> {
+    net: &'a PetriNet,
+    config: &'a LintConfig,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Linter<'a> {
+    const fn new(net: &'a PetriNet, config: &'a LintConfig) -> Self {
+        Self {
+            net,
+            config,
+            diagnostics: Vec::new(),
+        }
+    }
+
+    /// Records a diagnostic if the check is enabled.
+    fn report(
+        &mut self,
+        check: LintCheck,
+        places: BTreeSet<PlaceRef>,
+        transitions: BTreeSet<TransitionRef>,
+        message: String,
+    ) {
+        if let Some(severity) = self.config.severity(check) {
+            self.diagnostics.push(Diagnostic {
+                check,
+                severity,
+                places,
+                transitions,
+                message,
+            });
+        }
+    }
+
+    fn is_enabled(&self, check: LintCheck) -> bool {
+        self.config.severity(check).is_some()
+    }
+
+    fn run(mut self) -> Result<Vec<Diagnostic>, Error> {
+        self.check_arcs();
+        self.check_labels();
+        self.check_parallel_nodes();
+        if self.is_enabled(LintCheck::UnreachablePlace)
+            || self.is_enabled(LintCheck::UnreachableTransition)
+        {
+            self.check_reachability()?;
+        }
+        self.diagnostics
+            .sort_by_key(|diagnostic| LintCheck::ALL.iter().position(|c| *c == diagnostic.check));
+        Ok(self.diagnostics)
+    }
+
+    fn check_arcs(&mut self) {
+        for place_ref in self.net.find_unconnected_places() {
+            let message = format!("place \"{}\" has no arcs", place_ref.label());
+            self.report(
+                LintCheck::IsolatedPlace,
+                BTreeSet::from([place_ref]),
+                BTreeSet::new(),
+                message,
+            );
+        }
+        for (transition_ref, transition) in self.net.transitions_iter() {
+            let has_inputs =
+                !transition.get_preset().is_empty() || !transition.get_extended_preset().is_empty();
+            let (consumes, produces) = (
+                !transition.get_preset().is_empty(),
+                !transition.get_postset().is_empty(),
+            );
+            let label = transition_ref.label();
+            let (check, message) = if !has_inputs && !produces {
+                (
+                    LintCheck::IsolatedTransition,
+                    format!("transition \"{label}\" has no arcs"),
+                )
+            } else if !consumes && produces {
+                (
+                    LintCheck::SourceTransition,
+                    format!("transition \"{label}\" produces tokens without consuming any"),
+                )
+            } else if consumes && !produces {
+                (
+                    LintCheck::SinkTransition,
+                    format!("transition \"{label}\" consumes tokens without producing any"),
+                )
+            } else {
+                continue;
+            };
+            self.report(
+                check,
+                BTreeSet::new(),
+                BTreeSet::from([transition_ref.clone()]),
+                message,
+            );
+        }
+    }
+
+    fn check_labels(&mut self) {
+        let mut by_label: BTreeMap<&str, (BTreeSet<PlaceRef>, BTreeSet<TransitionRef>)> =
+            BTreeMap::new();
+        for (place_ref, _) in self.net.places_iter() {
+            by_label
+                .entry(place_ref.label())
+                .or_default()
+                .0
+                .insert(place_ref.clone());
+        }
+        for (transition_ref, _) in self.net.transitions_iter() {
+            by_label
+                .entry(transition_ref.label())
+                .or_default()
+                .1
+                .insert(transition_ref.clone());
+        }
+
+        for (label, (places, transitions)) in by_label {
+            for format in [IdFormat::Pnml, IdFormat::Dot, IdFormat::Lola] {
+                if sanitize_id(label, format) != label {
+                    self.report(
+                        LintCheck::InvalidIdentifier(format),
+                        places.clone(),
+                        transitions.clone(),
+                        format!("label \"{label}\" is not a valid {format} identifier"),
+                    );
+                }
+            }
+            let count = places.len() + transitions.len();
+            if count > 1 {
+                self.report(
+                    LintCheck::DuplicateLabel,
+                    places,
+                    transitions,
+                    format!("label \"{label}\" is used by {count} nodes"),
+                );
+            }
+        }
+    }
+
+    /// Groups the nodes with the same arcs. Nodes without arcs are reported as isolated instead.
+    fn check_parallel_nodes(&mut self) {
+        let mut places: BTreeMap<_, BTreeSet<PlaceRef>> = BTreeMap::new();
+        for (place_ref, place) in self.net.places_iter() {
+            let arcs = (
+                place.get_preset(),
+                place.get_postset(),
+                place.get_extended_postset(),
+            );
+            if !(arcs.0.is_empty() && arcs.1.is_empty() && arcs.2.is_empty()) {
+                places.entry(arcs).or_default().insert(place_ref.clone());
+            }
+        }
+        for group in places.into_values().filter(|group| group.len() > 1) {
+            let message = format!("{} places have the same arcs", group.len());
+            self.report(LintCheck::ParallelPlaces, group, BTreeSet::new(), message);
+        }
+
+        let mut transitions: BTreeMap<_, BTreeSet<TransitionRef>> = BTreeMap::new();
+        for (transition_ref, transition) in self.net.transitions_iter() {
+            let arcs = (
+                transition.get_preset(),
+                transition.get_postset(),
+                transition.get_extended_preset(),
+            );
+            if !(arcs.0.is_empty() && arcs.1.is_empty() && arcs.2.is_empty()) {
+                transitions
+                    .entry(arcs)
+                    .or_default()
+                    .insert(transition_ref.clone());
+            }
+        }
+        for group in transitions.into_values().filter(|group| group.len() > 1) {
+            let message = format!("{} transitions have the same arcs", group.len());
+            self.report(
+                LintCheck::ParallelTransitions,
+                BTreeSet::new(),
+                group,
+                message,
+            );
+        }
+    }
+
+    fn check_reachability(&mut self) -> Result<(), Error> {
+        let graph = ReachabilityGraph::new_from(
+            self.net,
+            &self.net.initial_marking_vector(),
+            self.config.state_limit(),
+        )?;
+        if graph.state_limit_reached() {
+            let message = format!(
+                "more than {} markings are reachable, unreachable nodes were not searched",
+                self.config.state_limit()
+            );
+            for check in [
+                LintCheck::UnreachablePlace,
+                LintCheck::UnreachableTransition,
+            ] {
+                self.report(check, BTreeSet::new(), BTreeSet::new(), message.clone());
+            }
+            return Ok(());
+        }
+
+        let mut marked: BTreeSet<&PlaceRef> = BTreeSet::new();
+        let mut fired: BTreeSet<&TransitionRef> = BTreeSet::new();
+        for (state, marking) in graph.states_iter() {
+            marked.extend(
+                marking
+                    .iter()
+                    .filter(|(_, tokens)| **tokens > 0)
+                    .map(|(place_ref, _)| place_ref),
+            );
+            fired.extend(
+                graph
+                    .successors(state)
+                    .iter()
+                    .map(|(transition_ref, _)| transition_ref),
+            );
+        }
+        for (place_ref, _) in self.net.places_iter() {
+            if !marked.contains(place_ref) {
+                let message = format!(
+                    "place \"{}\" is never marked from the initial marking",
+                    place_ref.label()
+                );
+                self.report(
+                    LintCheck::UnreachablePlace,
+                    BTreeSet::from([place_ref.clone()]),
+                    BTreeSet::new(),
+                    message,
+                );
+            }
+        }
+        for (transition_ref, _) in self.net.transitions_iter() {
+            if !fired.contains(transition_ref) {
+                let message = format!(
+                    "transition \"{}\" can never fire from the initial marking",
+                    transition_ref.label()
+                );
+                self.report(
+                    LintCheck::UnreachableTransition,
+                    BTreeSet::new(),
+                    BTreeSet::from([transition_ref.clone()]),
+                    message,
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+impl PetriNet {
+    /// Runs the checks enabled in the configuration and returns the problems found,
+    /// grouped by check in the order of `LintCheck::ALL`.
+    ///
+    /// # Errors
+    ///
+    /// If the checks for unreachable nodes are enabled
+    /// and firing a transition causes an overflow in a place, then an error is returned.
+    pub fn lint(&self, config: &LintConfig) -> Result<Vec<Diagnostic>, Error> {
+        Linter::new(self, config).run()
+    }
+}
+
+#[cfg(test)]
+mod lint_tests {
+    use super::*;
+    use crate::net_creator::*;
+
+    fn checks(diagnostics: &[Diagnostic]) -> Vec<LintCheck> {
+        diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.check)
+            .collect()
+    }
+
+    #[test]
+    fn lint_marked_loop_has_no_diagnostics() {
+        let (mut net, place_ref, _) = create_net_loop_topology();
+        assert!(net.add_token(&place_ref, 1).is_ok());
+
+        assert!(net.lint(&LintConfig::new()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn lint_finds_isolated_nodes() {
+        let (net, place_refs, transition_refs) = create_basic_unconnected_net(1, 1);
+        let mut config = LintConfig::empty();
+        config.enable(LintCheck::IsolatedPlace);
+        config.enable(LintCheck::IsolatedTransition);
+        let diagnostics = net.lint(&config).unwrap();
+
+        assert_eq!(
+            checks(&diagnostics),
+            [LintCheck::IsolatedPlace, LintCheck::IsolatedTransition]
+        );
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert_eq!(
+            diagnostics[0].places,
+            BTreeSet::from([place_refs[0].clone()])
+        );
+        assert_eq!(
+            diagnostics[1].transitions,
+            BTreeSet::from([transition_refs[0].clone()])
+        );
+    }
+
+    #[test]
+    fn lint_finds_source_and_sink_transitions() {
+        let (mut net, place_refs, transition_refs) = create_net_chain_topology(2);
+        let source_ref = net.add_transition("Source");
+        assert!(
+            net.add_arc_transition_place(&source_ref, &place_refs[0])
+                .is_ok()
+        );
+        let sink_ref = net.add_transition("Sink");
+        assert!(
+            net.add_arc_place_transition(&place_refs[1], &sink_ref)
+                .is_ok()
+        );
+        let mut config = LintConfig::empty();
+        config.enable(LintCheck::SourceTransition);
+        config.enable(LintCheck::SinkTransition);
+        let diagnostics = net.lint(&config).unwrap();
+
+        assert_eq!(
+            checks(&diagnostics),
+            [LintCheck::SourceTransition, LintCheck::SinkTransition]
+        );
+        assert_eq!(diagnostics[0].transitions, BTreeSet::from([source_ref]));
+        assert_eq!(diagnostics[1].transitions, BTreeSet::from([sink_ref]));
+        assert_eq!(diagnostics[1].severity, Severity::Info);
+        assert!(
+            diagnostics
+                .iter()
+                .all(|diagnostic| !diagnostic.transitions.contains(&transition_refs[0]))
+        );
+    }
+
+    #[test]
+    fn lint_finds_duplicate_labels_across_node_kinds() {
+        let mut net = PetriNet::new();
+        let first_ref = net.add_place("x");
+        let second_ref = net.add_place("x");
+        let transition_ref = net.add_transition("x");
+        net.add_place("y");
+        let mut config = LintConfig::empty();
+        config.enable(LintCheck::DuplicateLabel);
+        let diagnostics = net.lint(&config).unwrap();
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].places,
+            BTreeSet::from([first_ref, second_ref])
+        );
+        assert_eq!(diagnostics[0].transitions, BTreeSet::from([transition_ref]));
+        assert_eq!(diagnostics[0].message, "label \"x\" is used by 3 nodes");
+    }
+
+    #[test]
+    fn lint_finds_invalid_identifiers_per_format() {
+        let mut net = PetriNet::new();
+        let place_ref = net.add_place("a-b");
+        net.add_place("valid_label");
+        let mut config = LintConfig::empty();
+        for format in [IdFormat::Pnml, IdFormat::Dot, IdFormat::Lola] {
+            config.enable(LintCheck::InvalidIdentifier(format));
+        }
+        let diagnostics = net.lint(&config).unwrap();
+
+        // The hyphen is allowed in PNML and LoLA identifiers, but not in DOT.
+        assert_eq!(
+            checks(&diagnostics),
+            [LintCheck::InvalidIdentifier(IdFormat::Dot)]
+        );
+        assert_eq!(diagnostics[0].places, BTreeSet::from([place_ref]));
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "info: label \"a-b\" is not a valid DOT identifier (invalid DOT identifier)"
+        );
+    }
+
+    #[test]
+    fn lint_finds_parallel_nodes() {
+        let mut net = PetriNet::new();
+        let p1 = net.add_place("P1");
+        let p2 = net.add_place("P2");
+        let t1 = net.add_transition("T1");
+        let t2 = net.add_transition("T2");
+        for place_ref in [&p1, &p2] {
+            for transition_ref in [&t1, &t2] {
+                assert!(
+                    net.add_arc_place_transition(place_ref, transition_ref)
+                        .is_ok()
+                );
+            }
+        }
+        let mut config = LintConfig::empty();
+        config.enable(LintCheck::ParallelPlaces);
+        config.enable(LintCheck::ParallelTransitions);
+        let diagnostics = net.lint(&config).unwrap();
+
+        assert_eq!(
+            checks(&diagnostics),
+            [LintCheck::ParallelPlaces, LintCheck::ParallelTransitions]
+        );
+        assert_eq!(diagnostics[0].places, BTreeSet::from([p1, p2]));
+        assert_eq!(diagnostics[1].transitions, BTreeSet::from([t1, t2]));
+    }
+
+    #[test]
+    fn lint_finds_unreachable_nodes_from_initial_marking() {
+        let (mut net, place_refs, transition_refs) = create_net_chain_topology(3);
+        assert!(net.add_token(&place_refs[1], 1).is_ok());
+        assert!(net.set_initial_marking(&place_refs[1], 0).is_ok());
+        assert!(net.add_token(&place_refs[2], 1).is_ok());
+        let mut config = LintConfig::empty();
+        config.enable(LintCheck::UnreachablePlace);
+        config.enable(LintCheck::UnreachableTransition);
+        let diagnostics = net.lint(&config).unwrap();
+
+        // Only P3 is marked initially, so nothing can fire.
+        assert_eq!(
+            checks(&diagnostics),
+            [
+                LintCheck::UnreachablePlace,
+                LintCheck::UnreachablePlace,
+                LintCheck::UnreachableTransition,
+                LintCheck::UnreachableTransition,
+            ]
+        );
+        let places: BTreeSet<PlaceRef> = diagnostics
+            .iter()
+            .flat_map(|diagnostic| diagnostic.places.iter().cloned())
+            .collect();
+        assert_eq!(
+            places,
+            BTreeSet::from([place_refs[0].clone(), place_refs[1].clone()])
+        );
+        let transitions: BTreeSet<TransitionRef> = diagnostics
+            .iter()
+            .flat_map(|diagnostic| diagnostic.transitions.iter().cloned())
+            .collect();
+        assert_eq!(transitions, transition_refs.into_iter().collect());
+    }
+
+    #[test]
+    fn lint_state_limit_skips_unreachable_nodes() {
+        let (mut net, place_ref, _) = create_net_loop_topology();
+        let generator_ref = net.add_transition("Generator");
+        assert!(
+            net.add_arc_transition_place(&generator_ref, &place_ref)
+                .is_ok()
+        );
+        let dead_ref = net.add_transition("Dead");
+        let empty_ref = net.add_place("Empty");
+        assert!(net.add_arc_place_transition(&empty_ref, &dead_ref).is_ok());
+        let mut config = LintConfig::empty();
+        config.enable(LintCheck::UnreachableTransition);
+        config.set_state_limit(10);
+        let diagnostics = net.lint(&config).unwrap();
+
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].transitions.is_empty());
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn lint_config_changes_severity_and_disables_checks() {
+        let (net, _, _) = create_basic_unconnected_net(1, 0);
+        let mut config = LintConfig::new();
+        config.set_severity(LintCheck::IsolatedPlace, Severity::Error);
+        config.disable(LintCheck::UnreachablePlace);
+        let diagnostics = net.lint(&config).unwrap();
+
+        assert_eq!(checks(&diagnostics), [LintCheck::IsolatedPlace]);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert_eq!(config.severity(LintCheck::UnreachablePlace), None);
+        config.enable(LintCheck::IsolatedPlace);
+        assert_eq!(
+            config.severity(LintCheck::IsolatedPlace),
+            Some(Severity::Error)
+        );
+    }
+}
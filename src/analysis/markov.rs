@@ -0,0 +1,819 @@
+//! # Continuous-time Markov chain
+//!
+//! Builds the continuous-time Markov chain (CTMC) of a generalized stochastic Petri net (GSPN)
+//! from its reachability graph and solves it for the steady-state distribution.
+//! The chain starts from the current marking of the net,
+//! `MarkovChain::new_from` starts from another marking instead.
+//!
+//! Markings that enable an immediate transition are vanishing: no time is spent in them.
+//! They are removed from the chain by following the immediate transitions,
+//! chosen with probabilities proportional to their weights, until a tangible marking is reached.
+//! Immediate transitions may fire in cycles as long as the cycle can be left;
+//! only a set of vanishing markings that can never be left is an error.
+
+use crate::analysis::reachability::ReachabilityGraph;
+use crate::error::Error;
+use crate::petri_net::{PetriNet, PlaceRef, StochasticTiming, TransitionRef};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+/// The tangible states of the reachability graph reached from a state by firing immediate transitions,
+/// together with their probabilities and the expected number of firings of each immediate transition.
+#[derive(Clone, Default)]
+struct Absorption {
+    states: BTreeMap<usize, f64>,
+    firings: BTreeMap<TransitionRef, f64>,
+}
+
+impl Absorption {
+    fn add_scaled(&mut self, other: &Self, factor: f64) {
+        for (state, probability) in &other.states {
+            *self.states.entry(*state).or_default() += factor * probability;
+        }
+        for (transition_ref, firings) in &other.firings {
+            *self.firings.entry(transition_ref.clone()).or_default() += factor * firings;
+        }
+    }
+}
+
+/// The immediate transitions enabled in a vanishing state,
+/// with the state reached by each one and the probability of firing it.
+type ImmediateEdges = BTreeMap<usize, Vec<(TransitionRef, usize, f64)>>;
+
+/// Computes the absorption of every vanishing state of a reachability graph.
+///
+/// The vanishing states are grouped into strongly connected components of the graph of immediate firings,
+/// which are solved one at a time, starting with the components from which no other component is reachable.
+/// Within a component, the absorptions are the solution of a linear system,
+/// since the immediate transitions may fire in cycles before leaving the component.
+struct VanishingEliminator {
+    timings: BTreeMap<TransitionRef, StochasticTiming>,
+    resolved: BTreeMap<usize, Absorption>,
+}
+
+impl VanishingEliminator {
+    fn new(net: &PetriNet, graph: &ReachabilityGraph) -> Result<Self, Error> {
+        let timings = net
+            .transitions_iter()
+            .map(|(transition_ref, transition)| {
+                (transition_ref.clone(), transition.stochastic_timing())
+            })
+            .collect();
+        let mut eliminator = Self {
+            timings,
+            resolved: BTreeMap::new(),
+        };
+        let edges: ImmediateEdges = graph
+            .states_iter()
+            .filter_map(|(state, _)| {
+                let state_edges = eliminator.immediate_edges(graph, state);
+                (!state_edges.is_empty()).then_some((state, state_edges))
+            })
+            .collect();
+        for component in strongly_connected_components(&edges) {
+            eliminator.resolve_component(&component, &edges)?;
+        }
+        Ok(eliminator)
+    }
+
+    fn timing(&self, transition_ref: &TransitionRef) -> StochasticTiming {
+        self.timings
+            .get(transition_ref)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Gets the immediate transitions enabled in the state, with their probabilities.
+    fn immediate_edges(
+        &self,
+        graph: &ReachabilityGraph,
+        state: usize,
+    ) -> Vec<(TransitionRef, usize, f64)> {
+        let mut edges: Vec<(TransitionRef, usize, f64)> = graph
+            .successors(state)
+            .iter()
+            .filter_map(
+                |(transition_ref, next_state)| match self.timing(transition_ref) {
+                    StochasticTiming::Immediate(weight) => {
+                        Some((transition_ref.clone(), *next_state, weight))
+                    }
+                    StochasticTiming::Exponential(_) => None,
+                },
+            )
+            .collect();
+        let total_weight: f64 = edges.iter().map(|(_, _, weight)| weight).sum();
+        for (_, _, weight) in &mut edges {
+            *weight /= total_weight;
+        }
+        edges
+    }
+
+    /// Gets the tangible states reached from a state by firing immediate transitions.
+    /// A tangible state is only reached by itself.
+    fn absorb(&self, state: usize) -> Absorption {
+        self.resolved
+            .get(&state)
+            .cloned()
+            .unwrap_or_else(|| Absorption {
+                states: BTreeMap::from([(state, 1.0)]),
+                firings: BTreeMap::new(),
+            })
+    }
+
+    /// Solves `x = local + P x` for the states of the component,
+    /// where `P` holds the probabilities of the immediate firings within the component
+    /// and `local` the contribution of the firings that leave it, which are already resolved.
+    fn resolve_component(
+        &mut self,
+        component: &[usize],
+        edges: &ImmediateEdges,
+    ) -> Result<(), Error> {
+        let positions: BTreeMap<usize, usize> = component
+            .iter()
+            .enumerate()
+            .map(|(position, state)| (*state, position))
+            .collect();
+        let size = component.len();
+        let mut matrix = vec![vec![0.0; size]; size];
+        let mut local = vec![Absorption::default(); size];
+        let mut has_exit = false;
+        for (position, state) in component.iter().enumerate() {
+            matrix[position][position] = 1.0;
+            for (transition_ref, next_state, probability) in &edges[state] {
+                *local[position]
+                    .firings
+                    .entry(transition_ref.clone())
+                    .or_default() += probability;
+                if let Some(next_position) = positions.get(next_state) {
+                    matrix[position][*next_position] -= probability;
+                } else {
+                    has_exit = true;
+                    local[position].add_scaled(&self.absorb(*next_state), *probability);
+                }
+            }
+        }
+        // Without an exit, the immediate transitions fire forever inside the component.
+        let trap = || Error::TimelessTrap(edges[&component[0]][0].0.clone());
+        if !has_exit {
+            return Err(trap());
+        }
+        let inverse = invert(matrix).ok_or_else(trap)?;
+        for (position, state) in component.iter().enumerate() {
+            let mut absorption = Absorption::default();
+            for (other_position, factor) in inverse[position].iter().enumerate() {
+                if *factor != 0.0 {
+                    absorption.add_scaled(&local[other_position], *factor);
+                }
+            }
+            self.resolved.insert(*state, absorption);
+        }
+        Ok(())
+    }
+}
+
+/// Finds the strongly connected components of the graph of immediate firings with Tarjan's algorithm.
+/// Every component comes after the components reachable from it.
+/// The depth-first search keeps its own stack, so long chains of vanishing states cannot overflow the call stack.
+fn strongly_connected_components(edges: &ImmediateEdges) -> Vec<Vec<usize>> {
+    let mut search = TarjanSearch::default();
+    for root in edges.keys() {
+        if !search.indices.contains_key(root) {
+            search.run(*root, edges);
+        }
+    }
+    search.components
+}
+
+/// The state of the depth-first search of Tarjan's algorithm.
+#[derive(Default)]
+struct TarjanSearch {
+    indices: BTreeMap<usize, usize>,
+    lowlinks: BTreeMap<usize, usize>,
+    stack: Vec<usize>,
+    on_stack: BTreeSet<usize>,
+    /// Every frame holds a state and the position of the next edge to follow.
+    frames: Vec<(usize, usize)>,
+    components: Vec<Vec<usize>>,
+}
+
+impl TarjanSearch {
+    fn visit(&mut self, state: usize) {
+        let index = self.indices.len();
+        self.indices.insert(state, index);
+        self.lowlinks.insert(state, index);
+        self.stack.push(state);
+        self.on_stack.insert(state);
+        self.frames.push((state, 0));
+    }
+
+    fn lower(&mut self, state: usize, value: usize) {
+        if let Some(lowlink) = self.lowlinks.get_mut(&state) {
+            *lowlink = (*lowlink).min(value);
+        }
+    }
+
+    fn run(&mut self, root: usize, edges: &ImmediateEdges) {
+        self.visit(root);
+        while let Some((state, position)) = self.frames.last().copied() {
+            if let Some((_, next_state, _)) = edges[&state].get(position) {
+                if let Some(frame) = self.frames.last_mut() {
+                    frame.1 += 1;
+                }
+                if !edges.contains_key(next_state) {
+                    continue;
+                }
+                match self.indices.get(next_state) {
+                    Some(next_index) if self.on_stack.contains(next_state) => {
+                        self.lower(state, *next_index);
+                    }
+                    Some(_) => {}
+                    None => self.visit(*next_state),
+                }
+                continue;
+            }
+            self.frames.pop();
+            let lowlink = self.lowlinks[&state];
+            if let Some((parent, _)) = self.frames.last().copied() {
+                self.lower(parent, lowlink);
+            }
+            if lowlink == self.indices[&state] {
+                let mut component = Vec::new();
+                while let Some(member) = self.stack.pop() {
+                    self.on_stack.remove(&member);
+                    component.push(member);
+                    if member == state {
+                        break;
+                    }
+                }
+                self.components.push(component);
+            }
+        }
+    }
+}
+
+/// Inverts a square matrix by Gauss-Jordan elimination with partial pivoting.
+/// Returns `None` if the matrix is singular.
+fn invert(mut matrix: Vec<Vec<f64>>) -> Option<Vec<Vec<f64>>> {
+    let size = matrix.len();
+    let mut inverse: Vec<Vec<f64>> = (0..size)
+        .map(|row| {
+            let mut unit = vec![0.0; size];
+            unit[row] = 1.0;
+            unit
+        })
+        .collect();
+    for column in 0..size {
+        let pivot = (column..size).max_by(|first, second| {
+            matrix[*first][column]
+                .abs()
+                .total_cmp(&matrix[*second][column].abs())
+        })?;
+        if matrix[pivot][column].abs() < f64::EPSILON {
+            return None;
+        }
+        matrix.swap(column, pivot);
+        inverse.swap(column, pivot);
+        let divisor = matrix[column][column];
+        for value in &mut matrix[column] {
+            *value /= divisor;
+        }
+        for value in &mut inverse[column] {
+            *value /= divisor;
+        }
+        for row in 0..size {
+            let factor = matrix[row][column];
+            if row == column || factor == 0.0 {
+                continue;
+            }
+            for index in 0..size {
+                matrix[row][index] -= factor * matrix[column][index];
+                inverse[row][index] -= factor * inverse[column][index];
+            }
+        }
+    }
+    Some(inverse)
+}
+
+/// The continuous-time Markov chain of a generalized stochastic Petri net.
+///
+/// Every state is a tangible marking identified by its index.
+/// The initial marking of the net may be vanishing,
+/// the chain then starts in a probability distribution over the tangible markings.
+pub struct MarkovChain {
+    markings: Vec<BTreeMap<PlaceRef, usize>>,
+    initial_distribution: BTreeMap<usize, f64>,
+    rates: Vec<BTreeMap<usize, f64>>,
+    throughput_rates: Vec<BTreeMap<TransitionRef, f64>>,
+    transition_refs: Vec<TransitionRef>,
+}
+
+impl MarkovChain {
+    /// Builds the Markov chain of the net starting from its current marking.
+    ///
+    /// At most `state_limit` markings, tangible or vanishing, are explored.
+    ///
+    /// # Errors
+    ///
+    /// If firing a transition causes an overflow in a place, then an error is returned.
+    /// If the state limit is reached before exploring every reachable marking, then an error is returned.
+    /// If immediate transitions can fire forever without reaching a tangible marking,
+    /// then an error is returned.
+    pub fn new(net: &PetriNet, state_limit: usize) -> Result<Self, Error> {
+        Self::new_from(net, &net.marking_vector(), state_limit)
+    }
+
+    /// Builds the Markov chain of the net starting from the given marking,
+    /// e.g. from `PetriNet::initial_marking_vector`.
+    /// Places missing from the marking are considered to hold no tokens.
+    ///
+    /// # Errors
+    ///
+    /// See `new`.
+    pub fn new_from(
+        net: &PetriNet,
+        marking: &BTreeMap<PlaceRef, usize>,
+        state_limit: usize,
+    ) -> Result<Self, Error> {
+        let graph = ReachabilityGraph::new_from(net, marking, state_limit)?;
+        if graph.state_limit_reached() {
+            return Err(Error::StateLimitReached(state_limit));
+        }
+        let eliminator = VanishingEliminator::new(net, &graph)?;
+        let mut chain = Self {
+            markings: Vec::new(),
+            initial_distribution: BTreeMap::new(),
+            rates: Vec::new(),
+            throughput_rates: Vec::new(),
+            transition_refs: net.transitions_iter().map(|(t, _)| t.clone()).collect(),
+        };
+
+        let mut state_index: BTreeMap<usize, usize> = BTreeMap::new();
+        let mut queue: VecDeque<usize> = VecDeque::new();
+        for (graph_state, probability) in eliminator.absorb(0).states {
+            let state =
+                chain.find_or_insert_state(&graph, graph_state, &mut state_index, &mut queue);
+            chain.initial_distribution.insert(state, probability);
+        }
+
+        while let Some(graph_state) = queue.pop_front() {
+            let state = state_index[&graph_state];
+            for (transition_ref, next_graph_state) in graph.successors(graph_state) {
+                // Tangible markings enable no immediate transition.
+                let rate = eliminator.timing(transition_ref).value();
+                let absorption = eliminator.absorb(*next_graph_state);
+                for (target_graph_state, probability) in absorption.states {
+                    let target = chain.find_or_insert_state(
+                        &graph,
+                        target_graph_state,
+                        &mut state_index,
+                        &mut queue,
+                    );
+                    if target != state {
+                        *chain.rates[state].entry(target).or_default() += rate * probability;
+                    }
+                }
+                let throughput_rates = &mut chain.throughput_rates[state];
+                *throughput_rates.entry(transition_ref.clone()).or_default() += rate;
+                for (immediate_ref, firings) in absorption.firings {
+                    *throughput_rates.entry(immediate_ref).or_default() += rate * firings;
+                }
+            }
+        }
+        Ok(chain)
+    }
+
+    fn find_or_insert_state(
+        &mut self,
+        graph: &ReachabilityGraph,
+        graph_state: usize,
+        state_index: &mut BTreeMap<usize, usize>,
+        queue: &mut VecDeque<usize>,
+    ) -> usize {
+        if let Some(state) = state_index.get(&graph_state) {
+            return *state;
+        }
+        let state = self.markings.len();
+        let marking = graph.marking(graph_state).cloned().unwrap_or_default();
+        self.markings.push(marking);
+        self.rates.push(BTreeMap::new());
+        self.throughput_rates.push(BTreeMap::new());
+        state_index.insert(graph_state, state);
+        queue.push_back(graph_state);
+        state
+    }
+
+    /// Gets the number of tangible states in the chain.
+    #[inline]
+    #[must_use]
+    pub const fn get_cardinality_states(&self) -> usize {
+        self.markings.len()
+    }
+
+    /// Gets the marking of a state in the chain.
+    /// Returns `None` if the state does not exist.
+    #[must_use]
+    pub fn marking(&self, state: usize) -> Option<&BTreeMap<PlaceRef, usize>> {
+        self.markings.get(state)
+    }
+
+    /// Gets the probability of starting in each state.
+    /// States that are not in the map have probability zero.
+    #[must_use]
+    pub const fn initial_distribution(&self) -> &BTreeMap<usize, f64> {
+        &self.initial_distribution
+    }
+
+    /// Gets the transition rates from a state to the other states of the chain.
+    /// Returns `None` if the state does not exist.
+    #[must_use]
+    pub fn rates(&self, state: usize) -> Option<&BTreeMap<usize, f64>> {
+        self.rates.get(state)
+    }
+
+    /// Computes the steady-state distribution of the chain by power iteration on the uniformized chain,
+    /// starting from the initial distribution.
+    /// If the chain has several recurrent classes, the result is the limit reached from the initial marking.
+    ///
+    /// The iteration stops once no probability changes by more than `tolerance` in one step.
+    ///
+    /// # Errors
+    ///
+    /// If the iteration does not converge within `max_iterations` steps, then an error is returned.
+    /// If a place holds more tokens than can be represented exactly as a float, then an error is returned.
+    pub fn steady_state(
+        &self,
+        tolerance: f64,
+        max_iterations: usize,
+    ) -> Result<SteadyState, Error> {
+        let mut probabilities = vec![0.0; self.markings.len()];
+        for (state, probability) in &self.initial_distribution {
+            probabilities[*state] = *probability;
+        }
+        let exit_rates: Vec<f64> = self
+            .rates
+            .iter()
+            .map(|rates| rates.values().sum())
+            .collect();
+        let max_exit_rate = exit_rates.iter().copied().fold(0.0, f64::max);
+        if max_exit_rate <= 0.0 {
+            return self.summarize(probabilities, 0);
+        }
+        // A uniformization rate strictly above every exit rate keeps a self-loop in every state,
+        // which makes the uniformized chain aperiodic.
+        let uniformization_rate = 1.1 * max_exit_rate;
+
+        for iteration in 1..=max_iterations {
+            let mut next: Vec<f64> = probabilities
+                .iter()
+                .zip(&exit_rates)
+                .map(|(probability, exit_rate)| {
+                    probability * (1.0 - exit_rate / uniformization_rate)
+                })
+                .collect();
+            for (state, rates) in self.rates.iter().enumerate() {
+                for (target, rate) in rates {
+                    next[*target] += probabilities[state] * rate / uniformization_rate;
+                }
+            }
+            let difference = next
+                .iter()
+                .zip(&probabilities)
+                .map(|(next, previous)| (next - previous).abs())
+                .fold(0.0, f64::max);
+            probabilities = next;
+            if difference <= tolerance {
+                return self.summarize(probabilities, iteration);
+            }
+        }
+        Err(Error::NoConvergence(max_iterations))
+    }
+
+    fn summarize(&self, probabilities: Vec<f64>, iterations: usize) -> Result<SteadyState, Error> {
+        let mut mean_tokens: BTreeMap<PlaceRef, f64> = BTreeMap::new();
+        let mut throughput: BTreeMap<TransitionRef, f64> = self
+            .transition_refs
+            .iter()
+            .map(|transition_ref| (transition_ref.clone(), 0.0))
+            .collect();
+        for (state, probability) in probabilities.iter().enumerate() {
+            for (place_ref, tokens) in &self.markings[state] {
+                let tokens = u32::try_from(*tokens).map_err(|_| Error::ArithmeticOverflow)?;
+                *mean_tokens.entry(place_ref.clone()).or_default() +=
+                    probability * f64::from(tokens);
+            }
+            for (transition_ref, rate) in &self.throughput_rates[state] {
+                *throughput.entry(transition_ref.clone()).or_default() += probability * rate;
+            }
+        }
+        let probabilities = self.markings.iter().cloned().zip(probabilities).collect();
+
+        Ok(SteadyState {
+            probabilities,
+            mean_tokens,
+            throughput,
+            iterations,
+        })
+    }
+}
+
+/// The steady-state distribution of a Markov chain and the measures derived from it.
+pub struct SteadyState {
+    probabilities: BTreeMap<BTreeMap<PlaceRef, usize>, f64>,
+    mean_tokens: BTreeMap<PlaceRef, f64>,
+    throughput: BTreeMap<TransitionRef, f64>,
+    iterations: usize,
+}
+
+impl SteadyState {
+    /// Gets the steady-state probability of a marking.
+    /// Vanishing and unreachable markings have probability zero.
+    #[must_use]
+    pub fn probability(&self, marking: &BTreeMap<PlaceRef, usize>) -> f64 {
+        self.probabilities.get(marking).copied().unwrap_or_default()
+    }
+
+    /// Returns an iterator over the tangible markings and their steady-state probabilities.
+    pub fn probabilities_iter(&self) -> impl Iterator<Item = (&BTreeMap<PlaceRef, usize>, f64)> {
+        self.probabilities
+            .iter()
+            .map(|(marking, probability)| (marking, *probability))
+    }
+
+    /// Gets the mean number of tokens in each place.
+    #[must_use]
+    pub const fn mean_tokens(&self) -> &BTreeMap<PlaceRef, f64> {
+        &self.mean_tokens
+    }
+
+    /// Gets the mean number of firings per time unit of each transition.
+    #[must_use]
+    pub const fn throughput(&self) -> &BTreeMap<TransitionRef, f64> {
+        &self.throughput
+    }
+
+    /// Gets the number of iterations that the solver needed to converge.
+    #[must_use]
+    pub const fn iterations(&self) -> usize {
+        self.iterations
+    }
+}
+
+impl PetriNet {
+    /// Builds the continuous-time Markov chain of the net starting from its current marking,
+    /// using the stochastic timing of every transition.
+    ///
+    /// # Errors
+    ///
+    /// If firing a transition causes an overflow in a place, then an error is returned.
+    /// If the state limit is reached before exploring every reachable marking, then an error is returned.
+    /// If immediate transitions can fire forever without reaching a tangible marking,
+    /// then an error is returned.
+    pub fn markov_chain(&self, state_limit: usize) -> Result<MarkovChain, Error> {
+        MarkovChain::new(self, state_limit)
+    }
+}
+
+#[cfg(test)]
+mod markov_tests {
+    use super::*;
+    use crate::net_creator::*;
+
+    const TOLERANCE: f64 = 1e-12;
+    const MAX_ITERATIONS: usize = 100_000;
+
+    fn assert_close(value: f64, expected: f64) {
+        assert!(
+            (value - expected).abs() < 1e-6,
+            "expected {expected}, got {value}"
+        );
+    }
+
+    fn create_two_state_net() -> (PetriNet, Vec<PlaceRef>, Vec<TransitionRef>) {
+        let (mut net, place_refs, mut transition_refs) = create_net_chain_topology(2);
+        let back_ref = net.add_transition("T2");
+        assert!(
+            net.add_arc_place_transition(&place_refs[1], &back_ref)
+                .is_ok()
+        );
+        assert!(
+            net.add_arc_transition_place(&back_ref, &place_refs[0])
+                .is_ok()
+        );
+        transition_refs.push(back_ref);
+        assert!(net.add_token(&place_refs[0], 1).is_ok());
+        let timing = StochasticTiming::Exponential(2.0);
+        assert!(
+            net.set_stochastic_timing(&transition_refs[0], timing)
+                .is_ok()
+        );
+        let timing = StochasticTiming::Exponential(3.0);
+        assert!(
+            net.set_stochastic_timing(&transition_refs[1], timing)
+                .is_ok()
+        );
+        (net, place_refs, transition_refs)
+    }
+
+    #[test]
+    fn markov_chain_two_states_steady_state() {
+        let (net, place_refs, transition_refs) = create_two_state_net();
+        let chain = net.markov_chain(10).unwrap();
+        let steady_state = chain.steady_state(TOLERANCE, MAX_ITERATIONS).unwrap();
+
+        assert_eq!(chain.get_cardinality_states(), 2);
+        assert_close(steady_state.probability(&net.marking_vector()), 0.6);
+        assert_close(steady_state.mean_tokens()[&place_refs[0]], 0.6);
+        assert_close(steady_state.mean_tokens()[&place_refs[1]], 0.4);
+        assert_close(steady_state.throughput()[&transition_refs[0]], 1.2);
+        assert_close(steady_state.throughput()[&transition_refs[1]], 1.2);
+        let total: f64 = steady_state.probabilities_iter().map(|(_, p)| p).sum();
+        assert_close(total, 1.0);
+    }
+
+    #[test]
+    fn markov_chain_removes_vanishing_markings() {
+        let mut net = PetriNet::new();
+        let p0 = net.add_place("P0");
+        let p1 = net.add_place("P1");
+        let p2 = net.add_place("P2");
+        let p3 = net.add_place("P3");
+        let t0 = net.add_transition("T0");
+        let i1 = net.add_transition("I1");
+        let i2 = net.add_transition("I2");
+        let t2 = net.add_transition("T2");
+        let t3 = net.add_transition("T3");
+        assert!(net.add_arc_place_transition(&p0, &t0).is_ok());
+        assert!(net.add_arc_transition_place(&t0, &p1).is_ok());
+        assert!(net.add_arc_place_transition(&p1, &i1).is_ok());
+        assert!(net.add_arc_transition_place(&i1, &p2).is_ok());
+        assert!(net.add_arc_place_transition(&p1, &i2).is_ok());
+        assert!(net.add_arc_transition_place(&i2, &p3).is_ok());
+        assert!(net.add_arc_place_transition(&p2, &t2).is_ok());
+        assert!(net.add_arc_transition_place(&t2, &p0).is_ok());
+        assert!(net.add_arc_place_transition(&p3, &t3).is_ok());
+        assert!(net.add_arc_transition_place(&t3, &p0).is_ok());
+        assert!(
+            net.set_stochastic_timing(&i1, StochasticTiming::Immediate(1.0))
+                .is_ok()
+        );
+        assert!(
+            net.set_stochastic_timing(&i2, StochasticTiming::Immediate(3.0))
+                .is_ok()
+        );
+        assert!(net.add_token(&p0, 1).is_ok());
+
+        let chain = net.markov_chain(10).unwrap();
+        let steady_state = chain.steady_state(TOLERANCE, MAX_ITERATIONS).unwrap();
+
+        assert_eq!(chain.get_cardinality_states(), 3);
+        assert_close(steady_state.mean_tokens()[&p0], 0.5);
+        assert_close(steady_state.mean_tokens()[&p1], 0.0);
+        assert_close(steady_state.mean_tokens()[&p2], 0.125);
+        assert_close(steady_state.mean_tokens()[&p3], 0.375);
+        assert_close(steady_state.throughput()[&t0], 0.5);
+        assert_close(steady_state.throughput()[&i1], 0.125);
+        assert_close(steady_state.throughput()[&i2], 0.375);
+    }
+
+    #[test]
+    fn markov_chain_vanishing_initial_marking_is_distributed() {
+        let mut net = PetriNet::new();
+        let p0 = net.add_place("P0");
+        let p1 = net.add_place("P1");
+        let p2 = net.add_place("P2");
+        let i1 = net.add_transition("I1");
+        let i2 = net.add_transition("I2");
+        assert!(net.add_arc_place_transition(&p0, &i1).is_ok());
+        assert!(net.add_arc_transition_place(&i1, &p1).is_ok());
+        assert!(net.add_arc_place_transition(&p0, &i2).is_ok());
+        assert!(net.add_arc_transition_place(&i2, &p2).is_ok());
+        assert!(
+            net.set_stochastic_timing(&i1, StochasticTiming::Immediate(1.0))
+                .is_ok()
+        );
+        assert!(
+            net.set_stochastic_timing(&i2, StochasticTiming::Immediate(1.0))
+                .is_ok()
+        );
+        assert!(net.add_token(&p0, 1).is_ok());
+
+        let chain = net.markov_chain(10).unwrap();
+        let steady_state = chain.steady_state(TOLERANCE, MAX_ITERATIONS).unwrap();
+
+        assert_eq!(chain.get_cardinality_states(), 2);
+        assert_eq!(chain.initial_distribution().len(), 2);
+        assert_close(steady_state.mean_tokens()[&p1], 0.5);
+        assert_close(steady_state.mean_tokens()[&p2], 0.5);
+        assert_close(steady_state.throughput()[&i1], 0.0);
+        assert_eq!(steady_state.iterations(), 0);
+    }
+
+    #[test]
+    fn markov_chain_immediate_cycle_with_exit_is_eliminated() {
+        let mut net = PetriNet::new();
+        let p0 = net.add_place("P0");
+        let p1 = net.add_place("P1");
+        let p2 = net.add_place("P2");
+        let start = net.add_place("Start");
+        let t0 = net.add_transition("T0");
+        let i1 = net.add_transition("I1");
+        let i2 = net.add_transition("I2");
+        let i3 = net.add_transition("I3");
+        let t4 = net.add_transition("T4");
+        // T0 starts the cycle P1 -I2-> P0 -I1-> P1, which is left through I3.
+        assert!(net.add_arc_place_transition(&start, &t0).is_ok());
+        assert!(net.add_arc_transition_place(&t0, &p1).is_ok());
+        assert!(net.add_arc_place_transition(&p1, &i2).is_ok());
+        assert!(net.add_arc_transition_place(&i2, &p0).is_ok());
+        assert!(net.add_arc_place_transition(&p0, &i1).is_ok());
+        assert!(net.add_arc_transition_place(&i1, &p1).is_ok());
+        assert!(net.add_arc_place_transition(&p1, &i3).is_ok());
+        assert!(net.add_arc_transition_place(&i3, &p2).is_ok());
+        assert!(net.add_arc_place_transition(&p2, &t4).is_ok());
+        assert!(net.add_arc_transition_place(&t4, &start).is_ok());
+        for immediate_ref in [&i1, &i2, &i3] {
+            let timing = StochasticTiming::Immediate(1.0);
+            assert!(net.set_stochastic_timing(immediate_ref, timing).is_ok());
+        }
+        assert!(net.add_token(&start, 1).is_ok());
+
+        let chain = net.markov_chain(10).unwrap();
+        let steady_state = chain.steady_state(TOLERANCE, MAX_ITERATIONS).unwrap();
+
+        assert_eq!(chain.get_cardinality_states(), 2);
+        assert_close(steady_state.mean_tokens()[&start], 0.5);
+        assert_close(steady_state.mean_tokens()[&p2], 0.5);
+        assert_close(steady_state.mean_tokens()[&p0], 0.0);
+        // From P1, I2 and I1 fire once on average before I3 leaves the cycle.
+        assert_close(steady_state.throughput()[&t0], 0.5);
+        assert_close(steady_state.throughput()[&i1], 0.5);
+        assert_close(steady_state.throughput()[&i2], 0.5);
+        assert_close(steady_state.throughput()[&i3], 0.5);
+    }
+
+    #[test]
+    fn markov_chain_long_vanishing_chain_is_eliminated() {
+        let length = 100_000;
+        let mut net = PetriNet::new();
+        let start = net.add_place("Start");
+        let pending = net.add_place("Pending");
+        let done = net.add_place("Done");
+        let produce = net.add_transition("Produce");
+        let drain = net.add_transition("Drain");
+        let restart = net.add_transition("Restart");
+        // Drain empties Pending one token at a time, so every intermediate marking is vanishing.
+        assert!(net.add_arc_place_transition(&start, &produce).is_ok());
+        assert!(
+            net.add_weighted_arc_transition_place(&produce, &pending, length)
+                .is_ok()
+        );
+        assert!(net.add_arc_place_transition(&pending, &drain).is_ok());
+        assert!(net.add_arc_transition_place(&drain, &done).is_ok());
+        assert!(
+            net.add_weighted_arc_place_transition(&done, &restart, length)
+                .is_ok()
+        );
+        assert!(net.add_arc_transition_place(&restart, &start).is_ok());
+        let timing = StochasticTiming::Immediate(1.0);
+        assert!(net.set_stochastic_timing(&drain, timing).is_ok());
+        assert!(net.add_token(&start, 1).is_ok());
+
+        let chain = net.markov_chain(2 * length).unwrap();
+
+        assert_eq!(chain.get_cardinality_states(), 2);
+    }
+
+    #[test]
+    fn markov_chain_immediate_cycle_returns_error() {
+        let (mut net, _, transition_refs) = create_two_state_net();
+        for transition_ref in &transition_refs {
+            let timing = StochasticTiming::Immediate(1.0);
+            assert!(net.set_stochastic_timing(transition_ref, timing).is_ok());
+        }
+        let result = net.markov_chain(10);
+
+        assert!(matches!(result, Err(Error::TimelessTrap(_))));
+    }
+
+    #[test]
+    fn markov_chain_state_limit_returns_error() {
+        let mut net = PetriNet::new();
+        let place_ref = net.add_place("P1");
+        let transition_ref = net.add_transition("T1");
+        assert!(
+            net.add_arc_transition_place(&transition_ref, &place_ref)
+                .is_ok()
+        );
+        let result = net.markov_chain(10);
+
+        assert!(matches!(result, Err(Error::StateLimitReached(10))));
+    }
+
+    #[test]
+    fn markov_chain_no_convergence_returns_error() {
+        let (net, _, _) = create_two_state_net();
+        let chain = net.markov_chain(10).unwrap();
+        let result = chain.steady_state(0.0, 3);
+
+        assert!(matches!(result, Err(Error::NoConvergence(3))));
+    }
+}
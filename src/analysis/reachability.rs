@@ -0,0 +1,297 @@
+//! # Reachability graph
+//!
+//! Explores every marking reachable from the current marking of a net
+//! by firing the enabled transitions in breadth-first order.
+//! `ReachabilityGraph::new_from` starts from another marking instead,
+//! e.g. from `PetriNet::initial_marking_vector` after transitions were fired.
+//! The markings are stored explicitly using the same representation as `PetriNet::marking_vector`.
+
+use crate::error::Error;
+use crate::petri_net::{PetriNet, PlaceRef, TransitionRef};
+use std::collections::{BTreeMap, VecDeque};
+
+/// The reachability graph of a Petri net.
+///
+/// Every state is identified by its index, the initial marking is always the state `0`.
+/// Edges are labelled with the transition whose firing leads from one state to the other.
+pub struct ReachabilityGraph {
+    markings: Vec<BTreeMap<PlaceRef, usize>>,
+    state_index: BTreeMap<BTreeMap<PlaceRef, usize>, usize>,
+    edges: Vec<Vec<(TransitionRef, usize)>>,
+    predecessors: Vec<Option<(usize, TransitionRef)>>,
+    state_limit: usize,
+    state_limit_reached: bool,
+}
+
+impl ReachabilityGraph {
+    /// Builds the reachability graph of the net starting from its current marking.
+    ///
+    /// At most `state_limit` states are stored. If more markings are reachable,
+    /// the exploration stops, the graph is incomplete and `state_limit_reached` returns `true`.
+    ///
+    /// # Errors
+    ///
+    /// If firing a transition causes an overflow in a place, then an error is returned.
+    pub fn new(net: &PetriNet, state_limit: usize) -> Result<Self, Error> {
+        Self::new_from(net, &net.marking_vector(), state_limit)
+    }
+
+    /// Builds the reachability graph of the net starting from the given marking.
+    /// Places missing from the marking are considered to hold no tokens.
+    ///
+    /// The state limit is handled as in `new`.
+    ///
+    /// # Errors
+    ///
+    /// If firing a transition causes an overflow in a place, then an error is returned.
+    pub fn new_from(
+        net: &PetriNet,
+        marking: &BTreeMap<PlaceRef, usize>,
+        state_limit: usize,
+    ) -> Result<Self, Error> {
+        let mut graph = Self {
+            markings: Vec::new(),
+            state_index: BTreeMap::new(),
+            edges: Vec::new(),
+            predecessors: Vec::new(),
+            state_limit,
+            state_limit_reached: false,
+        };
+        if state_limit == 0 {
+            graph.state_limit_reached = true;
+            return Ok(graph);
+        }
+
+        let mut queue: VecDeque<usize> = VecDeque::new();
+        queue.push_back(graph.insert_state(net.complete_marking(marking), None));
+
+        while let Some(state) = queue.pop_front() {
+            for transition_ref in net.enabled_transitions_at(&graph.markings[state]) {
+                let next_marking = net.fire_at(&transition_ref, &graph.markings[state])?;
+                let next_state = if let Some(next_state) = graph.state_index.get(&next_marking) {
+                    *next_state
+                } else if graph.markings.len() < graph.state_limit {
+                    let predecessor = Some((state, transition_ref.clone()));
+                    let next_state = graph.insert_state(next_marking, predecessor);
+                    queue.push_back(next_state);
+                    next_state
+                } else {
+                    graph.state_limit_reached = true;
+                    return Ok(graph);
+                };
+                graph.edges[state].push((transition_ref, next_state));
+            }
+        }
+        Ok(graph)
+    }
+
+    /// Gets the number of states in the graph.
+    #[inline]
+    #[must_use]
+    pub const fn get_cardinality_states(&self) -> usize {
+        self.markings.len()
+    }
+
+    /// Gets the number of edges in the graph.
+    #[must_use]
+    pub fn get_cardinality_edges(&self) -> usize {
+        self.edges.iter().map(Vec::len).sum()
+    }
+
+    /// Gets the maximum number of states that the graph was allowed to store.
+    #[inline]
+    #[must_use]
+    pub const fn state_limit(&self) -> usize {
+        self.state_limit
+    }
+
+    /// Checks whether the exploration was stopped because the state limit was hit.
+    /// If so, the graph does not contain every reachable marking.
+    #[inline]
+    #[must_use]
+    pub const fn state_limit_reached(&self) -> bool {
+        self.state_limit_reached
+    }
+
+    /// Gets the marking of a state in the graph.
+    /// Returns `None` if the state does not exist.
+    #[must_use]
+    pub fn marking(&self, state: usize) -> Option<&BTreeMap<PlaceRef, usize>> {
+        self.markings.get(state)
+    }
+
+    /// Finds the state corresponding to a marking.
+    /// Returns `None` if the marking is not in the graph.
+    #[must_use]
+    pub fn find_state(&self, marking: &BTreeMap<PlaceRef, usize>) -> Option<usize> {
+        self.state_index.get(marking).copied()
+    }
+
+    /// Gets the outgoing edges of a state as pairs of (transition fired, state reached).
+    /// Returns an empty slice if the state does not exist.
+    #[must_use]
+    pub fn successors(&self, state: usize) -> &[(TransitionRef, usize)] {
+        self.edges.get(state).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns an iterator over the states and their markings.
+    /// The states are visited in the order in which they were discovered.
+    pub fn states_iter(&self) -> impl Iterator<Item = (usize, &BTreeMap<PlaceRef, usize>)> {
+        self.markings.iter().enumerate()
+    }
+
+    /// Gets a shortest sequence of transitions whose firing leads from the initial marking to the state.
+    /// Returns `None` if the state does not exist.
+    #[must_use]
+    pub fn firing_sequence(&self, state: usize) -> Option<Vec<TransitionRef>> {
+        if state >= self.markings.len() {
+            return None;
+        }
+        // The graph is explored in breadth-first order,
+        // so following the predecessors yields a shortest path.
+        let mut firing_sequence: Vec<TransitionRef> = Vec::new();
+        let mut current = state;
+        while let Some((predecessor, transition_ref)) = &self.predecessors[current] {
+            firing_sequence.push(transition_ref.clone());
+            current = *predecessor;
+        }
+        firing_sequence.reverse();
+        Some(firing_sequence)
+    }
+
+    fn insert_state(
+        &mut self,
+        marking: BTreeMap<PlaceRef, usize>,
+        predecessor: Option<(usize, TransitionRef)>,
+    ) -> usize {
+        let state = self.markings.len();
+        self.state_index.insert(marking.clone(), state);
+        self.markings.push(marking);
+        self.edges.push(Vec::new());
+        self.predecessors.push(predecessor);
+        state
+    }
+}
+
+#[cfg(test)]
+mod reachability_tests {
+    use super::*;
+    use crate::net_creator::*;
+
+    #[test]
+    fn reachability_graph_empty_net_has_one_state() {
+        let net = PetriNet::new();
+        let graph = ReachabilityGraph::new(&net, 10).unwrap();
+
+        assert_eq!(graph.get_cardinality_states(), 1);
+        assert_eq!(graph.get_cardinality_edges(), 0);
+        assert!(!graph.state_limit_reached());
+    }
+
+    #[test]
+    fn reachability_graph_initial_state_is_the_current_marking() {
+        let (mut net, place_refs, _) = create_net_chain_topology(3);
+        assert!(net.add_token(&place_refs[0], 1).is_ok());
+        let graph = ReachabilityGraph::new(&net, 10).unwrap();
+
+        assert_eq!(graph.marking(0), Some(&net.marking_vector()));
+        assert_eq!(graph.find_state(&net.marking_vector()), Some(0));
+    }
+
+    #[test]
+    fn reachability_graph_new_from_starts_from_the_given_marking() {
+        let (mut net, place_refs, transition_refs) = create_net_chain_topology(3);
+        assert!(net.add_token(&place_refs[0], 1).is_ok());
+        assert!(net.fire(&transition_refs[0]).is_ok());
+        let graph = ReachabilityGraph::new_from(&net, &net.initial_marking_vector(), 10).unwrap();
+
+        assert_eq!(graph.marking(0), Some(&net.initial_marking_vector()));
+        assert_eq!(graph.get_cardinality_states(), 3);
+        assert_eq!(
+            ReachabilityGraph::new(&net, 10)
+                .unwrap()
+                .get_cardinality_states(),
+            2
+        );
+    }
+
+    #[test]
+    fn reachability_graph_chain_topology() {
+        let (mut net, place_refs, transition_refs) = create_net_chain_topology(3);
+        assert!(net.add_token(&place_refs[0], 1).is_ok());
+        let graph = ReachabilityGraph::new(&net, 10).unwrap();
+
+        assert_eq!(graph.get_cardinality_states(), 3);
+        assert_eq!(graph.get_cardinality_edges(), 2);
+        assert_eq!(graph.successors(0), &[(transition_refs[0].clone(), 1)]);
+        assert_eq!(graph.successors(1), &[(transition_refs[1].clone(), 2)]);
+        assert!(graph.successors(2).is_empty());
+        assert_eq!(graph.marking(2).unwrap().get(&place_refs[2]), Some(&1));
+    }
+
+    #[test]
+    fn reachability_graph_loop_topology_has_self_loop() {
+        let (mut net, place_ref, transition_ref) = create_net_loop_topology();
+        assert!(net.add_token(&place_ref, 1).is_ok());
+        let graph = ReachabilityGraph::new(&net, 10).unwrap();
+
+        assert_eq!(graph.get_cardinality_states(), 1);
+        assert_eq!(graph.successors(0), &[(transition_ref, 0)]);
+    }
+
+    #[test]
+    fn reachability_graph_two_tokens_in_chain() {
+        let (mut net, place_refs, _) = create_net_chain_topology(2);
+        assert!(net.add_token(&place_refs[0], 2).is_ok());
+        let graph = ReachabilityGraph::new(&net, 10).unwrap();
+
+        assert_eq!(graph.get_cardinality_states(), 3);
+        assert_eq!(graph.get_cardinality_edges(), 2);
+        assert!(!graph.state_limit_reached());
+    }
+
+    #[test]
+    fn reachability_graph_unbounded_net_hits_the_state_limit() {
+        let mut net = PetriNet::new();
+        let place_ref = net.add_place("P1");
+        let transition_ref = net.add_transition("T1");
+        assert!(
+            net.add_arc_transition_place(&transition_ref, &place_ref)
+                .is_ok()
+        );
+        let graph = ReachabilityGraph::new(&net, 10).unwrap();
+
+        assert_eq!(graph.get_cardinality_states(), 10);
+        assert_eq!(graph.state_limit(), 10);
+        assert!(graph.state_limit_reached());
+    }
+
+    #[test]
+    fn reachability_graph_state_limit_zero_stores_nothing() {
+        let net = PetriNet::new();
+        let graph = ReachabilityGraph::new(&net, 0).unwrap();
+
+        assert_eq!(graph.get_cardinality_states(), 0);
+        assert!(graph.state_limit_reached());
+    }
+
+    #[test]
+    fn reachability_graph_successors_of_missing_state_is_empty() {
+        let net = PetriNet::new();
+        let graph = ReachabilityGraph::new(&net, 10).unwrap();
+
+        assert!(graph.successors(42).is_empty());
+        assert!(graph.marking(42).is_none());
+    }
+
+    #[test]
+    fn reachability_graph_firing_sequence_follows_the_chain() {
+        let (mut net, place_refs, transition_refs) = create_net_chain_topology(4);
+        assert!(net.add_token(&place_refs[0], 1).is_ok());
+        let graph = ReachabilityGraph::new(&net, 10).unwrap();
+
+        assert_eq!(graph.firing_sequence(0), Some(Vec::new()));
+        assert_eq!(graph.firing_sequence(3), Some(transition_refs));
+        assert_eq!(graph.firing_sequence(4), None);
+    }
+}
@@ -0,0 +1,739 @@
+//! # Structural reduction
+//!
+//! Shrinks a net with the reduction rules of Murata and Berthelot,
+//! which preserve boundedness and the liveness of the transitions that remain in the reduced net:
+//!
+//! - Fusion of series places: a transition that only moves a token from one place to the next.
+//! - Fusion of series transitions: an unmarked place between two transitions that only feeds the second one.
+//! - Fusion of parallel places and of parallel transitions: nodes with the same arcs as another node.
+//! - Elimination of self-loop places and of self-loop transitions.
+//! - Elimination of redundant places: places that never restrict the firing of their output transitions
+//!   because another place with the same effect always holds fewer tokens.
+//!
+//! The rules are applied on a copy of the net until none of them applies.
+//! Nodes of the reduced net keep the reference of one of the nodes they replace,
+//! and every node of the reduced net knows the original nodes it stands for.
+//!
+//! The fusion rules only apply to arcs of weight one and never touch places with a capacity.
+//! The conditions on the marking must hold both in the current and in the initial marking.
+
+use crate::error::Error;
+use crate::petri_net::{ArcKind, PetriNet, Place, PlaceRef, Transition, TransitionRef};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A reduction rule.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum ReductionRule {
+    /// A transition with a single input place and a single output place was removed
+    /// and its input place was merged into its output place.
+    FusionOfSeriesPlaces,
+    /// An unmarked place with a single input transition and a single output transition was removed
+    /// and its output transition was merged into its input transition.
+    FusionOfSeriesTransitions,
+    /// A place with the same arcs and the same marking as another place was merged into it.
+    FusionOfParallelPlaces,
+    /// A transition with the same arcs as another transition was merged into it.
+    FusionOfParallelTransitions,
+    /// A marked place connected in both directions to a single transition was removed.
+    EliminationOfSelfLoopPlace,
+    /// A transition connected in both directions to a single place with other output transitions was removed.
+    EliminationOfSelfLoopTransition,
+    /// A place that never restricts the firing of its output transitions was removed.
+    EliminationOfRedundantPlace,
+}
+
+/// The original places and transitions that a node of the reduced net stands for.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Origin {
+    /// The original places.
+    pub places: BTreeSet<PlaceRef>,
+    /// The original transitions.
+    pub transitions: BTreeSet<TransitionRef>,
+}
+
+impl Origin {
+    fn append(&mut self, other: Self) {
+        self.places.extend(other.places);
+        self.transitions.extend(other.transitions);
+    }
+}
+
+/// The result of reducing a net.
+pub struct Reduction {
+    net: PetriNet,
+    place_origins: BTreeMap<PlaceRef, Origin>,
+    transition_origins: BTreeMap<TransitionRef, Origin>,
+    removed: Origin,
+    applied_rules: Vec<ReductionRule>,
+}
+
+impl Reduction {
+    /// Reduces a copy of the net until no rule applies.
+    ///
+    /// # Errors
+    ///
+    /// If the net contains arcs of a kind other than `ArcKind::Normal`, then an error is returned.
+    /// If merging the tokens of two places causes an overflow, then an error is returned.
+    pub fn new(net: &PetriNet) -> Result<Self, Error> {
+        net.check_no_arc_kinds(&[ArcKind::Inhibitor, ArcKind::Read, ArcKind::Reset])?;
+        let mut reduction = Self {
+            net: net.clone(),
+            place_origins: net
+                .places_iter()
+                .map(|(place_ref, _)| {
+                    let origin = Origin {
+                        places: BTreeSet::from([place_ref.clone()]),
+                        transitions: BTreeSet::new(),
+                    };
+                    (place_ref.clone(), origin)
+                })
+                .collect(),
+            transition_origins: net
+                .transitions_iter()
+                .map(|(transition_ref, _)| {
+                    let origin = Origin {
+                        places: BTreeSet::new(),
+                        transitions: BTreeSet::from([transition_ref.clone()]),
+                    };
+                    (transition_ref.clone(), origin)
+                })
+                .collect(),
+            removed: Origin::default(),
+            applied_rules: Vec::new(),
+        };
+        while let Some(rule) = reduction.apply_next_rule()? {
+            reduction.applied_rules.push(rule);
+        }
+        Ok(reduction)
+    }
+
+    /// Gets the reduced net.
+    #[must_use]
+    pub const fn net(&self) -> &PetriNet {
+        &self.net
+    }
+
+    /// Consumes the reduction and returns the reduced net.
+    #[must_use]
+    pub fn into_net(self) -> PetriNet {
+        self.net
+    }
+
+    /// Gets the original nodes that a place of the reduced net stands for.
+    /// Returns `None` if the place is not in the reduced net.
+    #[must_use]
+    pub fn place_origin(&self, place_ref: &PlaceRef) -> Option<&Origin> {
+        self.place_origins.get(place_ref)
+    }
+
+    /// Gets the original nodes that a transition of the reduced net stands for.
+    /// Returns `None` if the transition is not in the reduced net.
+    #[must_use]
+    pub fn transition_origin(&self, transition_ref: &TransitionRef) -> Option<&Origin> {
+        self.transition_origins.get(transition_ref)
+    }
+
+    /// Gets the original nodes that were removed without being replaced by a node of the reduced net.
+    #[must_use]
+    pub const fn removed(&self) -> &Origin {
+        &self.removed
+    }
+
+    /// Gets the rules applied, in order.
+    #[must_use]
+    pub fn applied_rules(&self) -> &[ReductionRule] {
+        &self.applied_rules
+    }
+
+    /// Applies the first rule that can be applied somewhere in the net.
+    /// Returns the rule applied, or `None` if the net cannot be reduced any further.
+    fn apply_next_rule(&mut self) -> Result<Option<ReductionRule>, Error> {
+        let rule = if self.fuse_series_places()? {
+            ReductionRule::FusionOfSeriesPlaces
+        } else if self.fuse_series_transitions()? {
+            ReductionRule::FusionOfSeriesTransitions
+        } else if self.fuse_parallel_places()? {
+            ReductionRule::FusionOfParallelPlaces
+        } else if self.fuse_parallel_transitions()? {
+            ReductionRule::FusionOfParallelTransitions
+        } else if self.eliminate_self_loop_place()? {
+            ReductionRule::EliminationOfSelfLoopPlace
+        } else if self.eliminate_self_loop_transition()? {
+            ReductionRule::EliminationOfSelfLoopTransition
+        } else if self.eliminate_redundant_place()? {
+            ReductionRule::EliminationOfRedundantPlace
+        } else {
+            return Ok(None);
+        };
+        Ok(Some(rule))
+    }
+
+    fn places(&self) -> BTreeMap<&PlaceRef, &Place> {
+        self.net.places_iter().collect()
+    }
+
+    fn transitions(&self) -> BTreeMap<&TransitionRef, &Transition> {
+        self.net.transitions_iter().collect()
+    }
+
+    /// Gets the number of tokens of a place in the current and in the initial marking.
+    const fn tokens(place: &Place) -> [usize; 2] {
+        [place.marking(), place.initial_marking()]
+    }
+
+    fn is_ordinary<K>(arcs: &BTreeMap<K, usize>) -> bool {
+        arcs.values().all(|weight| *weight == 1)
+    }
+
+    /// Removes a transition `t` with `•t = {p1}`, `t• = {p2}` and `p1• = {t}`,
+    /// and moves the input arcs and the tokens of `p1` to `p2`.
+    fn fuse_series_places(&mut self) -> Result<bool, Error> {
+        let places = self.places();
+        let candidate = self
+            .net
+            .transitions_iter()
+            .find_map(|(transition_ref, transition)| {
+                let (preset, postset) = (transition.get_preset(), transition.get_postset());
+                if preset.len() != 1 || postset.len() != 1 {
+                    return None;
+                }
+                let (first_ref, second_ref) = (preset.keys().next()?, postset.keys().next()?);
+                let (first, second) = (places.get(first_ref)?, places.get(second_ref)?);
+                let applies = first_ref != second_ref
+                    && Self::is_ordinary(preset)
+                    && Self::is_ordinary(postset)
+                    && Self::is_ordinary(first.get_preset())
+                    && first.get_postset().len() == 1
+                    && first.capacity().is_none()
+                    && second.capacity().is_none()
+                    && first
+                        .get_preset()
+                        .keys()
+                        .all(|input_ref| !second.get_preset().contains_key(input_ref));
+                applies.then(|| {
+                    let inputs: Vec<TransitionRef> = first.get_preset().keys().cloned().collect();
+                    let tokens = Self::tokens(first);
+                    (
+                        transition_ref.clone(),
+                        first_ref.clone(),
+                        second_ref.clone(),
+                        inputs,
+                        tokens,
+                    )
+                })
+            });
+        let Some((transition_ref, first_ref, second_ref, inputs, tokens)) = candidate else {
+            return Ok(false);
+        };
+
+        for input_ref in &inputs {
+            self.net.add_arc_transition_place(input_ref, &second_ref)?;
+        }
+        self.move_tokens(tokens, &second_ref)?;
+        self.net.remove_transition(&transition_ref)?;
+        self.net.remove_place(&first_ref)?;
+        let mut origin = self.place_origins.remove(&first_ref).unwrap_or_default();
+        origin.append(
+            self.transition_origins
+                .remove(&transition_ref)
+                .unwrap_or_default(),
+        );
+        self.place_origins
+            .entry(second_ref)
+            .or_default()
+            .append(origin);
+        Ok(true)
+    }
+
+    /// Removes an unmarked place `p` with `•p = {t1}`, `p• = {t2}` and `•t2 = {p}`,
+    /// and moves the output arcs of `t2` to `t1`.
+    ///
+    /// The outputs of `t2` must not have a capacity,
+    /// since `t1` could otherwise be blocked by a full output that `t2` was never required to fill.
+    fn fuse_series_transitions(&mut self) -> Result<bool, Error> {
+        let places = self.places();
+        let transitions = self.transitions();
+        let candidate = self.net.places_iter().find_map(|(place_ref, place)| {
+            let (preset, postset) = (place.get_preset(), place.get_postset());
+            if preset.len() != 1 || postset.len() != 1 {
+                return None;
+            }
+            let (first_ref, second_ref) = (preset.keys().next()?, postset.keys().next()?);
+            let (first, second) = (transitions.get(first_ref)?, transitions.get(second_ref)?);
+            let applies = first_ref != second_ref
+                && Self::is_ordinary(preset)
+                && Self::is_ordinary(postset)
+                && Self::is_ordinary(second.get_postset())
+                && second.get_preset().len() == 1
+                && place.capacity().is_none()
+                && Self::tokens(place) == [0, 0]
+                && second.get_postset().keys().all(|output_ref| {
+                    !first.get_postset().contains_key(output_ref)
+                        && places
+                            .get(output_ref)
+                            .is_some_and(|output| output.capacity().is_none())
+                });
+            applies.then(|| {
+                let outputs: Vec<PlaceRef> = second.get_postset().keys().cloned().collect();
+                (
+                    place_ref.clone(),
+                    first_ref.clone(),
+                    second_ref.clone(),
+                    outputs,
+                )
+            })
+        });
+        let Some((place_ref, first_ref, second_ref, outputs)) = candidate else {
+            return Ok(false);
+        };
+
+        for output_ref in &outputs {
+            self.net.add_arc_transition_place(&first_ref, output_ref)?;
+        }
+        self.net.remove_transition(&second_ref)?;
+        self.net.remove_place(&place_ref)?;
+        let mut origin = self
+            .transition_origins
+            .remove(&second_ref)
+            .unwrap_or_default();
+        origin.append(self.place_origins.remove(&place_ref).unwrap_or_default());
+        self.transition_origins
+            .entry(first_ref)
+            .or_default()
+            .append(origin);
+        Ok(true)
+    }
+
+    /// Merges a place into another place with the same arcs and the same marking.
+    fn fuse_parallel_places(&mut self) -> Result<bool, Error> {
+        let mut seen: BTreeMap<_, &PlaceRef> = BTreeMap::new();
+        let candidate = self.net.places_iter().find_map(|(place_ref, place)| {
+            if place.capacity().is_some() {
+                return None;
+            }
+            let key = (place.get_preset(), place.get_postset(), Self::tokens(place));
+            if let Some(kept_ref) = seen.get(&key) {
+                return Some((place_ref.clone(), (*kept_ref).clone()));
+            }
+            seen.insert(key, place_ref);
+            None
+        });
+        let Some((place_ref, kept_ref)) = candidate else {
+            return Ok(false);
+        };
+
+        self.net.remove_place(&place_ref)?;
+        let origin = self.place_origins.remove(&place_ref).unwrap_or_default();
+        self.place_origins
+            .entry(kept_ref)
+            .or_default()
+            .append(origin);
+        Ok(true)
+    }
+
+    /// Merges a transition into another transition with the same arcs.
+    fn fuse_parallel_transitions(&mut self) -> Result<bool, Error> {
+        let mut seen: BTreeMap<_, &TransitionRef> = BTreeMap::new();
+        let candidate = self
+            .net
+            .transitions_iter()
+            .find_map(|(transition_ref, transition)| {
+                let key = (transition.get_preset(), transition.get_postset());
+                if let Some(kept_ref) = seen.get(&key) {
+                    return Some((transition_ref.clone(), (*kept_ref).clone()));
+                }
+                seen.insert(key, transition_ref);
+                None
+            });
+        let Some((transition_ref, kept_ref)) = candidate else {
+            return Ok(false);
+        };
+
+        self.net.remove_transition(&transition_ref)?;
+        let origin = self
+            .transition_origins
+            .remove(&transition_ref)
+            .unwrap_or_default();
+        self.transition_origins
+            .entry(kept_ref)
+            .or_default()
+            .append(origin);
+        Ok(true)
+    }
+
+    /// Removes a marked place `p` with `•p = p• = {t}`.
+    fn eliminate_self_loop_place(&mut self) -> Result<bool, Error> {
+        let candidate = self.net.places_iter().find_map(|(place_ref, place)| {
+            let is_self_loop = place.get_preset().len() == 1
+                && place.get_preset() == place.get_postset()
+                && Self::is_ordinary(place.get_preset());
+            let is_marked = Self::tokens(place).iter().all(|tokens| *tokens > 0);
+            (is_self_loop && is_marked && place.capacity().is_none()).then(|| place_ref.clone())
+        });
+        let Some(place_ref) = candidate else {
+            return Ok(false);
+        };
+
+        self.net.remove_place(&place_ref)?;
+        let origin = self.place_origins.remove(&place_ref).unwrap_or_default();
+        self.removed.append(origin);
+        Ok(true)
+    }
+
+    /// Removes a transition `t` with `•t = t• = {p}` if `p` has other output transitions.
+    /// Whenever one of them fires, `t` could fire as well, so `t` is live if they are.
+    fn eliminate_self_loop_transition(&mut self) -> Result<bool, Error> {
+        let places = self.places();
+        let candidate = self
+            .net
+            .transitions_iter()
+            .find_map(|(transition_ref, transition)| {
+                let preset = transition.get_preset();
+                let place = places.get(preset.keys().next()?)?;
+                let is_self_loop = preset.len() == 1
+                    && preset == transition.get_postset()
+                    && Self::is_ordinary(preset)
+                    && place.get_postset().len() > 1;
+                is_self_loop.then(|| transition_ref.clone())
+            });
+        let Some(transition_ref) = candidate else {
+            return Ok(false);
+        };
+
+        self.net.remove_transition(&transition_ref)?;
+        let origin = self
+            .transition_origins
+            .remove(&transition_ref)
+            .unwrap_or_default();
+        self.removed.append(origin);
+        Ok(true)
+    }
+
+    /// Removes a place `p` for which another place `q` exists such that
+    /// every transition changes both places by the same number of tokens,
+    /// every output transition of `p` needs at least as many tokens from `q`,
+    /// and `p` holds at least as many tokens as `q`.
+    /// Then `p` always holds at least as many tokens as `q` and never disables a transition.
+    fn eliminate_redundant_place(&mut self) -> Result<bool, Error> {
+        let places = self.places();
+        let candidate = places.iter().find_map(|(place_ref, place)| {
+            if place.capacity().is_some() {
+                return None;
+            }
+            let is_redundant = places.iter().any(|(other_ref, other)| {
+                other_ref != place_ref && Self::is_implied_by(place, other)
+            });
+            is_redundant.then(|| PlaceRef::clone(place_ref))
+        });
+        let Some(place_ref) = candidate else {
+            return Ok(false);
+        };
+
+        self.net.remove_place(&place_ref)?;
+        let origin = self.place_origins.remove(&place_ref).unwrap_or_default();
+        self.removed.append(origin);
+        Ok(true)
+    }
+
+    /// Checks whether `place` never restricts the firing of a transition when `other` is present.
+    fn is_implied_by(place: &Place, other: &Place) -> bool {
+        let [tokens, initial_tokens] = Self::tokens(place);
+        let [other_tokens, other_initial_tokens] = Self::tokens(other);
+        if tokens < other_tokens || initial_tokens < other_initial_tokens {
+            return false;
+        }
+        let weight = |arcs: &BTreeMap<TransitionRef, usize>, transition_ref| {
+            arcs.get(transition_ref).copied().unwrap_or(0)
+        };
+        let connected: BTreeSet<&TransitionRef> = [
+            place.get_preset(),
+            place.get_postset(),
+            other.get_preset(),
+            other.get_postset(),
+        ]
+        .into_iter()
+        .flat_map(BTreeMap::keys)
+        .collect();
+        connected.into_iter().all(|transition_ref| {
+            let consumed = weight(place.get_postset(), transition_ref);
+            let other_consumed = weight(other.get_postset(), transition_ref);
+            // Same effect: produced - consumed == other_produced - other_consumed.
+            let balance = weight(place.get_preset(), transition_ref).checked_add(other_consumed);
+            let other_balance = weight(other.get_preset(), transition_ref).checked_add(consumed);
+            consumed <= other_consumed && balance.is_some() && balance == other_balance
+        })
+    }
+
+    /// Adds the tokens of a removed place to another place, in the current and in the initial marking.
+    fn move_tokens(&mut self, tokens: [usize; 2], place_ref: &PlaceRef) -> Result<(), Error> {
+        let [tokens, initial_tokens] = tokens;
+        let initial_marking = self
+            .net
+            .initial_marking(place_ref)?
+            .checked_add(initial_tokens)
+            .ok_or_else(|| Error::TokenOverflow(place_ref.clone()))?;
+        self.net.add_token(place_ref, tokens)?;
+        self.net.set_initial_marking(place_ref, initial_marking)
+    }
+}
+
+impl PetriNet {
+    /// Reduces a copy of the net with the structural reduction rules of Murata and Berthelot.
+    /// The reduced net is bounded if and only if the original net is,
+    /// and a transition of the reduced net is live if and only if it is live in the original net.
+    /// See `Reduction` for the mapping from the reduced nodes back to the original ones.
+    ///
+    /// # Errors
+    ///
+    /// If the net contains arcs of a kind other than `ArcKind::Normal`, then an error is returned.
+    /// If merging the tokens of two places causes an overflow, then an error is returned.
+    pub fn reduce(&self) -> Result<Reduction, Error> {
+        Reduction::new(self)
+    }
+}
+
+#[cfg(test)]
+mod reduction_tests {
+    use super::*;
+    use crate::net_creator::*;
+
+    /// Checks that every node of the original net is either represented in the reduced net or removed.
+    fn assert_covers_original_net(net: &PetriNet, reduction: &Reduction) {
+        let mut covered = reduction.removed().clone();
+        for (place_ref, _) in reduction.net().places_iter() {
+            covered.append(reduction.place_origin(place_ref).unwrap().clone());
+        }
+        for (transition_ref, _) in reduction.net().transitions_iter() {
+            covered.append(reduction.transition_origin(transition_ref).unwrap().clone());
+        }
+        let places: BTreeSet<PlaceRef> = net.places_iter().map(|(p, _)| p.clone()).collect();
+        let transitions: BTreeSet<TransitionRef> =
+            net.transitions_iter().map(|(t, _)| t.clone()).collect();
+
+        assert_eq!(covered.places, places);
+        assert_eq!(covered.transitions, transitions);
+    }
+
+    #[test]
+    fn reduce_chain_topology_collapses_into_one_place() {
+        let (mut net, place_refs, transition_refs) = create_net_chain_topology(5);
+        assert!(net.add_token(&place_refs[0], 1).is_ok());
+        let reduction = net.reduce().unwrap();
+        let reduced = reduction.net();
+
+        assert_eq!(reduced.get_cardinality_places(), 1);
+        assert_eq!(reduced.get_cardinality_transitions(), 0);
+        let origin = reduction.place_origin(&place_refs[4]).unwrap();
+        assert_eq!(origin.places, place_refs.iter().cloned().collect());
+        assert_eq!(
+            origin.transitions,
+            transition_refs.iter().cloned().collect()
+        );
+        assert_eq!(reduced.marking(&place_refs[4]).unwrap(), 1);
+        assert_eq!(
+            reduction.applied_rules(),
+            [ReductionRule::FusionOfSeriesPlaces; 4]
+        );
+        assert_eq!(net.get_cardinality_places(), 5);
+    }
+
+    #[test]
+    fn reduce_fuses_series_transitions() {
+        let (mut net, place_refs, transition_refs) = create_basic_unconnected_net(5, 2);
+        // P0 and P1 feed T0, which feeds P2. T1 moves the token from P2 to P3 and P4.
+        assert!(
+            net.add_arc_place_transition(&place_refs[0], &transition_refs[0])
+                .is_ok()
+        );
+        assert!(
+            net.add_arc_place_transition(&place_refs[1], &transition_refs[0])
+                .is_ok()
+        );
+        assert!(
+            net.add_arc_transition_place(&transition_refs[0], &place_refs[2])
+                .is_ok()
+        );
+        assert!(
+            net.add_arc_place_transition(&place_refs[2], &transition_refs[1])
+                .is_ok()
+        );
+        assert!(
+            net.add_arc_transition_place(&transition_refs[1], &place_refs[3])
+                .is_ok()
+        );
+        assert!(
+            net.add_arc_transition_place(&transition_refs[1], &place_refs[4])
+                .is_ok()
+        );
+        assert!(net.add_token(&place_refs[0], 1).is_ok());
+        assert!(net.add_token(&place_refs[1], 1).is_ok());
+        let reduction = net.reduce().unwrap();
+
+        assert_eq!(
+            reduction.applied_rules().first(),
+            Some(&ReductionRule::FusionOfSeriesTransitions)
+        );
+        assert_eq!(reduction.net().get_cardinality_transitions(), 0);
+        assert_eq!(reduction.net().get_cardinality_places(), 1);
+        assert_covers_original_net(&net, &reduction);
+    }
+
+    #[test]
+    fn reduce_keeps_series_transitions_with_output_capacity() {
+        let mut net = PetriNet::new();
+        let source = net.add_place("Src");
+        let middle = net.add_place("P");
+        let full = net.add_place("Full");
+        let t1 = net.add_transition("T1");
+        let t2 = net.add_transition("T2");
+        // T1 can fire forever, but T2 stops once Full holds its single token.
+        assert!(net.add_arc_place_transition(&source, &t1).is_ok());
+        assert!(net.add_arc_transition_place(&t1, &source).is_ok());
+        assert!(net.add_arc_transition_place(&t1, &middle).is_ok());
+        assert!(net.add_arc_place_transition(&middle, &t2).is_ok());
+        assert!(net.add_arc_transition_place(&t2, &full).is_ok());
+        assert!(net.set_capacity(&full, Some(1)).is_ok());
+        assert!(net.add_token(&source, 1).is_ok());
+        let reduction = net.reduce().unwrap();
+
+        assert!(
+            !reduction
+                .applied_rules()
+                .contains(&ReductionRule::FusionOfSeriesTransitions)
+        );
+        assert!(!net.is_bounded().unwrap());
+        assert!(!reduction.net().is_bounded().unwrap());
+    }
+
+    #[test]
+    fn reduce_fuses_parallel_places_and_transitions() {
+        let mut net = PetriNet::new();
+        let p1 = net.add_place("P1");
+        let p2 = net.add_place("P2");
+        let t1 = net.add_transition("T1");
+        let t2 = net.add_transition("T2");
+        for place_ref in [&p1, &p2] {
+            for transition_ref in [&t1, &t2] {
+                assert!(
+                    net.add_arc_place_transition(place_ref, transition_ref)
+                        .is_ok()
+                );
+            }
+        }
+        let reduction = net.reduce().unwrap();
+
+        assert_eq!(
+            reduction.applied_rules(),
+            [
+                ReductionRule::FusionOfParallelPlaces,
+                ReductionRule::FusionOfParallelTransitions
+            ]
+        );
+        assert_eq!(
+            reduction.place_origin(&p1).unwrap().places,
+            BTreeSet::from([p1.clone(), p2])
+        );
+        assert_eq!(
+            reduction.transition_origin(&t1).unwrap().transitions,
+            BTreeSet::from([t1, t2])
+        );
+    }
+
+    #[test]
+    fn reduce_eliminates_marked_self_loop_place() {
+        let (mut net, place_ref, transition_ref) = create_net_loop_topology();
+        assert!(net.add_token(&place_ref, 1).is_ok());
+        let reduction = net.reduce().unwrap();
+
+        assert_eq!(
+            reduction.applied_rules(),
+            [ReductionRule::EliminationOfSelfLoopPlace]
+        );
+        assert!(reduction.removed().places.contains(&place_ref));
+        assert!(reduction.transition_origin(&transition_ref).is_some());
+    }
+
+    #[test]
+    fn reduce_eliminates_self_loop_transition() {
+        let (mut net, place_ref, transition_ref) = create_net_loop_topology();
+        let other_place_ref = net.add_place("P2");
+        let other_transition_ref = net.add_transition("T2");
+        assert!(
+            net.add_arc_place_transition(&place_ref, &other_transition_ref)
+                .is_ok()
+        );
+        assert!(
+            net.add_arc_transition_place(&other_transition_ref, &other_place_ref)
+                .is_ok()
+        );
+        let reduction = net.reduce().unwrap();
+
+        assert_eq!(
+            reduction.applied_rules(),
+            [
+                ReductionRule::EliminationOfSelfLoopTransition,
+                ReductionRule::FusionOfSeriesPlaces
+            ]
+        );
+        assert!(reduction.removed().transitions.contains(&transition_ref));
+        assert_covers_original_net(&net, &reduction);
+    }
+
+    #[test]
+    fn reduce_keeps_self_loop_transition_of_unmarked_place() {
+        let (net, _, _) = create_net_loop_topology();
+        let reduction = net.reduce().unwrap();
+
+        assert!(reduction.applied_rules().is_empty());
+    }
+
+    #[test]
+    fn reduce_eliminates_redundant_place() {
+        let mut net = PetriNet::new();
+        let p1 = net.add_place("P1");
+        let p2 = net.add_place("P2");
+        let q = net.add_place("Q");
+        let t1 = net.add_transition("T1");
+        let t2 = net.add_transition("T2");
+        // P1 and P2 form a cycle with T1 and T2. Q follows P1 with one more token.
+        assert!(net.add_arc_place_transition(&p1, &t1).is_ok());
+        assert!(net.add_arc_transition_place(&t1, &p2).is_ok());
+        assert!(net.add_weighted_arc_place_transition(&p2, &t2, 2).is_ok());
+        assert!(net.add_weighted_arc_transition_place(&t2, &p1, 2).is_ok());
+        assert!(net.add_arc_place_transition(&q, &t1).is_ok());
+        assert!(net.add_weighted_arc_transition_place(&t2, &q, 2).is_ok());
+        assert!(net.add_token(&p1, 2).is_ok());
+        assert!(net.add_token(&q, 3).is_ok());
+        let reduction = net.reduce().unwrap();
+
+        assert_eq!(
+            reduction.applied_rules().first(),
+            Some(&ReductionRule::EliminationOfRedundantPlace)
+        );
+        assert!(reduction.removed().places.contains(&q));
+    }
+
+    #[test]
+    fn reduce_keeps_places_with_capacity() {
+        let (mut net, place_refs, _) = create_net_chain_topology(2);
+        assert!(net.set_capacity(&place_refs[1], Some(1)).is_ok());
+        let reduction = net.reduce().unwrap();
+
+        assert!(reduction.applied_rules().is_empty());
+        assert_eq!(reduction.net().get_cardinality_places(), 2);
+    }
+
+    #[test]
+    fn reduce_extended_arc_returns_error() {
+        let (mut net, place_ref, transition_ref) = create_net_loop_topology();
+        let other_ref = net.add_place("P2");
+        assert!(net.add_read_arc(&other_ref, &transition_ref, 1).is_ok());
+
+        let result = net.reduce();
+        assert!(matches!(
+            result,
+            Err(Error::UnsupportedArc(_, ArcKind::Read))
+        ));
+        assert!(net.check_place_ref(&place_ref));
+    }
+}
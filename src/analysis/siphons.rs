@@ -0,0 +1,321 @@
+//! # Siphons and traps
+//!
+//! A siphon is a nonempty set of places such that every transition that puts tokens into the set
+//! also takes tokens from it. Once a siphon is unmarked, it stays unmarked forever.
+//! A trap is a nonempty set of places such that every transition that takes tokens from the set
+//! also puts tokens into it. Once a trap is marked, it stays marked forever.
+//!
+//! If every siphon of a net contains a marked trap (Commoner's property), no siphon can ever
+//! become empty. For ordinary nets, i.e. nets whose arcs all have weight one, this implies that
+//! the net is deadlock-free. For free-choice nets it is equivalent to liveness.
+//! The check is purely structural and does not explore the state space.
+//!
+//! The enumeration of the minimal siphons and traps takes exponential time in the worst case.
+
+use crate::error::Error;
+use crate::petri_net::{ArcKind, PetriNet, PlaceRef};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The outcome of checking Commoner's property on a Petri net.
+#[derive(Debug, PartialEq, Eq)]
+pub enum SiphonTrapCheck {
+    /// Every minimal siphon contains a trap marked in the initial marking.
+    Satisfied,
+    /// The minimal siphon contains no trap marked in the initial marking.
+    Violated {
+        /// The siphon without a marked trap.
+        siphon: BTreeSet<PlaceRef>,
+    },
+}
+
+/// The conditions for a place to belong to a set closed in the sense of siphons or traps.
+/// A place may only be in the set if every one of its guards intersects the set.
+struct Guards {
+    guards: BTreeMap<PlaceRef, Vec<BTreeSet<PlaceRef>>>,
+}
+
+impl Guards {
+    /// For siphons, every transition that puts tokens into a place must take tokens from the set.
+    /// For traps, every transition that takes tokens from a place must put tokens into the set.
+    /// Read arcs count as taking the tokens and putting them back.
+    fn new(net: &PetriNet, siphons: bool) -> Self {
+        let mut guards: BTreeMap<PlaceRef, Vec<BTreeSet<PlaceRef>>> = net
+            .places_iter()
+            .map(|(place_ref, _)| (place_ref.clone(), Vec::new()))
+            .collect();
+        for (_, transition) in net.transitions_iter() {
+            let read_places = transition
+                .get_extended_preset()
+                .iter()
+                .filter(|(_, (kind, _))| *kind == ArcKind::Read)
+                .map(|(place_ref, _)| place_ref);
+            let inputs: BTreeSet<PlaceRef> = transition
+                .get_preset()
+                .keys()
+                .chain(read_places.clone())
+                .cloned()
+                .collect();
+            let outputs: BTreeSet<PlaceRef> = transition
+                .get_postset()
+                .keys()
+                .chain(read_places)
+                .cloned()
+                .collect();
+            let (guarded, guard) = if siphons {
+                (&outputs, &inputs)
+            } else {
+                (&inputs, &outputs)
+            };
+            for place_ref in guarded {
+                if let Some(place_guards) = guards.get_mut(place_ref) {
+                    place_guards.push(guard.clone());
+                }
+            }
+        }
+        Self { guards }
+    }
+
+    /// Finds the largest closed set contained in `allowed`, which is empty if there is none.
+    /// Closed sets are closed under union, so the result contains every closed set inside `allowed`.
+    fn largest_within(&self, allowed: &BTreeSet<PlaceRef>) -> BTreeSet<PlaceRef> {
+        let mut set = allowed.clone();
+        loop {
+            let unguarded: Vec<PlaceRef> = set
+                .iter()
+                .filter(|place_ref| {
+                    self.guards.get(*place_ref).is_none_or(|place_guards| {
+                        place_guards.iter().any(|guard| guard.is_disjoint(&set))
+                    })
+                })
+                .cloned()
+                .collect();
+            if unguarded.is_empty() {
+                return set;
+            }
+            for place_ref in &unguarded {
+                set.remove(place_ref);
+            }
+        }
+    }
+
+    /// Shrinks a nonempty closed set until none of its proper subsets is closed.
+    fn minimize(&self, set: BTreeSet<PlaceRef>) -> BTreeSet<PlaceRef> {
+        let mut current = set;
+        loop {
+            let smaller = current.iter().find_map(|place_ref| {
+                let mut allowed = current.clone();
+                allowed.remove(place_ref);
+                let smaller = self.largest_within(&allowed);
+                (!smaller.is_empty()).then_some(smaller)
+            });
+            match smaller {
+                Some(smaller) => current = smaller,
+                None => return current,
+            }
+        }
+    }
+
+    /// Enumerates the minimal closed sets.
+    ///
+    /// After finding a minimal set inside the allowed places, every other minimal set
+    /// misses at least one of its places, so the search continues without each of them in turn.
+    fn find_minimal(&self) -> Vec<BTreeSet<PlaceRef>> {
+        let mut found: BTreeSet<BTreeSet<PlaceRef>> = BTreeSet::new();
+        let mut visited: BTreeSet<BTreeSet<PlaceRef>> = BTreeSet::new();
+        let mut stack: Vec<BTreeSet<PlaceRef>> = vec![self.guards.keys().cloned().collect()];
+
+        while let Some(allowed) = stack.pop() {
+            let largest = self.largest_within(&allowed);
+            if largest.is_empty() || !visited.insert(largest.clone()) {
+                continue;
+            }
+            let minimal = self.minimize(largest.clone());
+            for place_ref in &minimal {
+                let mut next = largest.clone();
+                next.remove(place_ref);
+                stack.push(next);
+            }
+            found.insert(minimal);
+        }
+        found.into_iter().collect()
+    }
+}
+
+impl PetriNet {
+    /// Finds the minimal siphons of the net.
+    /// Returns a vector of `BTreeSet` with the places of each siphon, in lexicographic order.
+    ///
+    /// Read arcs count as a consumption and a production of the tokens.
+    ///
+    /// # Errors
+    ///
+    /// If the net contains an inhibitor or a reset arc, then an error is returned.
+    pub fn find_minimal_siphons(&self) -> Result<Vec<BTreeSet<PlaceRef>>, Error> {
+        self.check_no_arc_kinds(&[ArcKind::Inhibitor, ArcKind::Reset])?;
+        Ok(Guards::new(self, true).find_minimal())
+    }
+
+    /// Finds the minimal traps of the net.
+    /// Returns a vector of `BTreeSet` with the places of each trap, in lexicographic order.
+    ///
+    /// Read arcs count as a consumption and a production of the tokens.
+    ///
+    /// # Errors
+    ///
+    /// If the net contains an inhibitor or a reset arc, then an error is returned.
+    pub fn find_minimal_traps(&self) -> Result<Vec<BTreeSet<PlaceRef>>, Error> {
+        self.check_no_arc_kinds(&[ArcKind::Inhibitor, ArcKind::Reset])?;
+        Ok(Guards::new(self, false).find_minimal())
+    }
+
+    /// Checks Commoner's property: whether every siphon of the net contains a trap
+    /// that is marked in the initial marking.
+    /// It suffices to check the minimal siphons, since every siphon contains a minimal one.
+    ///
+    /// For ordinary nets, a satisfied property proves that the net is deadlock-free.
+    /// For free-choice nets, it proves that the net is live.
+    ///
+    /// # Errors
+    ///
+    /// If the net contains an inhibitor or a reset arc, then an error is returned.
+    pub fn check_siphon_trap_property(&self) -> Result<SiphonTrapCheck, Error> {
+        let traps = Guards::new(self, false);
+        let initial_marking = self.initial_marking_vector();
+
+        for siphon in self.find_minimal_siphons()? {
+            let trap = traps.largest_within(&siphon);
+            let is_marked = trap.iter().any(|place_ref| {
+                initial_marking
+                    .get(place_ref)
+                    .is_some_and(|tokens| *tokens > 0)
+            });
+            if !is_marked {
+                return Ok(SiphonTrapCheck::Violated { siphon });
+            }
+        }
+        Ok(SiphonTrapCheck::Satisfied)
+    }
+}
+
+#[cfg(test)]
+mod siphons_tests {
+    use super::*;
+    use crate::net_creator::*;
+
+    #[test]
+    fn find_minimal_siphons_loop_topology() {
+        let (net, place_ref, _) = create_net_loop_topology();
+        let expected = vec![BTreeSet::from([place_ref])];
+
+        assert_eq!(net.find_minimal_siphons().unwrap(), expected);
+        assert_eq!(net.find_minimal_traps().unwrap(), expected);
+    }
+
+    #[test]
+    fn find_minimal_siphons_chain_topology_starts_at_source() {
+        let (net, place_refs, _) = create_net_chain_topology(3);
+
+        assert_eq!(
+            net.find_minimal_siphons().unwrap(),
+            vec![BTreeSet::from([place_refs[0].clone()])]
+        );
+        assert_eq!(
+            net.find_minimal_traps().unwrap(),
+            vec![BTreeSet::from([place_refs[2].clone()])]
+        );
+    }
+
+    #[test]
+    fn find_minimal_siphons_finds_every_minimal_siphon() {
+        let mut net = PetriNet::new();
+        let p1 = net.add_place("P1");
+        let p2 = net.add_place("P2");
+        let p3 = net.add_place("P3");
+        let t1 = net.add_transition("T1");
+        let t2 = net.add_transition("T2");
+        // T1 moves a token from P1 to P2 and T2 moves it back, both need a token in P3.
+        assert!(net.add_arc_place_transition(&p1, &t1).is_ok());
+        assert!(net.add_arc_transition_place(&t1, &p2).is_ok());
+        assert!(net.add_arc_place_transition(&p2, &t2).is_ok());
+        assert!(net.add_arc_transition_place(&t2, &p1).is_ok());
+        assert!(net.add_arc_place_transition(&p3, &t1).is_ok());
+        assert!(net.add_arc_transition_place(&t1, &p3).is_ok());
+        assert!(net.add_arc_place_transition(&p3, &t2).is_ok());
+        assert!(net.add_arc_transition_place(&t2, &p3).is_ok());
+        let siphons = net.find_minimal_siphons().unwrap();
+
+        assert_eq!(siphons.len(), 2);
+        assert!(siphons.contains(&BTreeSet::from([p1.clone(), p2.clone()])));
+        assert!(siphons.contains(&BTreeSet::from([p3])));
+        assert!(!siphons.contains(&BTreeSet::from([p1])));
+        assert!(!siphons.contains(&BTreeSet::from([p2])));
+    }
+
+    #[test]
+    fn find_minimal_siphons_empty_net_has_none() {
+        let net = PetriNet::new();
+
+        assert!(net.find_minimal_siphons().unwrap().is_empty());
+        assert!(net.find_minimal_traps().unwrap().is_empty());
+    }
+
+    #[test]
+    fn find_minimal_siphons_inhibitor_arc_returns_error() {
+        let (mut net, _, transition_ref) = create_net_loop_topology();
+        let place_ref = net.add_place("P2");
+        assert!(
+            net.add_inhibitor_arc(&place_ref, &transition_ref, 1)
+                .is_ok()
+        );
+
+        let result = net.find_minimal_siphons();
+        assert!(matches!(
+            result,
+            Err(Error::UnsupportedArc(_, ArcKind::Inhibitor))
+        ));
+        let result = net.check_siphon_trap_property();
+        assert!(matches!(
+            result,
+            Err(Error::UnsupportedArc(_, ArcKind::Inhibitor))
+        ));
+    }
+
+    #[test]
+    fn check_siphon_trap_property_marked_loop_is_satisfied() {
+        let (mut net, place_ref, transition_ref) = create_net_loop_topology();
+        assert!(net.add_token(&place_ref, 1).is_ok());
+        assert!(net.fire(&transition_ref).is_ok());
+
+        assert_eq!(
+            net.check_siphon_trap_property().unwrap(),
+            SiphonTrapCheck::Satisfied
+        );
+    }
+
+    #[test]
+    fn check_siphon_trap_property_uses_initial_marking() {
+        let (mut net, place_ref, _) = create_net_loop_topology();
+        assert!(net.add_token(&place_ref, 1).is_ok());
+        assert!(net.set_initial_marking(&place_ref, 0).is_ok());
+
+        assert_eq!(
+            net.check_siphon_trap_property().unwrap(),
+            SiphonTrapCheck::Violated {
+                siphon: BTreeSet::from([place_ref])
+            }
+        );
+    }
+
+    #[test]
+    fn check_siphon_trap_property_chain_is_violated() {
+        let (mut net, place_refs, _) = create_net_chain_topology(2);
+        assert!(net.add_token(&place_refs[0], 1).is_ok());
+
+        assert_eq!(
+            net.check_siphon_trap_property().unwrap(),
+            SiphonTrapCheck::Violated {
+                siphon: BTreeSet::from([place_refs[0].clone()])
+            }
+        );
+    }
+}
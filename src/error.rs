@@ -0,0 +1,210 @@
+use crate::petri_net::{ArcKind, ArcRef, PlaceRef, TransitionRef};
+
+/// The error type for the operations on a `PetriNet`.
+///
+/// Every variant carries the references involved,
+/// so that the error can be kept after the net is modified.
+#[derive(Debug)]
+pub enum Error {
+    /// The place reference is not present in the net.
+    InvalidPlaceRef(PlaceRef),
+    /// The transition reference is not present in the net.
+    InvalidTransitionRef(TransitionRef),
+    /// The arc cannot be added because it already exists.
+    DuplicateArc(ArcRef),
+    /// The arc does not exist in the net.
+    ArcNotFound(ArcRef),
+    /// The arc was found only on one side or with different weights on each side.
+    InconsistentArc(ArcRef),
+    /// The weight of the arc is zero.
+    InvalidArcWeight(ArcRef),
+    /// The operation does not support arcs of this kind, but the net contains this arc.
+    UnsupportedArc(ArcRef, ArcKind),
+    /// Adding tokens to the place would overflow the marking.
+    TokenOverflow(PlaceRef),
+    /// The place does not have as many tokens as requested.
+    InsufficientTokens(PlaceRef),
+    /// Adding tokens to the place would exceed its capacity.
+    CapacityExceeded(PlaceRef),
+    /// The transition cannot fire because it is not enabled.
+    TransitionNotEnabled(TransitionRef),
+    /// The transition is enabled but has not been enabled for its earliest firing time yet.
+    FiringTooEarly(TransitionRef),
+    /// Letting the time pass would make the transition exceed the latest firing time of its interval.
+    DeadlineMissed(TransitionRef),
+    /// The rate or the weight of the stochastic timing of the transition is not positive and finite.
+    InvalidRate(TransitionRef),
+    /// The places cannot be fused, because their markings or capacities differ
+    /// or one of them is already fused with another place.
+    IncompatibleFusion(PlaceRef, PlaceRef),
+    /// The transitions cannot be synchronised, because their firing intervals are disjoint,
+    /// their stochastic timings differ
+    /// or they are connected to the same place and one of the arcs is not a normal arc.
+    IncompatibleSynchronisation(TransitionRef, TransitionRef),
+    /// Both nets of a composition were given the same prefix.
+    EqualPrefixes(String),
+    /// The composition would give the same label to nodes that come from different nets.
+    LabelCollision(String),
+    /// The state space exploration stopped after storing the given number of states.
+    StateLimitReached(usize),
+    /// Immediate transitions can fire forever without letting the time pass,
+    /// starting from the marking reached by firing the transition.
+    TimelessTrap(TransitionRef),
+    /// The iterative solver did not converge within the given number of iterations.
+    NoConvergence(usize),
+    /// The coefficients grew too large during a computation over the net.
+    ArithmeticOverflow,
+    /// The writer failed while exporting the net.
+    Io(std::io::Error),
+    /// The input could not be parsed into a net.
+    /// The line and the column start at one.
+    Parse {
+        line: u64,
+        column: u64,
+        message: String,
+    },
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidPlaceRef(place_ref) => write!(
+                f,
+                "Place reference {place_ref} is invalid. It is not present in the net."
+            ),
+            Self::InvalidTransitionRef(transition_ref) => write!(
+                f,
+                "Transition reference {transition_ref} is invalid. It is not present in the net."
+            ),
+            Self::DuplicateArc(arc_ref) => {
+                write!(f, "Cannot add the arc {arc_ref}. The arc already exists.")
+            }
+            Self::ArcNotFound(arc_ref) => write!(f, "The arc {arc_ref} does not exist."),
+            Self::InconsistentArc(arc_ref) => write!(
+                f,
+                "The arc {arc_ref} does not match on both sides. The net was in an inconsistent state."
+            ),
+            Self::InvalidArcWeight(arc_ref) => write!(
+                f,
+                "Invalid weight for the arc {arc_ref}. The weight of an arc must be at least one."
+            ),
+            Self::UnsupportedArc(arc_ref, kind) => write!(
+                f,
+                "The operation does not support {kind} arcs, but the net contains the {kind} arc {arc_ref}."
+            ),
+            Self::TokenOverflow(place_ref) => {
+                write!(f, "Overflow when adding tokens to the place {place_ref}.")
+            }
+            Self::InsufficientTokens(place_ref) => write!(
+                f,
+                "Cannot remove more tokens than available at the place {place_ref}."
+            ),
+            Self::CapacityExceeded(place_ref) => {
+                write!(f, "Cannot exceed the capacity of the place {place_ref}.")
+            }
+            Self::TransitionNotEnabled(transition_ref) => write!(
+                f,
+                "Cannot fire the transition {transition_ref}. The transition is not enabled."
+            ),
+            Self::FiringTooEarly(transition_ref) => write!(
+                f,
+                "Cannot fire the transition {transition_ref} yet. Its earliest firing time has not been reached."
+            ),
+            Self::DeadlineMissed(transition_ref) => write!(
+                f,
+                "Cannot let the time pass. The transition {transition_ref} would miss its latest firing time."
+            ),
+            Self::InvalidRate(transition_ref) => write!(
+                f,
+                "Invalid stochastic timing for the transition {transition_ref}. The rate or the weight must be positive and finite."
+            ),
+            Self::IncompatibleFusion(place_ref, other_ref) => write!(
+                f,
+                "Cannot fuse the place {place_ref} with the place {other_ref}. The markings or the capacities differ, or a place is fused twice."
+            ),
+            Self::IncompatibleSynchronisation(transition_ref, other_ref) => write!(
+                f,
+                "Cannot synchronise the transition {transition_ref} with the transition {other_ref}. The firing intervals are disjoint, the stochastic timings differ or the arcs cannot be combined."
+            ),
+            Self::EqualPrefixes(prefix) => write!(
+                f,
+                "Cannot compose the nets. Both nets have the prefix \"{prefix}\"."
+            ),
+            Self::LabelCollision(label) => write!(
+                f,
+                "Cannot compose the nets. The label \"{label}\" would be given to nodes from different nets."
+            ),
+            Self::StateLimitReached(state_limit) => write!(
+                f,
+                "The state limit of {state_limit} states was reached before exploring every reachable marking."
+            ),
+            Self::TimelessTrap(transition_ref) => write!(
+                f,
+                "Firing the transition {transition_ref} leads to a cycle of immediate transitions that can never be left. The time can never pass."
+            ),
+            Self::NoConvergence(max_iterations) => write!(
+                f,
+                "The solver did not converge within {max_iterations} iterations."
+            ),
+            Self::ArithmeticOverflow => {
+                write!(f, "Overflow while computing over the net.")
+            }
+            Self::Io(error) => write!(f, "Could not export the net: {error}"),
+            Self::Parse {
+                line,
+                column,
+                message,
+            } => write!(f, "Parse error at line {line}, column {column}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+#[cfg(test)]
+mod error_tests {
+    use super::*;
+
+    #[test]
+    fn error_display_trait_includes_the_ref() {
+        let place_ref = PlaceRef::new("P1");
+        let error = Error::InvalidPlaceRef(place_ref);
+
+        assert_eq!(
+            format!("{error}"),
+            "Place reference P1 is invalid. It is not present in the net."
+        );
+    }
+
+    #[test]
+    fn error_from_io_error_keeps_the_source() {
+        let error: Error = std::io::Error::other("Writer failed").into();
+
+        assert!(matches!(error, Error::Io(_)));
+        assert!(std::error::Error::source(&error).is_some());
+    }
+
+    #[test]
+    fn error_display_trait_unsupported_arc_names_the_kind() {
+        let arc_ref = ArcRef::PlaceTransition(PlaceRef::new("P1"), TransitionRef::new("T1"));
+        let error = Error::UnsupportedArc(arc_ref, ArcKind::Reset);
+
+        assert_eq!(
+            format!("{error}"),
+            "The operation does not support reset arcs, but the net contains the reset arc (P1, T1)."
+        );
+    }
+}
@@ -1,4 +1,5 @@
 pub mod dot;
+pub mod ids;
 pub mod lola;
 pub mod pnml;
 
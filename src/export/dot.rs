@@ -1,4 +1,6 @@
-use crate::petri_net::PetriNet;
+use crate::error::Error;
+use crate::export::ids::{ExportIds, IdFormat};
+use crate::petri_net::{ArcKind, PetriNet};
 
 const MAX_TOKENS_AS_DOT: usize = 5;
 
@@ -8,45 +10,49 @@ impl PetriNet {
     /// # Errors
     ///
     /// If the writer fails to write the contents of the net, then an error is returned.
-    pub fn to_dot_string(&self) -> Result<String, std::io::Error> {
+    pub fn to_dot_string(&self) -> Result<String, Error> {
         let mut writer = Vec::new();
         self.to_dot(&mut writer)?;
         String::from_utf8(writer).map_err(|_|
             // This error could only be due to a bug, map it to a more standard error type.
-            std::io::Error::other(
+            Error::Io(std::io::Error::other(
                 "Could not convert the string to UTF-8",
-            ))
+            )))
     }
 
     /// Converts the net to the dot format.
+    /// The nodes are named with the identifiers generated by `ExportIds` and display their labels.
     /// Writes the output to a trait object which implements `std::io::Write`.
     ///
     /// # Errors
     ///
     /// If the writer fails to write the contents of the net, then an error is returned.
-    pub fn to_dot<T>(&self, writer: &mut T) -> Result<(), std::io::Error>
+    pub fn to_dot<T>(&self, writer: &mut T) -> Result<(), Error>
     where
         T: std::io::Write,
     {
         writer.write_all(b"digraph petrinet {\n")?;
-        self.write_dot_places(writer)?;
-        self.write_dot_transitions(writer)?;
-        self.write_dot_arcs(writer)?;
+        let ids = self.export_ids(IdFormat::Dot);
+        self.write_dot_places(&ids, writer)?;
+        self.write_dot_transitions(&ids, writer)?;
+        self.write_dot_arcs(&ids, writer)?;
         writer.write_all(b"}\n")?;
         Ok(())
     }
 
     /// Writes the lines that define the places
     /// to a trait object which implements `std::io::Write`.
-    fn write_dot_places<T>(&self, writer: &mut T) -> Result<(), std::io::Error>
+    fn write_dot_places<T>(&self, ids: &ExportIds, writer: &mut T) -> Result<(), std::io::Error>
     where
         T: std::io::Write,
     {
         for (place_ref, place) in self.places_iter() {
-            let label = Self::sanitize_string(place_ref.label());
-            let marking = Self::marking_to_string(place.marking());
+            let id = ids.place(place_ref);
+            let label = Self::sanitize_string(place_ref.label())
+                + &Self::capacity_to_string(place.capacity());
+            let marking = Self::marking_to_string(place.initial_marking());
             let line =
-                format!("    {label} [shape=\"circle\" xlabel=\"{label}\" label=\"{marking}\"];\n");
+                format!("    {id} [shape=\"circle\" xlabel=\"{label}\" label=\"{marking}\"];\n");
             writer.write_all(line.as_bytes())?;
         }
         Ok(())
@@ -54,13 +60,18 @@ impl PetriNet {
 
     /// Writes the lines that define the transitions
     /// to a trait object which implements `std::io::Write`.
-    fn write_dot_transitions<T>(&self, writer: &mut T) -> Result<(), std::io::Error>
+    fn write_dot_transitions<T>(
+        &self,
+        ids: &ExportIds,
+        writer: &mut T,
+    ) -> Result<(), std::io::Error>
     where
         T: std::io::Write,
     {
         for (transition_ref, _) in self.transitions_iter() {
+            let id = ids.transition(transition_ref);
             let label = Self::sanitize_string(transition_ref.label());
-            let line = format!("    {label} [shape=\"box\" xlabel=\"\" label=\"{label}\"];\n");
+            let line = format!("    {id} [shape=\"box\" xlabel=\"\" label=\"{label}\"];\n");
             writer.write_all(line.as_bytes())?;
         }
         Ok(())
@@ -68,26 +79,39 @@ impl PetriNet {
 
     /// Writes the lines that define the arcs
     /// to a trait object which implements `std::io::Write`.
-    fn write_dot_arcs<T>(&self, writer: &mut T) -> Result<(), std::io::Error>
+    fn write_dot_arcs<T>(&self, ids: &ExportIds, writer: &mut T) -> Result<(), std::io::Error>
     where
         T: std::io::Write,
     {
-        let arcs = self.find_arcs_place_transition();
-        for (place_ref, transition_ref) in arcs {
+        let arcs = self.find_weighted_arcs_place_transition();
+        for ((place_ref, transition_ref), weight) in arcs {
             let line = format!(
-                "    {} -> {};\n",
-                Self::sanitize_string(place_ref.label()),
-                Self::sanitize_string(transition_ref.label())
+                "    {} -> {}{};\n",
+                ids.place(&place_ref),
+                ids.transition(&transition_ref),
+                Self::weight_to_string(weight),
             );
             writer.write_all(line.as_bytes())?;
         }
 
-        let arcs = self.find_arcs_transition_place();
-        for (transition_ref, place_ref) in arcs {
+        let arcs = self.find_weighted_arcs_transition_place();
+        for ((transition_ref, place_ref), weight) in arcs {
             let line = format!(
-                "    {} -> {};\n",
-                Self::sanitize_string(transition_ref.label()),
-                Self::sanitize_string(place_ref.label()),
+                "    {} -> {}{};\n",
+                ids.transition(&transition_ref),
+                ids.place(&place_ref),
+                Self::weight_to_string(weight),
+            );
+            writer.write_all(line.as_bytes())?;
+        }
+
+        let arcs = self.find_extended_arcs();
+        for ((place_ref, transition_ref), (kind, weight)) in arcs {
+            let line = format!(
+                "    {} -> {}{};\n",
+                ids.place(&place_ref),
+                ids.transition(&transition_ref),
+                Self::extended_arc_to_string(kind, weight),
             );
             writer.write_all(line.as_bytes())?;
         }
@@ -112,6 +136,40 @@ impl PetriNet {
             _ => marking.to_string(),
         }
     }
+
+    /// Convert the capacity of a place to a line appended to its label.
+    /// Places without a capacity get no annotation.
+    fn capacity_to_string(capacity: Option<usize>) -> String {
+        capacity.map_or_else(String::new, |capacity| format!("\\nK={capacity}"))
+    }
+
+    /// Convert the weight of an arc to a valid attribute list.
+    /// Arcs with multiplicity one are drawn without a label.
+    fn weight_to_string(weight: usize) -> String {
+        if weight == 1 {
+            String::new()
+        } else {
+            format!(" [label=\"{weight}\"]")
+        }
+    }
+
+    /// Convert the kind and the weight of an arc from a place to a transition
+    /// to a valid attribute list.
+    /// Inhibitor arcs end in a circle, read arcs have no arrowhead
+    /// and reset arcs end in a double arrowhead without a label.
+    fn extended_arc_to_string(kind: ArcKind, weight: usize) -> String {
+        let arrowhead = match kind {
+            ArcKind::Normal => return Self::weight_to_string(weight),
+            ArcKind::Inhibitor => "odot",
+            ArcKind::Read => "none",
+            ArcKind::Reset => return " [arrowhead=\"normalnormal\"]".to_string(),
+        };
+        if weight == 1 {
+            format!(" [arrowhead=\"{arrowhead}\"]")
+        } else {
+            format!(" [label=\"{weight}\" arrowhead=\"{arrowhead}\"]")
+        }
+    }
 }
 
 #[cfg(test)]
@@ -183,4 +241,70 @@ mod dot_tests {
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), DOT_STRING_NET_WITH_LOOP_TOPOLOGY);
     }
+
+    #[test]
+    fn dot_string_net_with_weighted_arcs() {
+        let mut net = PetriNet::new();
+        let p1 = net.add_place("P1");
+        let p2 = net.add_place("P2");
+        let t1 = net.add_transition("T1");
+
+        assert!(net.add_weighted_arc_place_transition(&p1, &t1, 2).is_ok());
+        assert!(net.add_weighted_arc_transition_place(&t1, &p2, 3).is_ok());
+        assert!(net.add_token(&p1, 2).is_ok());
+        let result = net.to_dot_string();
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), DOT_STRING_NET_WITH_WEIGHTED_ARCS);
+    }
+
+    #[test]
+    fn dot_string_duplicate_labels_get_unique_ids() {
+        let mut net = PetriNet::new();
+        net.add_place("mutex");
+        net.add_place("mutex");
+        net.add_transition("lock step");
+        let result = net.to_dot_string().unwrap();
+
+        assert!(result.contains(r#"    mutex [shape="circle" xlabel="mutex" label=""];"#));
+        assert!(result.contains(r#"    mutex_2 [shape="circle" xlabel="mutex" label=""];"#));
+        assert!(result.contains(r#"    lock_step [shape="box" xlabel="" label="lock step"];"#));
+    }
+
+    #[test]
+    fn dot_string_net_with_capacities() {
+        let mut net = PetriNet::new();
+        let p1 = net.add_place("P1");
+        net.add_place("P2");
+        assert!(net.add_token(&p1, 1).is_ok());
+        assert!(net.set_capacity(&p1, Some(3)).is_ok());
+        let result = net.to_dot_string();
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), DOT_STRING_NET_WITH_CAPACITIES);
+    }
+
+    #[test]
+    fn dot_string_net_with_extended_arcs() {
+        let mut net = PetriNet::new();
+        let p1 = net.add_place("P1");
+        let p2 = net.add_place("P2");
+        let t1 = net.add_transition("T1");
+        assert!(net.add_arc_place_transition(&p1, &t1).is_ok());
+        assert!(net.add_inhibitor_arc(&p2, &t1, 2).is_ok());
+        let result = net.to_dot_string().unwrap();
+
+        assert!(result.contains("    P1 -> T1;\n"));
+        assert!(result.contains("    P2 -> T1 [label=\"2\" arrowhead=\"odot\"];\n"));
+        assert!(net.remove_extended_arc(&p2, &t1).is_ok());
+        assert!(net.add_inhibitor_arc(&p2, &t1, 1).is_ok());
+        let p3 = net.add_place("P3");
+        let p4 = net.add_place("P4");
+        assert!(net.add_read_arc(&p3, &t1, 1).is_ok());
+        assert!(net.add_reset_arc(&p4, &t1).is_ok());
+        let result = net.to_dot_string().unwrap();
+        assert!(result.contains("    P2 -> T1 [arrowhead=\"odot\"];\n"));
+        assert!(result.contains("    P3 -> T1 [arrowhead=\"none\"];\n"));
+        assert!(result.contains("    P4 -> T1 [arrowhead=\"normalnormal\"];\n"));
+    }
 }
@@ -0,0 +1,313 @@
+use crate::petri_net::{ArcRef, PetriNet, PlaceRef, TransitionRef};
+use std::collections::{BTreeMap, BTreeSet};
+
+const DOT_KEYWORDS: [&str; 6] = ["node", "edge", "graph", "digraph", "subgraph", "strict"];
+const LOLA_KEYWORDS: [&str; 9] = [
+    "PLACE",
+    "MARKING",
+    "TRANSITION",
+    "CONSUME",
+    "PRODUCE",
+    "SAFE",
+    "STRONG",
+    "WEAK",
+    "FAIR",
+];
+
+/// The export format for which the identifiers are generated.
+/// Each format has its own rules for what constitutes a valid identifier.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum IdFormat {
+    /// PNML identifiers must be valid XML names without colons (`NCName`).
+    Pnml,
+    /// DOT identifiers must be unquoted IDs that are not keywords of the language.
+    Dot,
+    /// `LoLA` identifiers may not contain separators nor be a keyword or a number.
+    Lola,
+}
+
+impl std::fmt::Display for IdFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pnml => write!(f, "PNML"),
+            Self::Dot => write!(f, "DOT"),
+            Self::Lola => write!(f, "LoLA"),
+        }
+    }
+}
+
+/// Unique identifiers for the places, transitions and arcs of a net in a given export format.
+///
+/// Labels in the net need not be unique, so they cannot be used directly as identifiers.
+/// The identifier of each node is derived from its label by replacing the characters
+/// that are not allowed in the format. Collisions are resolved by appending a numeric suffix.
+/// Places, transitions and arcs share a single namespace.
+///
+/// The assignment only depends on the order of the iterators of the net,
+/// therefore exporting the same net twice produces the same identifiers.
+#[derive(Debug)]
+pub struct ExportIds {
+    places: BTreeMap<PlaceRef, String>,
+    transitions: BTreeMap<TransitionRef, String>,
+    arcs: BTreeMap<ArcRef, String>,
+}
+
+impl ExportIds {
+    /// Assigns an identifier to every place, transition and arc of the net for the given format.
+    /// Places are processed first, then transitions and finally arcs.
+    /// Arcs from places to transitions of every kind share the same form of identifier.
+    #[must_use]
+    pub fn new(net: &PetriNet, format: IdFormat) -> Self {
+        let mut used: BTreeSet<String> = BTreeSet::new();
+        let mut places = BTreeMap::new();
+        let mut transitions = BTreeMap::new();
+        let mut arcs = BTreeMap::new();
+
+        for (place_ref, _) in net.places_iter() {
+            let id = unique_id(&sanitize_id(place_ref.label(), format), &mut used);
+            places.insert(place_ref.clone(), id);
+        }
+        for (transition_ref, _) in net.transitions_iter() {
+            let id = unique_id(&sanitize_id(transition_ref.label(), format), &mut used);
+            transitions.insert(transition_ref.clone(), id);
+        }
+        for (place_ref, transition_ref) in net.find_arcs_place_transition().into_keys() {
+            let base = format!("{}_{}", places[&place_ref], transitions[&transition_ref]);
+            let id = unique_id(&base, &mut used);
+            arcs.insert(ArcRef::PlaceTransition(place_ref, transition_ref), id);
+        }
+        for (transition_ref, place_ref) in net.find_arcs_transition_place() {
+            let base = format!("{}_{}", transitions[&transition_ref], places[&place_ref]);
+            let id = unique_id(&base, &mut used);
+            arcs.insert(ArcRef::TransitionPlace(transition_ref, place_ref), id);
+        }
+
+        Self {
+            places,
+            transitions,
+            arcs,
+        }
+    }
+
+    /// Returns the identifier assigned to the place,
+    /// or `None` if the place was not in the net.
+    #[must_use]
+    pub fn place_id(&self, place_ref: &PlaceRef) -> Option<&str> {
+        self.places.get(place_ref).map(String::as_str)
+    }
+
+    /// Returns the identifier assigned to the transition,
+    /// or `None` if the transition was not in the net.
+    #[must_use]
+    pub fn transition_id(&self, transition_ref: &TransitionRef) -> Option<&str> {
+        self.transitions.get(transition_ref).map(String::as_str)
+    }
+
+    /// Returns the identifier assigned to the arc,
+    /// or `None` if the arc was not in the net.
+    #[must_use]
+    pub fn arc_id(&self, arc_ref: &ArcRef) -> Option<&str> {
+        self.arcs.get(arc_ref).map(String::as_str)
+    }
+
+    /// Returns the identifier of a place that is known to be in the net.
+    pub(crate) fn place(&self, place_ref: &PlaceRef) -> &str {
+        &self.places[place_ref]
+    }
+
+    /// Returns the identifier of a transition that is known to be in the net.
+    pub(crate) fn transition(&self, transition_ref: &TransitionRef) -> &str {
+        &self.transitions[transition_ref]
+    }
+
+    /// Returns the identifier of an arc that is known to be in the net.
+    pub(crate) fn arc(&self, arc_ref: &ArcRef) -> &str {
+        &self.arcs[arc_ref]
+    }
+}
+
+impl PetriNet {
+    /// Returns the identifiers that the exporter for the given format uses
+    /// for the places, transitions and arcs of the net.
+    ///
+    /// This is useful to refer to the nodes in queries written for other tools,
+    /// e.g. a `LoLA` formula, when the labels are not valid identifiers or not unique.
+    #[must_use]
+    pub fn export_ids(&self, format: IdFormat) -> ExportIds {
+        ExportIds::new(self, format)
+    }
+}
+
+/// Converts a label to a valid identifier for the format.
+/// Characters that are not allowed are replaced by underscores.
+pub(crate) fn sanitize_id(label: &str, format: IdFormat) -> String {
+    let is_allowed = |character: char| match format {
+        IdFormat::Pnml => character.is_alphanumeric() || matches!(character, '_' | '-' | '.'),
+        IdFormat::Dot => {
+            character.is_ascii_alphanumeric() || character == '_' || !character.is_ascii()
+        }
+        IdFormat::Lola => {
+            !(character.is_whitespace()
+                || matches!(character, ',' | ';' | ':' | '(' | ')' | '{' | '}' | '/'))
+        }
+    };
+    let mut id: String = label
+        .chars()
+        .map(|character| {
+            if is_allowed(character) {
+                character
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    let needs_prefix = match format {
+        IdFormat::Pnml => id
+            .chars()
+            .next()
+            .is_none_or(|first| !(first.is_alphabetic() || first == '_')),
+        IdFormat::Dot => id.chars().next().is_none_or(|first| first.is_ascii_digit()),
+        IdFormat::Lola => id.chars().all(|character| character.is_ascii_digit()),
+    };
+    if needs_prefix {
+        id.insert(0, '_');
+    }
+
+    let is_keyword = match format {
+        IdFormat::Pnml => false,
+        IdFormat::Dot => DOT_KEYWORDS
+            .iter()
+            .any(|keyword| keyword.eq_ignore_ascii_case(&id)),
+        IdFormat::Lola => LOLA_KEYWORDS.contains(&id.as_str()),
+    };
+    if is_keyword {
+        id.push('_');
+    }
+    id
+}
+
+/// Returns the base identifier if it is still free, otherwise appends the first free suffix.
+/// The returned identifier is marked as used.
+fn unique_id(base: &str, used: &mut BTreeSet<String>) -> String {
+    let mut id = base.to_string();
+    let mut suffix = 2;
+    while used.contains(&id) {
+        id = format!("{base}_{suffix}");
+        suffix += 1;
+    }
+    used.insert(id.clone());
+    id
+}
+
+#[cfg(test)]
+mod ids_tests {
+    use super::*;
+    use crate::net_creator::*;
+
+    #[test]
+    fn export_ids_keep_valid_unique_labels() {
+        let (net, place_refs, transition_refs) = create_net_chain_topology(2);
+
+        for format in [IdFormat::Pnml, IdFormat::Dot, IdFormat::Lola] {
+            let ids = net.export_ids(format);
+            assert_eq!(ids.place_id(&place_refs[0]), Some("P1"));
+            assert_eq!(ids.place_id(&place_refs[1]), Some("P2"));
+            assert_eq!(ids.transition_id(&transition_refs[0]), Some("T1"));
+            let arc_ref =
+                ArcRef::PlaceTransition(place_refs[0].clone(), transition_refs[0].clone());
+            assert_eq!(ids.arc_id(&arc_ref), Some("P1_T1"));
+        }
+    }
+
+    #[test]
+    fn export_ids_duplicate_labels_get_suffixes() {
+        let mut net = PetriNet::new();
+        let first = net.add_place("mutex");
+        let second = net.add_place("mutex");
+        let ids = net.export_ids(IdFormat::Pnml);
+
+        let mut assigned = vec![
+            ids.place_id(&first).unwrap(),
+            ids.place_id(&second).unwrap(),
+        ];
+        assigned.sort_unstable();
+        assert_eq!(assigned, vec!["mutex", "mutex_2"]);
+    }
+
+    #[test]
+    fn export_ids_places_and_transitions_share_namespace() {
+        let mut net = PetriNet::new();
+        let place_ref = net.add_place("x");
+        let transition_ref = net.add_transition("x");
+        let ids = net.export_ids(IdFormat::Lola);
+
+        assert_eq!(ids.place_id(&place_ref), Some("x"));
+        assert_eq!(ids.transition_id(&transition_ref), Some("x_2"));
+    }
+
+    #[test]
+    fn export_ids_suffix_does_not_collide_with_existing_label() {
+        let mut net = PetriNet::new();
+        let first = net.add_place("a");
+        let second = net.add_place("a_2");
+        let third = net.add_transition("a");
+        let ids = net.export_ids(IdFormat::Dot);
+
+        assert_eq!(ids.place_id(&first), Some("a"));
+        assert_eq!(ids.place_id(&second), Some("a_2"));
+        assert_eq!(ids.transition_id(&third), Some("a_3"));
+    }
+
+    #[test]
+    fn export_ids_are_stable() {
+        let mut net = PetriNet::new();
+        let place_refs: Vec<PlaceRef> = (0..5).map(|_| net.add_place("same")).collect();
+        let first_ids = net.export_ids(IdFormat::Pnml);
+        let second_ids = net.export_ids(IdFormat::Pnml);
+
+        for place_ref in &place_refs {
+            assert_eq!(
+                first_ids.place_id(place_ref),
+                second_ids.place_id(place_ref)
+            );
+        }
+    }
+
+    #[test]
+    fn sanitize_id_pnml_produces_ncname() {
+        assert_eq!(
+            sanitize_id("Example place", IdFormat::Pnml),
+            "Example_place"
+        );
+        assert_eq!(sanitize_id("1st", IdFormat::Pnml), "_1st");
+        assert_eq!(sanitize_id("-a.b", IdFormat::Pnml), "_-a.b");
+        assert_eq!(sanitize_id("ns:name", IdFormat::Pnml), "ns_name");
+        assert_eq!(sanitize_id("(P1, T1)", IdFormat::Pnml), "_P1__T1_");
+        assert_eq!(sanitize_id("", IdFormat::Pnml), "_");
+        assert_eq!(sanitize_id("größe", IdFormat::Pnml), "größe");
+    }
+
+    #[test]
+    fn sanitize_id_dot_produces_unquoted_id() {
+        assert_eq!(
+            sanitize_id("a \"quoted\" label", IdFormat::Dot),
+            "a__quoted__label"
+        );
+        assert_eq!(sanitize_id("1st", IdFormat::Dot), "_1st");
+        assert_eq!(sanitize_id("a-b", IdFormat::Dot), "a_b");
+        assert_eq!(sanitize_id("Node", IdFormat::Dot), "Node_");
+        assert_eq!(sanitize_id("größe", IdFormat::Dot), "größe");
+        assert_eq!(sanitize_id("", IdFormat::Dot), "_");
+    }
+
+    #[test]
+    fn sanitize_id_lola_produces_identifier() {
+        assert_eq!(sanitize_id("a b,c;d:e", IdFormat::Lola), "a_b_c_d_e");
+        assert_eq!(sanitize_id("{x}", IdFormat::Lola), "_x_");
+        assert_eq!(sanitize_id("42", IdFormat::Lola), "_42");
+        assert_eq!(sanitize_id("p-1.x", IdFormat::Lola), "p-1.x");
+        assert_eq!(sanitize_id("PLACE", IdFormat::Lola), "PLACE_");
+        assert_eq!(sanitize_id("", IdFormat::Lola), "_");
+    }
+}
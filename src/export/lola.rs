@@ -1,41 +1,54 @@
-use crate::petri_net::{PetriNet, PlaceRef};
-use std::collections::BTreeSet;
+use crate::error::Error;
+use crate::export::ids::{ExportIds, IdFormat};
+use crate::petri_net::{ArcKind, PetriNet, PlaceRef};
+use std::collections::BTreeMap;
 
 impl PetriNet {
     /// Converts the net to a string in the format accepted by the `LoLA` model checker and returns it.
     ///
     /// # Errors
     ///
+    /// If the net contains an inhibitor or a reset arc, then an error is returned.
     /// If the writer fails to write the contents of the net, then an error is returned.
-    pub fn to_lola_string(&self) -> Result<String, std::io::Error> {
+    pub fn to_lola_string(&self) -> Result<String, Error> {
         let mut writer = Vec::new();
         self.to_lola(&mut writer)?;
         String::from_utf8(writer).map_err(|_|
             // This error could only be due to a bug, map it to a more standard error type.
-            std::io::Error::other(
+            Error::Io(std::io::Error::other(
                 "Could not convert the string to UTF-8",
-            ))
+            )))
     }
 
     /// Converts the net to the format accepted by the `LoLA` model checker.
+    /// The places and transitions are named with the identifiers generated by `ExportIds`.
+    /// Read arcs are written as a consumption and a production of the same weight.
     /// Writes the output to a trait object which implements `std::io::Write`.
     ///
     /// # Errors
     ///
+    /// If the net contains an inhibitor or a reset arc, which the format cannot express,
+    /// then an error is returned before writing anything.
     /// If the writer fails to write the contents of the net, then an error is returned.
-    pub fn to_lola<T>(&self, writer: &mut T) -> Result<(), std::io::Error>
+    pub fn to_lola<T>(&self, writer: &mut T) -> Result<(), Error>
     where
         T: std::io::Write,
     {
-        self.write_lola_places(writer)?;
-        self.write_lola_initial_marking(writer)?;
-        self.write_lola_transitions(writer)?;
+        self.check_no_arc_kinds(&[ArcKind::Inhibitor, ArcKind::Reset])?;
+        let ids = self.export_ids(IdFormat::Lola);
+        self.write_lola_places(&ids, writer)?;
+        self.write_lola_initial_marking(&ids, writer)?;
+        self.write_lola_transitions(&ids, writer)?;
         Ok(())
     }
 
     /// Writes the lines that define the places
     /// to a trait object which implements `std::io::Write`.
-    fn write_lola_places<T>(&self, writer: &mut T) -> Result<(), std::io::Error>
+    ///
+    /// Places are written in the order of the net. Every run of consecutive places
+    /// with the same capacity forms a group, which starts with a `SAFE n :` declaration
+    /// if the places have a capacity.
+    fn write_lola_places<T>(&self, ids: &ExportIds, writer: &mut T) -> Result<(), std::io::Error>
     where
         T: std::io::Write,
     {
@@ -44,22 +57,43 @@ impl PetriNet {
         }
         writer.write_all(b"PLACE\n")?;
 
-        let last_index = self.get_cardinality_places() - 1;
-        for (i, (place_ref, _)) in self.places_iter().enumerate() {
-            let line = if i == last_index {
-                // Last place line has a semicolon and an empty line.
-                format!("    {place_ref};\n\n")
-            } else {
-                format!("    {place_ref},\n")
-            };
-            writer.write_all(line.as_bytes())?;
+        let mut groups: Vec<(Option<usize>, Vec<&str>)> = Vec::new();
+        for (place_ref, place) in self.places_iter() {
+            let place_id = ids.place(place_ref);
+            match groups.last_mut() {
+                Some((capacity, place_ids)) if *capacity == place.capacity() => {
+                    place_ids.push(place_id);
+                }
+                _ => groups.push((place.capacity(), vec![place_id])),
+            }
+        }
+        for (capacity, place_ids) in groups {
+            if let Some(capacity) = capacity {
+                let header_line = format!("SAFE {capacity} :\n");
+                writer.write_all(header_line.as_bytes())?;
+            }
+            let last_index = place_ids.len() - 1;
+            for (i, place_id) in place_ids.iter().enumerate() {
+                let line = if i == last_index {
+                    // Last place line of every group has a semicolon.
+                    format!("    {place_id};\n")
+                } else {
+                    format!("    {place_id},\n")
+                };
+                writer.write_all(line.as_bytes())?;
+            }
         }
+        writer.write_all(b"\n")?;
         Ok(())
     }
 
     /// Writes the lines that define the initial marking
     /// to a trait object which implements `std::io::Write`.
-    fn write_lola_initial_marking<T>(&self, writer: &mut T) -> Result<(), std::io::Error>
+    fn write_lola_initial_marking<T>(
+        &self,
+        ids: &ExportIds,
+        writer: &mut T,
+    ) -> Result<(), std::io::Error>
     where
         T: std::io::Write,
     {
@@ -70,7 +104,8 @@ impl PetriNet {
 
         let last_index = self.get_cardinality_places() - 1;
         for (i, (place_ref, place)) in self.places_iter().enumerate() {
-            let marking = place.marking();
+            let place_ref = ids.place(place_ref);
+            let marking = place.initial_marking();
 
             let line = if i == last_index {
                 // Last marking line has a semicolon and an empty line.
@@ -85,16 +120,27 @@ impl PetriNet {
 
     /// Writes the lines that define the transitions
     /// to a trait object which implements `std::io::Write`.
-    fn write_lola_transitions<T>(&self, writer: &mut T) -> Result<(), std::io::Error>
+    fn write_lola_transitions<T>(
+        &self,
+        ids: &ExportIds,
+        writer: &mut T,
+    ) -> Result<(), std::io::Error>
     where
         T: std::io::Write,
     {
         for (transition_ref, transition) in self.transitions_iter() {
-            let header_line = format!("TRANSITION {transition_ref}\n");
+            let header_line = format!("TRANSITION {}\n", ids.transition(transition_ref));
             writer.write_all(header_line.as_bytes())?;
 
-            Self::write_transition_arcs(transition.get_preset(), "CONSUME", writer)?;
-            Self::write_transition_arcs(transition.get_postset(), "PRODUCE", writer)?;
+            let mut consume = transition.get_preset().clone();
+            let mut produce = transition.get_postset().clone();
+            for (place_ref, (_, weight)) in transition.get_extended_preset() {
+                // Only read arcs are left, they put back the tokens they consume.
+                *consume.entry(place_ref.clone()).or_default() += weight;
+                *produce.entry(place_ref.clone()).or_default() += weight;
+            }
+            Self::write_transition_arcs(&consume, "CONSUME", ids, writer)?;
+            Self::write_transition_arcs(&produce, "PRODUCE", ids, writer)?;
         }
         Ok(())
     }
@@ -103,8 +149,9 @@ impl PetriNet {
     // that define tokens from which places are consumed and produced when the transition is fired.
     // The result is written to a trait object which implements `std::io::Write`.
     fn write_transition_arcs<T>(
-        set: &BTreeSet<PlaceRef>,
+        set: &BTreeMap<PlaceRef, usize>,
         header: &str,
+        ids: &ExportIds,
         writer: &mut T,
     ) -> Result<(), std::io::Error>
     where
@@ -120,13 +167,13 @@ impl PetriNet {
         writer.write_all(header_line.as_bytes())?;
 
         let last_index = set.len() - 1;
-        for (i, place_ref) in set.iter().enumerate() {
-            // Edge multiplicity is always 1 for now.
+        for (i, (place_ref, weight)) in set.iter().enumerate() {
+            let place_ref = ids.place(place_ref);
             let line = if i == last_index {
                 // Last line has a semicolon and an empty line.
-                format!("    {place_ref} : 1;\n")
+                format!("    {place_ref} : {weight};\n")
             } else {
-                format!("    {place_ref} : 1,\n")
+                format!("    {place_ref} : {weight},\n")
             };
             writer.write_all(line.as_bytes())?;
         }
@@ -177,6 +224,18 @@ mod lola_tests {
         assert_eq!(result.unwrap().as_str(), LOLA_STRING_MARKED_PLACES_NET);
     }
 
+    #[test]
+    fn lola_string_ignores_fired_transitions() {
+        let (mut net, place_refs, transition_refs) = create_net_chain_topology(3);
+        assert!(net.add_token(&place_refs[0], 1).is_ok());
+        assert!(net.fire(&transition_refs[0]).is_ok());
+        assert!(net.fire(&transition_refs[1]).is_ok());
+        let result = net.to_lola_string();
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), LOLA_STRING_MARKED_NET_WITH_CHAIN_TOPOLOGY);
+    }
+
     #[test]
     fn lola_string_only_empty_transitions_net() {
         let (net, _, _) = create_basic_unconnected_net(0, 5);
@@ -203,4 +262,111 @@ mod lola_tests {
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), LOLA_STRING_NET_WITH_LOOP_TOPOLOGY);
     }
+
+    #[test]
+    fn lola_string_net_with_weighted_arcs() {
+        let mut net = PetriNet::new();
+        let p1 = net.add_place("P1");
+        let p2 = net.add_place("P2");
+        let t1 = net.add_transition("T1");
+
+        assert!(net.add_weighted_arc_place_transition(&p1, &t1, 2).is_ok());
+        assert!(net.add_weighted_arc_transition_place(&t1, &p2, 3).is_ok());
+        assert!(net.add_token(&p1, 2).is_ok());
+        let result = net.to_lola_string();
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), LOLA_STRING_NET_WITH_WEIGHTED_ARCS);
+    }
+
+    #[test]
+    fn lola_string_duplicate_labels_get_unique_ids() {
+        let mut net = PetriNet::new();
+        let first = net.add_place("mutex");
+        let second = net.add_place("mutex");
+        let transition_ref = net.add_transition("lock step");
+        assert!(
+            net.add_arc_place_transition(&first, &transition_ref)
+                .is_ok()
+        );
+        assert!(
+            net.add_arc_place_transition(&second, &transition_ref)
+                .is_ok()
+        );
+        let result = net.to_lola_string().unwrap();
+
+        assert!(result.starts_with("PLACE\n    mutex,\n    mutex_2;\n"));
+        assert!(result.contains("TRANSITION lock_step\n  CONSUME\n"));
+        let imported = PetriNet::from_lola_string(&result).unwrap();
+        assert_eq!(imported.get_cardinality_places(), 2);
+        assert_eq!(imported.find_arcs_place_transition().len(), 2);
+    }
+
+    #[test]
+    fn lola_string_net_with_capacities() {
+        let mut net = PetriNet::new();
+        let p1 = net.add_place("P1");
+        let p2 = net.add_place("P2");
+        let p3 = net.add_place("P3");
+        net.add_place("P4");
+        assert!(net.set_capacity(&p1, Some(3)).is_ok());
+        assert!(net.set_capacity(&p2, Some(1)).is_ok());
+        assert!(net.set_capacity(&p3, Some(3)).is_ok());
+        let result = net.to_lola_string();
+
+        assert!(result.is_ok());
+        let result = result.unwrap();
+        assert_eq!(result, LOLA_STRING_NET_WITH_CAPACITIES);
+        let imported = PetriNet::from_lola_string(&result).unwrap();
+        let capacities: Vec<Option<usize>> = imported
+            .places_iter()
+            .map(|(_, place)| place.capacity())
+            .collect();
+        assert_eq!(capacities, [Some(3), Some(1), Some(3), None]);
+    }
+
+    #[test]
+    fn lola_string_net_with_inhibitor_or_reset_arc_returns_err() {
+        let (mut net, place_refs, transition_refs) = create_net_chain_topology(2);
+        assert!(
+            net.add_inhibitor_arc(&place_refs[1], &transition_refs[0], 1)
+                .is_ok()
+        );
+        let mut writer = Vec::new();
+        let result = net.to_lola(&mut writer);
+
+        assert!(matches!(
+            result,
+            Err(Error::UnsupportedArc(_, ArcKind::Inhibitor))
+        ));
+        assert!(writer.is_empty());
+
+        assert!(
+            net.remove_extended_arc(&place_refs[1], &transition_refs[0])
+                .is_ok()
+        );
+        assert!(
+            net.add_reset_arc(&place_refs[1], &transition_refs[0])
+                .is_ok()
+        );
+        assert!(net.to_lola_string().is_err());
+    }
+
+    #[test]
+    fn lola_string_net_with_read_arc_writes_a_loop() {
+        let mut net = PetriNet::new();
+        let guard = net.add_place("guard");
+        let output = net.add_place("output");
+        let transition_ref = net.add_transition("t");
+        assert!(net.add_read_arc(&guard, &transition_ref, 2).is_ok());
+        assert!(
+            net.add_arc_transition_place(&transition_ref, &output)
+                .is_ok()
+        );
+        let result = net.to_lola_string().unwrap();
+
+        assert!(result.ends_with(
+            "TRANSITION t\n  CONSUME\n    guard : 2;\n  PRODUCE\n    guard : 2,\n    output : 1;\n"
+        ));
+    }
 }
@@ -1,8 +1,14 @@
-use crate::petri_net::PetriNet;
+use crate::error::Error;
+use crate::export::ids::{ExportIds, IdFormat};
+use crate::petri_net::{ArcKind, ArcRef, PetriNet};
 use xml::writer::{EmitterConfig, EventWriter, Result as XmlResult, XmlEvent};
 
 const XML_PNML_DEFAULT_NAMESPACE: &str = "http://www.pnml.org/version-2009/grammar/pnml";
 const XML_PNML_DEFAULT_GRAMMAR: &str = "http://www.pnml.org/version-2009/grammar/ptnet";
+/// The name of this library in the `toolspecific` elements of PNML.
+pub const XML_PNML_TOOL_NAME: &str = "netcrab";
+/// The version of the contents of the `toolspecific` elements of PNML.
+pub const XML_PNML_TOOL_VERSION: &str = "1.0";
 
 impl PetriNet {
     /// Converts the net to a string in PNML format and returns it.
@@ -10,30 +16,31 @@ impl PetriNet {
     /// # Errors
     ///
     /// If the writer fails to write the contents of the net, then an error is returned.
-    pub fn to_pnml_string(&self) -> Result<String, std::io::Error> {
+    pub fn to_pnml_string(&self) -> Result<String, Error> {
         let mut writer = Vec::new();
         self.to_pnml(&mut writer)?;
         String::from_utf8(writer).map_err(|_|
             // This error could only be due to a bug, map it to a more standard error type.
-            std::io::Error::other(
+            Error::Io(std::io::Error::other(
                 "Could not convert the string to UTF-8",
-            ))
+            )))
     }
 
     /// Converts the net to the PNML format.
+    /// The labels are written as names and the identifiers are generated by `ExportIds`.
     /// Writes the output to a trait object which implements `std::io::Write`.
     ///
     /// # Errors
     ///
     /// If the writer fails to write the contents of the net, then an error is returned.
-    pub fn to_pnml<T>(&self, writer: &mut T) -> Result<(), std::io::Error>
+    pub fn to_pnml<T>(&self, writer: &mut T) -> Result<(), Error>
     where
         T: std::io::Write,
     {
         self.write_pnml(writer).map_err(|_| {
             // Map the XML error of the library to a more standard error type
             // to stay consistent with the other export formats.
-            std::io::Error::other("Could not convert the net to PNML")
+            Error::Io(std::io::Error::other("Could not convert the net to PNML"))
         })
     }
 
@@ -59,9 +66,10 @@ impl PetriNet {
         )?;
         xml_writer.write(XmlEvent::start_element("page").attr("id", "page0"))?;
 
-        self.write_pnml_places(&mut xml_writer)?;
-        self.write_pnml_transitions(&mut xml_writer)?;
-        self.write_pnml_arcs(&mut xml_writer)?;
+        let ids = self.export_ids(IdFormat::Pnml);
+        self.write_pnml_places(&ids, &mut xml_writer)?;
+        self.write_pnml_transitions(&ids, &mut xml_writer)?;
+        self.write_pnml_arcs(&ids, &mut xml_writer)?;
 
         // Close the tags of the general properties of the XML.
         xml_writer.write(XmlEvent::end_element())?;
@@ -72,15 +80,17 @@ impl PetriNet {
 
     /// Writes the XML elements that define the places
     /// to an instance of `xml::writer::Writer`.
-    fn write_pnml_places<T>(&self, writer: &mut EventWriter<T>) -> XmlResult<()>
+    fn write_pnml_places<T>(&self, ids: &ExportIds, writer: &mut EventWriter<T>) -> XmlResult<()>
     where
         T: std::io::Write,
     {
         for (place_ref, place) in self.places_iter() {
-            let place_xml_element = XmlEvent::start_element("place").attr("id", place_ref.label());
+            let place_xml_element =
+                XmlEvent::start_element("place").attr("id", ids.place(place_ref));
             writer.write(place_xml_element)?;
             Self::label_to_pnml(place_ref.label(), writer)?;
-            Self::marking_to_pnml(place.marking(), writer)?;
+            Self::marking_to_pnml(place.initial_marking(), writer)?;
+            Self::capacity_to_pnml(place.capacity(), writer)?;
             writer.write(XmlEvent::end_element())?;
         }
         Ok(())
@@ -88,13 +98,17 @@ impl PetriNet {
 
     /// Writes the XML elements that define the transitions
     /// to an instance of `xml::writer::Writer`.
-    fn write_pnml_transitions<T>(&self, writer: &mut EventWriter<T>) -> XmlResult<()>
+    fn write_pnml_transitions<T>(
+        &self,
+        ids: &ExportIds,
+        writer: &mut EventWriter<T>,
+    ) -> XmlResult<()>
     where
         T: std::io::Write,
     {
         for (transition_ref, _) in self.transitions_iter() {
             let transition_xml_element =
-                XmlEvent::start_element("transition").attr("id", transition_ref.label());
+                XmlEvent::start_element("transition").attr("id", ids.transition(transition_ref));
             writer.write(transition_xml_element)?;
             Self::label_to_pnml(transition_ref.label(), writer)?;
             writer.write(XmlEvent::end_element())?;
@@ -104,18 +118,56 @@ impl PetriNet {
 
     /// Writes the XML elements that define the arcs
     /// to an instance of `xml::writer::Writer`.
-    fn write_pnml_arcs<T>(&self, writer: &mut EventWriter<T>) -> XmlResult<()>
+    fn write_pnml_arcs<T>(&self, ids: &ExportIds, writer: &mut EventWriter<T>) -> XmlResult<()>
     where
         T: std::io::Write,
     {
-        let arcs = self.find_arcs_place_transition();
-        for (place_ref, transition_ref) in arcs {
-            Self::write_arc(place_ref.label(), transition_ref.label(), writer)?;
+        let arcs = self.find_weighted_arcs_place_transition();
+        for ((place_ref, transition_ref), weight) in arcs {
+            let source = ids.place(&place_ref);
+            let dest = ids.transition(&transition_ref);
+            let arc_ref = ArcRef::PlaceTransition(place_ref, transition_ref);
+            Self::write_arc(
+                source,
+                dest,
+                ids.arc(&arc_ref),
+                &arc_ref,
+                weight,
+                ArcKind::Normal,
+                writer,
+            )?;
+        }
+
+        let arcs = self.find_weighted_arcs_transition_place();
+        for ((transition_ref, place_ref), weight) in arcs {
+            let source = ids.transition(&transition_ref);
+            let dest = ids.place(&place_ref);
+            let arc_ref = ArcRef::TransitionPlace(transition_ref, place_ref);
+            Self::write_arc(
+                source,
+                dest,
+                ids.arc(&arc_ref),
+                &arc_ref,
+                weight,
+                ArcKind::Normal,
+                writer,
+            )?;
         }
 
-        let arcs = self.find_arcs_transition_place();
-        for (transition_ref, place_ref) in arcs {
-            Self::write_arc(transition_ref.label(), place_ref.label(), writer)?;
+        let arcs = self.find_extended_arcs();
+        for ((place_ref, transition_ref), (kind, weight)) in arcs {
+            let source = ids.place(&place_ref);
+            let dest = ids.transition(&transition_ref);
+            let arc_ref = ArcRef::PlaceTransition(place_ref, transition_ref);
+            Self::write_arc(
+                source,
+                dest,
+                ids.arc(&arc_ref),
+                &arc_ref,
+                weight,
+                kind,
+                writer,
+            )?;
         }
 
         Ok(())
@@ -123,25 +175,35 @@ impl PetriNet {
 
     /// Writes a single arc in the net as a XML node
     /// as required by the PNML standard.
+    /// The name of the arc is built from the labels of its source and destination.
+    /// Arcs whose kind is not `ArcKind::Normal` are marked with the attribute `type`,
+    /// e.g. `type="inhibitor"`.
     fn write_arc<T>(
-        source: &String,
-        dest: &String,
+        source: &str,
+        dest: &str,
+        id: &str,
+        arc_ref: &ArcRef,
+        weight: usize,
+        kind: ArcKind,
         xml_writer: &mut EventWriter<T>,
     ) -> XmlResult<()>
     where
         T: std::io::Write,
     {
-        let arc_label = format!("({source}, {dest})");
-        let start_element = XmlEvent::start_element("arc")
+        let arc_label = arc_ref.to_string();
+        let arc_type = kind.to_string();
+        let mut start_element = XmlEvent::start_element("arc")
             .attr("source", source)
             .attr("target", dest)
-            .attr("id", &arc_label);
+            .attr("id", id);
+        if kind != ArcKind::Normal {
+            start_element = start_element.attr("type", &arc_type);
+        }
         xml_writer.write(start_element)?;
         Self::label_to_pnml(&arc_label, xml_writer)?;
         xml_writer.write(XmlEvent::start_element("inscription"))?;
         xml_writer.write(XmlEvent::start_element("text"))?;
-        // Weights in arcs are not supported for now.
-        xml_writer.write(XmlEvent::Characters("1"))?;
+        xml_writer.write(XmlEvent::Characters(&weight.to_string()))?;
         xml_writer.write(XmlEvent::end_element())?;
         xml_writer.write(XmlEvent::end_element())?;
         xml_writer.write(XmlEvent::end_element())?;
@@ -162,7 +224,33 @@ impl PetriNet {
         Ok(())
     }
 
-    /// Writes the marking of a place as a XML node
+    /// Writes the capacity of a place as a `toolspecific` XML node,
+    /// since the PNML standard for place/transition nets does not define capacities.
+    fn capacity_to_pnml<T>(
+        capacity: Option<usize>,
+        xml_writer: &mut EventWriter<T>,
+    ) -> XmlResult<()>
+    where
+        T: std::io::Write,
+    {
+        let Some(capacity) = capacity else {
+            return Ok(());
+        };
+        xml_writer.write(
+            XmlEvent::start_element("toolspecific")
+                .attr("tool", XML_PNML_TOOL_NAME)
+                .attr("version", XML_PNML_TOOL_VERSION),
+        )?;
+        xml_writer.write(XmlEvent::start_element("capacity"))?;
+        xml_writer.write(XmlEvent::start_element("text"))?;
+        xml_writer.write(XmlEvent::Characters(&capacity.to_string()))?;
+        xml_writer.write(XmlEvent::end_element())?;
+        xml_writer.write(XmlEvent::end_element())?;
+        xml_writer.write(XmlEvent::end_element())?;
+        Ok(())
+    }
+
+    /// Writes the initial marking of a place as a XML node
     /// as required by the PNML standard.
     fn marking_to_pnml<T>(marking: usize, xml_writer: &mut EventWriter<T>) -> XmlResult<()>
     where
@@ -250,4 +338,66 @@ mod pnml_tests {
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), PNML_STRING_NET_WITH_LOOP_TOPOLOGY);
     }
+
+    #[test]
+    fn pnml_string_net_with_weighted_arcs() {
+        let mut net = PetriNet::new();
+        let p1 = net.add_place("P1");
+        let p2 = net.add_place("P2");
+        let t1 = net.add_transition("T1");
+
+        assert!(net.add_weighted_arc_place_transition(&p1, &t1, 2).is_ok());
+        assert!(net.add_weighted_arc_transition_place(&t1, &p2, 3).is_ok());
+        assert!(net.add_token(&p1, 2).is_ok());
+        let result = net.to_pnml_string();
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), PNML_STRING_NET_WITH_WEIGHTED_ARCS);
+    }
+
+    #[test]
+    fn pnml_string_duplicate_labels_get_unique_ids() {
+        let mut net = PetriNet::new();
+        net.add_place("mutex");
+        net.add_place("mutex");
+        net.add_transition("lock step");
+        let result = net.to_pnml_string().unwrap();
+
+        assert!(result.contains(r#"<place id="mutex">"#));
+        assert!(result.contains(r#"<place id="mutex_2">"#));
+        assert!(result.contains(r#"<transition id="lock_step">"#));
+        assert_eq!(result.matches("<text>mutex</text>").count(), 2);
+        assert!(result.contains("<text>lock step</text>"));
+    }
+
+    #[test]
+    fn pnml_string_net_with_capacities() {
+        let mut net = PetriNet::new();
+        let p1 = net.add_place("P1");
+        net.add_place("P2");
+        assert!(net.add_token(&p1, 1).is_ok());
+        assert!(net.set_capacity(&p1, Some(3)).is_ok());
+        let result = net.to_pnml_string();
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), PNML_STRING_NET_WITH_CAPACITIES);
+    }
+
+    #[test]
+    fn pnml_string_net_with_extended_arcs() {
+        let mut net = PetriNet::new();
+        let p1 = net.add_place("P1");
+        let p2 = net.add_place("P2");
+        let p3 = net.add_place("P3");
+        let t1 = net.add_transition("T1");
+        assert!(net.add_inhibitor_arc(&p1, &t1, 2).is_ok());
+        assert!(net.add_read_arc(&p2, &t1, 1).is_ok());
+        assert!(net.add_reset_arc(&p3, &t1).is_ok());
+        let result = net.to_pnml_string().unwrap();
+
+        assert!(result.contains(r#"<arc source="P1" target="T1" id="P1_T1" type="inhibitor">"#));
+        assert!(result.contains(r#"<arc source="P2" target="T1" id="P2_T1" type="read">"#));
+        assert!(result.contains(r#"<arc source="P3" target="T1" id="P3_T1" type="reset">"#));
+        assert!(result.contains("<text>2</text>"));
+    }
 }
@@ -56,6 +56,15 @@ pub const DOT_STRING_NET_WITH_LOOP_TOPOLOGY: &str = r#"digraph petrinet {
 }
 "#;
 
+pub const DOT_STRING_NET_WITH_WEIGHTED_ARCS: &str = r#"digraph petrinet {
+    P1 [shape="circle" xlabel="P1" label="••"];
+    P2 [shape="circle" xlabel="P2" label=""];
+    T1 [shape="box" xlabel="" label="T1"];
+    P1 -> T1 [label="2"];
+    T1 -> P2 [label="3"];
+}
+"#;
+
 pub const LOLA_STRING_ONLY_EMPTY_PLACES_NET: &str = r"PLACE
     P1,
     P2,
@@ -127,6 +136,28 @@ TRANSITION T2
     P3 : 1;
 ";
 
+pub const LOLA_STRING_MARKED_NET_WITH_CHAIN_TOPOLOGY: &str = r"PLACE
+    P1,
+    P2,
+    P3;
+
+MARKING
+    P1 : 1,
+    P2 : 0,
+    P3 : 0;
+
+TRANSITION T1
+  CONSUME
+    P1 : 1;
+  PRODUCE
+    P2 : 1;
+TRANSITION T2
+  CONSUME
+    P2 : 1;
+  PRODUCE
+    P3 : 1;
+";
+
 pub const LOLA_STRING_NET_WITH_LOOP_TOPOLOGY: &str = r"PLACE
     P1;
 
@@ -140,6 +171,44 @@ TRANSITION T1
     P1 : 1;
 ";
 
+pub const LOLA_STRING_NET_WITH_WEIGHTED_ARCS: &str = r"PLACE
+    P1,
+    P2;
+
+MARKING
+    P1 : 2,
+    P2 : 0;
+
+TRANSITION T1
+  CONSUME
+    P1 : 2;
+  PRODUCE
+    P2 : 3;
+";
+
+pub const LOLA_STRING_NET_WITH_CAPACITIES: &str = r"PLACE
+SAFE 3 :
+    P1;
+SAFE 1 :
+    P2;
+SAFE 3 :
+    P3;
+    P4;
+
+MARKING
+    P1 : 0,
+    P2 : 0,
+    P3 : 0,
+    P4 : 0;
+
+";
+
+pub const DOT_STRING_NET_WITH_CAPACITIES: &str = r#"digraph petrinet {
+    P1 [shape="circle" xlabel="P1\nK=3" label="•"];
+    P2 [shape="circle" xlabel="P2" label=""];
+}
+"#;
+
 pub const PNML_STRING_EMPTY_NET: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
 <pnml xmlns="http://www.pnml.org/version-2009/grammar/pnml">
   <net id="net0" type="http://www.pnml.org/version-2009/grammar/ptnet">
@@ -290,7 +359,7 @@ pub const PNML_STRING_NET_WITH_CHAIN_TOPOLOPY: &str = r#"<?xml version="1.0" enc
           <text>T2</text>
         </name>
       </transition>
-      <arc source="P1" target="T1" id="(P1, T1)">
+      <arc source="P1" target="T1" id="P1_T1">
         <name>
           <text>(P1, T1)</text>
         </name>
@@ -298,7 +367,7 @@ pub const PNML_STRING_NET_WITH_CHAIN_TOPOLOPY: &str = r#"<?xml version="1.0" enc
           <text>1</text>
         </inscription>
       </arc>
-      <arc source="P2" target="T2" id="(P2, T2)">
+      <arc source="P2" target="T2" id="P2_T2">
         <name>
           <text>(P2, T2)</text>
         </name>
@@ -306,7 +375,7 @@ pub const PNML_STRING_NET_WITH_CHAIN_TOPOLOPY: &str = r#"<?xml version="1.0" enc
           <text>1</text>
         </inscription>
       </arc>
-      <arc source="T1" target="P2" id="(T1, P2)">
+      <arc source="T1" target="P2" id="T1_P2">
         <name>
           <text>(T1, P2)</text>
         </name>
@@ -314,7 +383,7 @@ pub const PNML_STRING_NET_WITH_CHAIN_TOPOLOPY: &str = r#"<?xml version="1.0" enc
           <text>1</text>
         </inscription>
       </arc>
-      <arc source="T2" target="P3" id="(T2, P3)">
+      <arc source="T2" target="P3" id="T2_P3">
         <name>
           <text>(T2, P3)</text>
         </name>
@@ -340,7 +409,7 @@ pub const PNML_STRING_NET_WITH_LOOP_TOPOLOGY: &str = r#"<?xml version="1.0" enco
           <text>T1</text>
         </name>
       </transition>
-      <arc source="P1" target="T1" id="(P1, T1)">
+      <arc source="P1" target="T1" id="P1_T1">
         <name>
           <text>(P1, T1)</text>
         </name>
@@ -348,7 +417,7 @@ pub const PNML_STRING_NET_WITH_LOOP_TOPOLOGY: &str = r#"<?xml version="1.0" enco
           <text>1</text>
         </inscription>
       </arc>
-      <arc source="T1" target="P1" id="(T1, P1)">
+      <arc source="T1" target="P1" id="T1_P1">
         <name>
           <text>(T1, P1)</text>
         </name>
@@ -359,3 +428,71 @@ pub const PNML_STRING_NET_WITH_LOOP_TOPOLOGY: &str = r#"<?xml version="1.0" enco
     </page>
   </net>
 </pnml>"#;
+
+pub const PNML_STRING_NET_WITH_WEIGHTED_ARCS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<pnml xmlns="http://www.pnml.org/version-2009/grammar/pnml">
+  <net id="net0" type="http://www.pnml.org/version-2009/grammar/ptnet">
+    <page id="page0">
+      <place id="P1">
+        <name>
+          <text>P1</text>
+        </name>
+        <initialMarking>
+          <text>2</text>
+        </initialMarking>
+      </place>
+      <place id="P2">
+        <name>
+          <text>P2</text>
+        </name>
+      </place>
+      <transition id="T1">
+        <name>
+          <text>T1</text>
+        </name>
+      </transition>
+      <arc source="P1" target="T1" id="P1_T1">
+        <name>
+          <text>(P1, T1)</text>
+        </name>
+        <inscription>
+          <text>2</text>
+        </inscription>
+      </arc>
+      <arc source="T1" target="P2" id="T1_P2">
+        <name>
+          <text>(T1, P2)</text>
+        </name>
+        <inscription>
+          <text>3</text>
+        </inscription>
+      </arc>
+    </page>
+  </net>
+</pnml>"#;
+
+pub const PNML_STRING_NET_WITH_CAPACITIES: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<pnml xmlns="http://www.pnml.org/version-2009/grammar/pnml">
+  <net id="net0" type="http://www.pnml.org/version-2009/grammar/ptnet">
+    <page id="page0">
+      <place id="P1">
+        <name>
+          <text>P1</text>
+        </name>
+        <initialMarking>
+          <text>1</text>
+        </initialMarking>
+        <toolspecific tool="netcrab" version="1.0">
+          <capacity>
+            <text>3</text>
+          </capacity>
+        </toolspecific>
+      </place>
+      <place id="P2">
+        <name>
+          <text>P2</text>
+        </name>
+      </place>
+    </page>
+  </net>
+</pnml>"#;
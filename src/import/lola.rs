@@ -0,0 +1,613 @@
+use crate::error::Error;
+use crate::petri_net::{PetriNet, PlaceRef};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A token of the `LoLA` net syntax with the position where it starts.
+#[derive(Clone, Debug)]
+struct Token {
+    text: String,
+    line: u64,
+    column: u64,
+}
+
+/// Splits the `LoLA` net file into tokens, skipping whitespace and comments.
+/// Block comments `{ ... }` and `/* ... */` as well as line comments `// ...` are supported.
+fn tokenize_lola(string: &str) -> Result<Vec<Token>, Error> {
+    let mut tokens = Vec::new();
+    let mut chars = string.chars().peekable();
+    let (mut line, mut column) = (1, 1);
+
+    while let Some(&character) = chars.peek() {
+        let (start_line, start_column) = (line, column);
+        let mut advance = |chars: &mut std::iter::Peekable<std::str::Chars>| {
+            let next = chars.next();
+            if next == Some('\n') {
+                line += 1;
+                column = 1;
+            } else {
+                column += 1;
+            }
+            next
+        };
+
+        if character.is_whitespace() {
+            advance(&mut chars);
+        } else if character == '{' {
+            loop {
+                match advance(&mut chars) {
+                    Some('}') => break,
+                    Some(_) => {}
+                    None => return Err(unterminated_comment(start_line, start_column)),
+                }
+            }
+        } else if character == '/' {
+            advance(&mut chars);
+            match chars.peek() {
+                Some('/') => {
+                    while !matches!(chars.peek(), Some('\n') | None) {
+                        advance(&mut chars);
+                    }
+                }
+                Some('*') => {
+                    advance(&mut chars);
+                    let mut previous = ' ';
+                    loop {
+                        match advance(&mut chars) {
+                            Some('/') if previous == '*' => break,
+                            Some(next) => previous = next,
+                            None => return Err(unterminated_comment(start_line, start_column)),
+                        }
+                    }
+                }
+                _ => {
+                    return Err(Error::Parse {
+                        line: start_line,
+                        column: start_column,
+                        message: "Unexpected character `/`".to_string(),
+                    });
+                }
+            }
+        } else if matches!(character, ',' | ';' | ':') {
+            advance(&mut chars);
+            tokens.push(Token {
+                text: character.to_string(),
+                line: start_line,
+                column: start_column,
+            });
+        } else {
+            let mut text = String::new();
+            while let Some(&next) = chars.peek() {
+                if next.is_whitespace() || matches!(next, ',' | ';' | ':' | '{' | '}') {
+                    break;
+                }
+                text.push(next);
+                advance(&mut chars);
+            }
+            tokens.push(Token {
+                text,
+                line: start_line,
+                column: start_column,
+            });
+        }
+    }
+    Ok(tokens)
+}
+
+/// Creates the error for a comment that is not closed before the end of the input.
+fn unterminated_comment(line: u64, column: u64) -> Error {
+    Error::Parse {
+        line,
+        column,
+        message: "Unterminated comment".to_string(),
+    }
+}
+
+/// A parser over the tokens of a `LoLA` net file.
+struct LolaParser {
+    tokens: Vec<Token>,
+    index: usize,
+    end_line: u64,
+    end_column: u64,
+}
+
+impl LolaParser {
+    /// Returns the text of the next token without consuming it.
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.index).map(|token| token.text.as_str())
+    }
+
+    /// Consumes the next token and returns it.
+    fn next(&mut self) -> Result<&Token, Error> {
+        if self.index >= self.tokens.len() {
+            return Err(Error::Parse {
+                line: self.end_line,
+                column: self.end_column,
+                message: "Unexpected end of input".to_string(),
+            });
+        }
+        self.index += 1;
+        Ok(&self.tokens[self.index - 1])
+    }
+
+    /// Returns the last token consumed. Must only be called after a token was consumed.
+    fn previous(&self) -> &Token {
+        &self.tokens[self.index - 1]
+    }
+
+    /// Consumes the next token if it is equal to the expected text.
+    fn accept(&mut self, expected: &str) -> bool {
+        if self.peek() == Some(expected) {
+            self.index += 1;
+            return true;
+        }
+        false
+    }
+
+    /// Consumes the next token and fails if it is not equal to the expected text.
+    fn expect(&mut self, expected: &str) -> Result<(), Error> {
+        let token = self.next()?;
+        if token.text != expected {
+            return Err(Self::error(token, format!("Expected `{expected}`")));
+        }
+        Ok(())
+    }
+
+    /// Consumes the next token and returns it if it is an identifier.
+    fn identifier(&mut self) -> Result<&Token, Error> {
+        let token = self.next()?;
+        if matches!(token.text.as_str(), "," | ";" | ":") || is_lola_keyword(&token.text) {
+            return Err(Self::error(token, "Expected an identifier"));
+        }
+        Ok(token)
+    }
+
+    /// Consumes the next token and returns it as a number.
+    fn number(&mut self) -> Result<usize, Error> {
+        let token = self.next()?;
+        token
+            .text
+            .parse::<usize>()
+            .map_err(|_| Self::error(token, "Expected a non-negative number"))
+    }
+
+    /// Creates a parse error at the position of the given token.
+    fn error(token: &Token, message: impl Into<String>) -> Error {
+        Error::Parse {
+            line: token.line,
+            column: token.column,
+            message: format!("{}, found `{}`", message.into(), token.text),
+        }
+    }
+
+    /// Turns an error of an operation on the net into a parse error at the position of the token
+    /// that caused it, in the same way as the PNML importer does.
+    fn semantic_error(token: &Token, error: &Error) -> Error {
+        Error::Parse {
+            line: token.line,
+            column: token.column,
+            message: error.to_string(),
+        }
+    }
+}
+
+/// Returns `true` if the text is one of the keywords of the `LoLA` net syntax.
+fn is_lola_keyword(text: &str) -> bool {
+    matches!(
+        text,
+        "PLACE"
+            | "MARKING"
+            | "TRANSITION"
+            | "CONSUME"
+            | "PRODUCE"
+            | "SAFE"
+            | "STRONG"
+            | "WEAK"
+            | "FAIR"
+    )
+}
+
+impl PetriNet {
+    /// Parses a string in the net format accepted by the `LoLA` model checker
+    /// and returns the net it describes.
+    ///
+    /// The places keep the names found in the file as their labels and so do the transitions.
+    /// Places missing from the `MARKING` section are empty and a multiplicity of one is assumed
+    /// if it is omitted. Capacities declared with `SAFE n` are set on the places,
+    /// `SAFE` without a number means a capacity of one.
+    /// Fairness annotations of the transitions are accepted but ignored.
+    ///
+    /// # Errors
+    ///
+    /// If the string is not a valid `LoLA` net, then an error is returned
+    /// with the line and the column where the problem was found.
+    pub fn from_lola_string(string: &str) -> Result<Self, Error> {
+        let tokens = tokenize_lola(string)?;
+        let (end_line, end_column) = string
+            .lines()
+            .enumerate()
+            .last()
+            .map_or((1, 1), |(i, line)| {
+                (i as u64 + 1, line.chars().count() as u64 + 1)
+            });
+        let mut parser = LolaParser {
+            tokens,
+            index: 0,
+            end_line,
+            end_column,
+        };
+
+        let mut net = Self::new();
+        let mut place_refs: BTreeMap<String, PlaceRef> = BTreeMap::new();
+        let mut transition_labels: BTreeSet<String> = BTreeSet::new();
+        if parser.accept("PLACE") {
+            net.read_lola_places(&mut parser, &mut place_refs)?;
+            parser.expect("MARKING")?;
+            net.read_lola_marking(&mut parser, &place_refs)?;
+        }
+        while parser.peek().is_some() {
+            parser.expect("TRANSITION")?;
+            net.read_lola_transition(&mut parser, &place_refs, &mut transition_labels)?;
+        }
+        Ok(net)
+    }
+
+    /// Parses a net file in the format accepted by the `LoLA` model checker
+    /// and returns the net it describes.
+    /// Reads the input from a trait object which implements `std::io::Read`.
+    ///
+    /// # Errors
+    ///
+    /// If the reader fails or the input is not a valid `LoLA` net, then an error is returned.
+    pub fn from_lola<T>(mut reader: T) -> Result<Self, Error>
+    where
+        T: std::io::Read,
+    {
+        let mut string = String::new();
+        reader.read_to_string(&mut string)?;
+        Self::from_lola_string(&string)
+    }
+
+    /// Reads the groups of place declarations after the `PLACE` keyword.
+    /// Each group may start with a capacity declaration and ends with a semicolon.
+    fn read_lola_places(
+        &mut self,
+        parser: &mut LolaParser,
+        place_refs: &mut BTreeMap<String, PlaceRef>,
+    ) -> Result<(), Error> {
+        loop {
+            let mut capacity = None;
+            if parser.accept("SAFE") {
+                capacity = Some(if parser.peek() == Some(":") {
+                    1
+                } else {
+                    parser.number()?
+                });
+                parser.expect(":")?;
+            }
+            loop {
+                let token = parser.identifier()?;
+                if place_refs.contains_key(&token.text) {
+                    return Err(LolaParser::error(token, "Duplicate place"));
+                }
+                let place_ref = self.add_place(&token.text);
+                self.set_capacity(&place_ref, capacity)
+                    .map_err(|error| LolaParser::semantic_error(token, &error))?;
+                place_refs.insert(token.text.clone(), place_ref);
+                if !parser.accept(",") {
+                    break;
+                }
+            }
+            parser.expect(";")?;
+            if parser.peek() == Some("MARKING") {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Reads the initial marking after the `MARKING` keyword up to the closing semicolon.
+    fn read_lola_marking(
+        &mut self,
+        parser: &mut LolaParser,
+        place_refs: &BTreeMap<String, PlaceRef>,
+    ) -> Result<(), Error> {
+        let entries = Self::read_lola_place_list(parser, place_refs)?;
+        for (place_ref, (tokens, token)) in entries {
+            self.add_token(&place_ref, tokens)
+                .map_err(|error| LolaParser::semantic_error(&token, &error))?;
+        }
+        Ok(())
+    }
+
+    /// Reads a transition declaration after the `TRANSITION` keyword
+    /// and adds the arcs listed in the `CONSUME` and `PRODUCE` sections.
+    fn read_lola_transition(
+        &mut self,
+        parser: &mut LolaParser,
+        place_refs: &BTreeMap<String, PlaceRef>,
+        transition_labels: &mut BTreeSet<String>,
+    ) -> Result<(), Error> {
+        let token = parser.identifier()?;
+        if !transition_labels.insert(token.text.clone()) {
+            return Err(LolaParser::error(token, "Duplicate transition"));
+        }
+        let label = token.text.clone();
+        if parser.accept("STRONG") || parser.accept("WEAK") {
+            parser.expect("FAIR")?;
+        }
+        let transition_ref = self.add_transition(&label);
+
+        parser.expect("CONSUME")?;
+        for (place_ref, (weight, token)) in Self::read_lola_place_list(parser, place_refs)? {
+            self.add_weighted_arc_place_transition(&place_ref, &transition_ref, weight)
+                .map_err(|error| LolaParser::semantic_error(&token, &error))?;
+        }
+        parser.expect("PRODUCE")?;
+        for (place_ref, (weight, token)) in Self::read_lola_place_list(parser, place_refs)? {
+            self.add_weighted_arc_transition_place(&transition_ref, &place_ref, weight)
+                .map_err(|error| LolaParser::semantic_error(&token, &error))?;
+        }
+        Ok(())
+    }
+
+    /// Reads a possibly empty list of places with optional multiplicities terminated by a semicolon.
+    /// Places listed more than once have their multiplicities added up.
+    /// Every place comes with the last token that changed its multiplicity,
+    /// so that errors found when adding the place to the net can point to it.
+    fn read_lola_place_list(
+        parser: &mut LolaParser,
+        place_refs: &BTreeMap<String, PlaceRef>,
+    ) -> Result<BTreeMap<PlaceRef, (usize, Token)>, Error> {
+        let mut entries: BTreeMap<PlaceRef, (usize, Token)> = BTreeMap::new();
+        if parser.accept(";") {
+            return Ok(entries);
+        }
+        loop {
+            let token = parser.identifier()?;
+            let Some(place_ref) = place_refs.get(&token.text) else {
+                return Err(LolaParser::error(token, "Undeclared place"));
+            };
+            let (place_ref, mut last_token) = (place_ref.clone(), token.clone());
+            let multiplicity = if parser.accept(":") {
+                let multiplicity = parser.number()?;
+                last_token = parser.previous().clone();
+                multiplicity
+            } else {
+                1
+            };
+            let (total, token) = entries
+                .entry(place_ref)
+                .or_insert_with(|| (0, last_token.clone()));
+            *total = total.checked_add(multiplicity).ok_or_else(|| {
+                LolaParser::semantic_error(&last_token, &Error::ArithmeticOverflow)
+            })?;
+            *token = last_token;
+
+            if parser.accept(";") {
+                return Ok(entries);
+            }
+            parser.expect(",")?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod lola_import_tests {
+    use super::*;
+    use crate::export::test_export_examples::*;
+
+    #[test]
+    fn lola_round_trip_empty_net() {
+        let net = PetriNet::from_lola_string("").unwrap();
+
+        assert_eq!(net.get_cardinality_places(), 0);
+        assert_eq!(net.get_cardinality_transitions(), 0);
+        assert_eq!(net.to_lola_string().unwrap(), "");
+    }
+
+    #[test]
+    fn lola_round_trip_example_nets() {
+        for example in [
+            LOLA_STRING_ONLY_EMPTY_PLACES_NET,
+            LOLA_STRING_MARKED_PLACES_NET,
+            LOLA_STRING_ONLY_EMPTY_TRANSITIONS_NET,
+            LOLA_STRING_NET_WITH_CHAIN_TOPOLOPY,
+            LOLA_STRING_NET_WITH_LOOP_TOPOLOGY,
+            LOLA_STRING_NET_WITH_WEIGHTED_ARCS,
+            LOLA_STRING_NET_WITH_CAPACITIES,
+        ] {
+            let net = PetriNet::from_lola_string(example).unwrap();
+            assert_eq!(net.to_lola_string().unwrap(), example);
+        }
+    }
+
+    #[test]
+    fn lola_import_reads_markings_and_weights() {
+        let net = PetriNet::from_lola_string(LOLA_STRING_NET_WITH_WEIGHTED_ARCS).unwrap();
+        let (place_ref, _) = net.places_iter().next().unwrap();
+        let (transition_ref, _) = net.transitions_iter().next().unwrap();
+
+        assert_eq!(place_ref.label(), "P1");
+        assert_eq!(transition_ref.label(), "T1");
+        assert_eq!(net.marking(place_ref).unwrap(), 2);
+        let weight = net.arc_weight_place_transition(place_ref, transition_ref);
+        assert_eq!(weight.unwrap(), 2);
+    }
+
+    #[test]
+    fn lola_import_safe_comments_and_default_multiplicities() {
+        let string = "{ A producer and a consumer }
+PLACE
+    SAFE : idle, busy;
+    SAFE 4 : buffer; // room for four items
+    done;
+
+MARKING
+    idle, buffer : 2;
+
+/* The transitions
+   of the net */
+TRANSITION produce STRONG FAIR
+  CONSUME idle;
+  PRODUCE busy, buffer : 1, buffer;
+TRANSITION consume
+  CONSUME busy : 1, buffer : 3;
+  PRODUCE idle, done;
+";
+        let net = PetriNet::from_lola_string(string).unwrap();
+
+        assert_eq!(net.get_cardinality_places(), 4);
+        assert_eq!(net.get_cardinality_transitions(), 2);
+        let marking: Vec<(String, usize)> = net
+            .places_iter()
+            .map(|(place_ref, place)| (place_ref.label().clone(), place.marking()))
+            .collect();
+        assert_eq!(
+            marking,
+            vec![
+                ("buffer".to_string(), 2),
+                ("busy".to_string(), 0),
+                ("done".to_string(), 0),
+                ("idle".to_string(), 1),
+            ]
+        );
+        let capacities: Vec<Option<usize>> = net
+            .places_iter()
+            .map(|(_, place)| place.capacity())
+            .collect();
+        assert_eq!(capacities, vec![Some(4), Some(1), None, Some(1)]);
+        let arcs = net.find_weighted_arcs_transition_place();
+        let produced: usize = arcs
+            .iter()
+            .filter(|((transition_ref, place_ref), _)| {
+                transition_ref.label() == "produce" && place_ref.label() == "buffer"
+            })
+            .map(|(_, weight)| *weight)
+            .sum();
+        assert_eq!(produced, 2);
+    }
+
+    #[test]
+    fn lola_import_undeclared_place_returns_position() {
+        let string = "PLACE P1;\nMARKING P1 : 1;\nTRANSITION T1\n  CONSUME P2 : 1;\n  PRODUCE;\n";
+        let result = PetriNet::from_lola_string(string);
+
+        assert!(matches!(
+            result,
+            Err(Error::Parse {
+                line: 4,
+                column: 11,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn lola_import_duplicate_place_returns_error() {
+        let result = PetriNet::from_lola_string("PLACE P1, P1;\nMARKING;\n");
+
+        assert!(matches!(
+            result,
+            Err(Error::Parse {
+                line: 1,
+                column: 11,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn lola_import_marking_above_capacity_returns_position() {
+        let result = PetriNet::from_lola_string("PLACE SAFE 2 : P1;\nMARKING\n  P1 : 3;\n");
+
+        assert!(matches!(
+            result,
+            Err(Error::Parse {
+                line: 3,
+                column: 8,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn lola_import_duplicate_transition_returns_error() {
+        let string = "PLACE P1;\nMARKING;\nTRANSITION T1\n  CONSUME;\n  PRODUCE;\nTRANSITION T1\n  CONSUME;\n  PRODUCE;\n";
+        let result = PetriNet::from_lola_string(string);
+
+        assert!(matches!(
+            result,
+            Err(Error::Parse {
+                line: 6,
+                column: 12,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn lola_import_zero_multiplicity_returns_position() {
+        let string = "PLACE P1;\nMARKING;\nTRANSITION T1\n  CONSUME P1 : 0;\n  PRODUCE;\n";
+        let result = PetriNet::from_lola_string(string);
+
+        assert!(matches!(
+            result,
+            Err(Error::Parse {
+                line: 4,
+                column: 16,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn lola_import_multiplicity_overflow_returns_position() {
+        let string = format!("PLACE P1;\nMARKING P1 : {}, P1 : 1;\n", usize::MAX);
+        let result = PetriNet::from_lola_string(&string);
+
+        assert!(matches!(result, Err(Error::Parse { line: 2, .. })));
+    }
+
+    #[test]
+    fn lola_import_missing_semicolon_returns_error() {
+        let result = PetriNet::from_lola_string("PLACE P1\nMARKING P1 : 1;\n");
+
+        assert!(matches!(
+            result,
+            Err(Error::Parse {
+                line: 2,
+                column: 1,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn lola_import_unexpected_end_of_input_returns_error() {
+        let result = PetriNet::from_lola_string("PLACE P1;\nMARKING P1 :");
+
+        assert!(matches!(result, Err(Error::Parse { line: 2, .. })));
+    }
+
+    #[test]
+    fn lola_import_unterminated_comment_returns_error() {
+        let result = PetriNet::from_lola_string("PLACE P1;\n{ never closed");
+
+        assert!(matches!(
+            result,
+            Err(Error::Parse {
+                line: 2,
+                column: 1,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn lola_import_from_reader() {
+        let reader = LOLA_STRING_NET_WITH_CHAIN_TOPOLOPY.as_bytes();
+        let net = PetriNet::from_lola(reader).unwrap();
+
+        assert_eq!(net.get_cardinality_places(), 3);
+        assert_eq!(net.get_cardinality_transitions(), 2);
+    }
+}
@@ -0,0 +1,624 @@
+use crate::error::Error;
+use crate::export::pnml::XML_PNML_TOOL_NAME;
+use crate::petri_net::{ArcKind, PetriNet, PlaceRef, TransitionRef};
+use std::collections::BTreeMap;
+use xml::attribute::OwnedAttribute;
+use xml::common::{Position, TextPosition};
+use xml::reader::{EventReader, ParserConfig, XmlEvent};
+
+/// A place or a transition as found in the PNML document.
+struct PnmlNode {
+    id: String,
+    name: Option<String>,
+    marking: usize,
+    capacity: Option<usize>,
+    position: TextPosition,
+}
+
+/// An arc as found in the PNML document.
+struct PnmlArc {
+    source: String,
+    target: String,
+    weight: usize,
+    kind: ArcKind,
+    position: TextPosition,
+}
+
+/// The element of the net whose labels are being read.
+enum PnmlObject {
+    Place(PnmlNode),
+    Transition(PnmlNode),
+    Arc(PnmlArc),
+}
+
+/// The contents of the PNML document, flattened over all the pages.
+#[derive(Default)]
+struct PnmlDocument {
+    places: Vec<PnmlNode>,
+    transitions: Vec<PnmlNode>,
+    arcs: Vec<PnmlArc>,
+    references: BTreeMap<String, (String, TextPosition)>,
+    number_of_nets: usize,
+}
+
+impl PetriNet {
+    /// Parses a string in PNML format and returns the place/transition net it describes.
+    ///
+    /// # Errors
+    ///
+    /// If the document is not a valid PNML place/transition net, then an error is returned
+    /// with the line and the column where the problem was found.
+    pub fn from_pnml_string(string: &str) -> Result<Self, Error> {
+        Self::from_pnml(string.as_bytes())
+    }
+
+    /// Parses a document in PNML format and returns the place/transition net it describes.
+    /// Reads the input from a trait object which implements `std::io::Read`.
+    ///
+    /// Places, transitions, arcs with inscriptions, initial markings and names are imported.
+    /// The attribute `type` of the arcs gives their kind, it can be `normal`, `inhibitor`,
+    /// `read` (also written `test`) or `reset`. Arcs without the attribute are normal arcs.
+    /// The capacities of the places are read from the `toolspecific` elements written by this library.
+    /// The objects in nested pages are merged into a single net
+    /// and reference nodes are resolved to the nodes they point to.
+    /// The name of a node is used as its label, if it has no name the id is used instead.
+    /// Graphical information and the information specific to other tools is ignored.
+    ///
+    /// # Errors
+    ///
+    /// If the document is not a valid PNML place/transition net, then an error is returned
+    /// with the line and the column where the problem was found.
+    pub fn from_pnml<T>(reader: T) -> Result<Self, Error>
+    where
+        T: std::io::Read,
+    {
+        let mut xml_reader = ParserConfig::new()
+            .trim_whitespace(true)
+            .cdata_to_characters(true)
+            .ignore_comments(true)
+            .create_reader(reader);
+        let document = Self::read_pnml_document(&mut xml_reader)?;
+        Self::build_from_pnml_document(document)
+    }
+
+    /// Reads the XML events of the document and collects the objects of the net.
+    fn read_pnml_document<T>(xml_reader: &mut EventReader<T>) -> Result<PnmlDocument, Error>
+    where
+        T: std::io::Read,
+    {
+        let mut document = PnmlDocument::default();
+        let mut element_stack: Vec<String> = Vec::new();
+        let mut current_object: Option<PnmlObject> = None;
+
+        loop {
+            let event = xml_reader
+                .next()
+                .map_err(|error| pnml_parse_error(error.position(), error.msg()))?;
+            let position = xml_reader.position();
+            match event {
+                XmlEvent::StartElement {
+                    name, attributes, ..
+                } => {
+                    let is_own_tool = attributes.iter().any(|attribute| {
+                        attribute.name.local_name == "tool" && attribute.value == XML_PNML_TOOL_NAME
+                    });
+                    if name.local_name == "graphics"
+                        || (name.local_name == "toolspecific" && !is_own_tool)
+                    {
+                        xml_reader
+                            .skip()
+                            .map_err(|error| pnml_parse_error(error.position(), error.msg()))?;
+                        continue;
+                    }
+                    Self::read_pnml_element(
+                        &name.local_name,
+                        &attributes,
+                        position,
+                        &mut document,
+                        &mut current_object,
+                    )?;
+                    element_stack.push(name.local_name);
+                }
+                XmlEvent::Characters(text) => {
+                    Self::read_pnml_text(&text, &element_stack, &mut current_object, position)?;
+                }
+                XmlEvent::EndElement { name } => {
+                    element_stack.pop();
+                    if matches!(name.local_name.as_str(), "place" | "transition" | "arc") {
+                        match current_object.take() {
+                            Some(PnmlObject::Place(place)) => document.places.push(place),
+                            Some(PnmlObject::Transition(transition)) => {
+                                document.transitions.push(transition);
+                            }
+                            Some(PnmlObject::Arc(arc)) => document.arcs.push(arc),
+                            None => {}
+                        }
+                    }
+                }
+                XmlEvent::EndDocument => break,
+                _ => {}
+            }
+        }
+
+        if document.number_of_nets == 0 {
+            return Err(pnml_parse_error(
+                xml_reader.position(),
+                "The document does not contain a net",
+            ));
+        }
+        Ok(document)
+    }
+
+    /// Reads the attributes of an element which starts a net, a node or an arc.
+    fn read_pnml_element(
+        element: &str,
+        attributes: &[OwnedAttribute],
+        position: TextPosition,
+        document: &mut PnmlDocument,
+        current_object: &mut Option<PnmlObject>,
+    ) -> Result<(), Error> {
+        let attribute = |key: &str| {
+            attributes
+                .iter()
+                .find(|attribute| attribute.name.local_name == key)
+                .map(|attribute| attribute.value.clone())
+        };
+        let required_attribute = |key: &str| {
+            attribute(key).ok_or_else(|| {
+                let message = format!("Missing attribute `{key}` in element `{element}`");
+                pnml_parse_error(position, message)
+            })
+        };
+
+        match element {
+            "net" => {
+                document.number_of_nets += 1;
+                if document.number_of_nets > 1 {
+                    return Err(pnml_parse_error(
+                        position,
+                        "The document contains more than one net",
+                    ));
+                }
+                let net_type = attribute("type").unwrap_or_default();
+                if !net_type.to_lowercase().contains("ptnet") {
+                    return Err(pnml_parse_error(
+                        position,
+                        format!(
+                            "Unsupported net type `{net_type}`, only place/transition nets are supported"
+                        ),
+                    ));
+                }
+            }
+            "place" | "transition" => {
+                let node = PnmlNode {
+                    id: required_attribute("id")?,
+                    name: None,
+                    marking: 0,
+                    capacity: None,
+                    position,
+                };
+                *current_object = Some(if element == "place" {
+                    PnmlObject::Place(node)
+                } else {
+                    PnmlObject::Transition(node)
+                });
+            }
+            "arc" => {
+                let kind = match attribute("type").as_deref() {
+                    None | Some("normal") => ArcKind::Normal,
+                    Some("inhibitor") => ArcKind::Inhibitor,
+                    Some("read" | "test") => ArcKind::Read,
+                    Some("reset") => ArcKind::Reset,
+                    Some(arc_type) => {
+                        return Err(pnml_parse_error(
+                            position,
+                            format!("Unsupported arc type `{arc_type}`"),
+                        ));
+                    }
+                };
+                *current_object = Some(PnmlObject::Arc(PnmlArc {
+                    source: required_attribute("source")?,
+                    target: required_attribute("target")?,
+                    weight: 1,
+                    kind,
+                    position,
+                }));
+            }
+            "referencePlace" | "referenceTransition" => {
+                let id = required_attribute("id")?;
+                let reference = required_attribute("ref")?;
+                document.references.insert(id, (reference, position));
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Stores the text of a label in the object currently being read.
+    /// Only the names, the initial markings and the inscriptions are relevant for the net.
+    fn read_pnml_text(
+        text: &str,
+        element_stack: &[String],
+        current_object: &mut Option<PnmlObject>,
+        position: TextPosition,
+    ) -> Result<(), Error> {
+        let [.., object, label, element] = element_stack else {
+            return Ok(());
+        };
+        if element != "text" {
+            return Ok(());
+        }
+        let parse_number = || {
+            text.trim().parse::<usize>().map_err(|_| {
+                pnml_parse_error(position, format!("Invalid number `{text}` in `{label}`"))
+            })
+        };
+
+        match (current_object, object.as_str(), label.as_str()) {
+            (Some(PnmlObject::Place(node) | PnmlObject::Transition(node)), _, "name")
+                if object == "place" || object == "transition" =>
+            {
+                node.name = Some(text.to_string());
+            }
+            (Some(PnmlObject::Place(node)), "place", "initialMarking") => {
+                node.marking = parse_number()?;
+            }
+            (Some(PnmlObject::Place(node)), "toolspecific", "capacity") => {
+                node.capacity = Some(parse_number()?);
+            }
+            (Some(PnmlObject::Arc(arc)), "arc", "inscription") => {
+                arc.weight = parse_number()?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Creates the net from the objects collected in the document.
+    fn build_from_pnml_document(document: PnmlDocument) -> Result<Self, Error> {
+        let mut net = Self::new();
+        let mut place_refs: BTreeMap<String, PlaceRef> = BTreeMap::new();
+        let mut transition_refs: BTreeMap<String, TransitionRef> = BTreeMap::new();
+
+        for place in document.places {
+            if place_refs.contains_key(&place.id) {
+                return Err(pnml_parse_error(
+                    place.position,
+                    format!("Duplicate node id `{}`", place.id),
+                ));
+            }
+            let place_ref = net.add_place(place.name.as_ref().unwrap_or(&place.id));
+            net.set_capacity(&place_ref, place.capacity)
+                .map_err(|error| pnml_parse_error(place.position, error.to_string()))?;
+            net.add_token(&place_ref, place.marking)
+                .map_err(|error| pnml_parse_error(place.position, error.to_string()))?;
+            place_refs.insert(place.id, place_ref);
+        }
+        for transition in document.transitions {
+            if place_refs.contains_key(&transition.id)
+                || transition_refs.contains_key(&transition.id)
+            {
+                return Err(pnml_parse_error(
+                    transition.position,
+                    format!("Duplicate node id `{}`", transition.id),
+                ));
+            }
+            let transition_ref =
+                net.add_transition(transition.name.as_ref().unwrap_or(&transition.id));
+            transition_refs.insert(transition.id, transition_ref);
+        }
+
+        for arc in document.arcs {
+            let source = resolve_pnml_reference(&arc.source, &document.references, arc.position)?;
+            let target = resolve_pnml_reference(&arc.target, &document.references, arc.position)?;
+            let result = match (
+                place_refs.get(source),
+                transition_refs.get(source),
+                place_refs.get(target),
+                transition_refs.get(target),
+            ) {
+                (Some(place_ref), None, None, Some(transition_ref)) => {
+                    net.add_arc_with_kind(place_ref, transition_ref, arc.kind, arc.weight)
+                }
+                (None, Some(_), Some(_), None) if arc.kind != ArcKind::Normal => {
+                    return Err(pnml_parse_error(
+                        arc.position,
+                        format!(
+                            "The {} arc from `{}` to `{}` does not go from a place to a transition",
+                            arc.kind, arc.source, arc.target
+                        ),
+                    ));
+                }
+                (None, Some(transition_ref), Some(place_ref), None) => {
+                    net.add_weighted_arc_transition_place(transition_ref, place_ref, arc.weight)
+                }
+                _ => {
+                    return Err(pnml_parse_error(
+                        arc.position,
+                        format!(
+                            "The arc from `{}` to `{}` does not connect a place and a transition",
+                            arc.source, arc.target
+                        ),
+                    ));
+                }
+            };
+            result.map_err(|error| pnml_parse_error(arc.position, error.to_string()))?;
+        }
+        Ok(net)
+    }
+}
+
+/// Follows the chain of reference nodes until a place or a transition is reached.
+fn resolve_pnml_reference<'a>(
+    id: &'a str,
+    references: &'a BTreeMap<String, (String, TextPosition)>,
+    position: TextPosition,
+) -> Result<&'a str, Error> {
+    let mut current = id;
+    // A chain longer than the number of references must contain a cycle.
+    for _ in 0..=references.len() {
+        match references.get(current) {
+            Some((reference, _)) => current = reference,
+            None => return Ok(current),
+        }
+    }
+    Err(pnml_parse_error(
+        position,
+        format!("The reference node `{id}` is part of a cycle"),
+    ))
+}
+
+/// Creates a parse error, converting the position to start at one.
+fn pnml_parse_error(position: TextPosition, message: impl Into<String>) -> Error {
+    Error::Parse {
+        line: position.row + 1,
+        column: position.column + 1,
+        message: message.into(),
+    }
+}
+
+#[cfg(test)]
+mod pnml_import_tests {
+    use super::*;
+    use crate::export::test_export_examples::*;
+
+    #[test]
+    fn pnml_round_trip_empty_net() {
+        let net = PetriNet::from_pnml_string(PNML_STRING_EMPTY_NET).unwrap();
+
+        assert_eq!(net.get_cardinality_places(), 0);
+        assert_eq!(net.get_cardinality_transitions(), 0);
+        assert_eq!(net.to_pnml_string().unwrap(), PNML_STRING_EMPTY_NET);
+    }
+
+    #[test]
+    fn pnml_round_trip_example_nets() {
+        for example in [
+            PNML_STRING_ONLY_EMPTY_PLACES_NET,
+            PNML_STRING_MARKED_PLACES_NET,
+            PNML_STRING_ONLY_EMPTY_TRANSITIONS_NET,
+            PNML_STRING_NET_WITH_CHAIN_TOPOLOPY,
+            PNML_STRING_NET_WITH_LOOP_TOPOLOGY,
+            PNML_STRING_NET_WITH_WEIGHTED_ARCS,
+            PNML_STRING_NET_WITH_CAPACITIES,
+        ] {
+            let net = PetriNet::from_pnml_string(example).unwrap();
+            assert_eq!(net.to_pnml_string().unwrap(), example);
+        }
+    }
+
+    #[test]
+    fn pnml_import_reads_markings_and_weights() {
+        let net = PetriNet::from_pnml_string(PNML_STRING_NET_WITH_WEIGHTED_ARCS).unwrap();
+        let (place_ref, _) = net.places_iter().next().unwrap();
+        let (transition_ref, _) = net.transitions_iter().next().unwrap();
+
+        assert_eq!(place_ref.label(), "P1");
+        assert_eq!(net.marking(place_ref).unwrap(), 2);
+        let weight = net.arc_weight_place_transition(place_ref, transition_ref);
+        assert_eq!(weight.unwrap(), 2);
+    }
+
+    #[test]
+    fn pnml_import_nested_pages_and_reference_nodes() {
+        let document = r#"<?xml version="1.0" encoding="UTF-8"?>
+<pnml xmlns="http://www.pnml.org/version-2009/grammar/pnml">
+  <net id="net0" type="http://www.pnml.org/version-2009/grammar/ptnet">
+    <name><text>Example net</text></name>
+    <page id="outer">
+      <place id="p1">
+        <name>
+          <text>  Buffer  </text>
+          <graphics><offset x="0" y="0"/></graphics>
+        </name>
+        <initialMarking><text> 3 </text></initialMarking>
+        <graphics><position x="10" y="10"/></graphics>
+      </place>
+      <page id="inner">
+        <referencePlace id="p1_ref" ref="p1"/>
+        <transition id="t1"/>
+        <arc id="a1" source="p1_ref" target="t1">
+          <inscription><text>2</text></inscription>
+        </arc>
+      </page>
+      <toolspecific tool="example" version="1.0"><place id="ignored"/></toolspecific>
+    </page>
+  </net>
+</pnml>"#;
+        let net = PetriNet::from_pnml_string(document).unwrap();
+
+        assert_eq!(net.get_cardinality_places(), 1);
+        assert_eq!(net.get_cardinality_transitions(), 1);
+        let (place_ref, _) = net.places_iter().next().unwrap();
+        let (transition_ref, _) = net.transitions_iter().next().unwrap();
+        assert_eq!(place_ref.label(), "Buffer");
+        assert_eq!(transition_ref.label(), "t1");
+        assert_eq!(net.marking(place_ref).unwrap(), 3);
+        let weight = net.arc_weight_place_transition(place_ref, transition_ref);
+        assert_eq!(weight.unwrap(), 2);
+    }
+
+    #[test]
+    fn pnml_import_malformed_xml_returns_position() {
+        let document = "<pnml>\n  <net id=\"n\" type=\"ptnet\">\n  </pnml>";
+        let result = PetriNet::from_pnml_string(document);
+
+        assert!(matches!(result, Err(Error::Parse { line: 3, .. })));
+    }
+
+    #[test]
+    fn pnml_import_arc_to_unknown_node_returns_position() {
+        let document = r#"<pnml>
+  <net id="n" type="ptnet">
+    <page id="page0">
+      <place id="p1"/>
+      <arc id="a1" source="p1" target="missing"/>
+    </page>
+  </net>
+</pnml>"#;
+        let result = PetriNet::from_pnml_string(document);
+
+        assert!(matches!(
+            result,
+            Err(Error::Parse {
+                line: 5,
+                column: 7,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn pnml_import_invalid_marking_returns_error() {
+        let document = r#"<pnml>
+  <net id="n" type="ptnet">
+    <page id="page0">
+      <place id="p1"><initialMarking><text>many</text></initialMarking></place>
+    </page>
+  </net>
+</pnml>"#;
+        let result = PetriNet::from_pnml_string(document);
+
+        assert!(matches!(result, Err(Error::Parse { line: 4, .. })));
+    }
+
+    #[test]
+    fn pnml_import_rejects_other_net_types() {
+        let document = r#"<pnml><net id="n" type="http://www.pnml.org/version-2009/grammar/highlevelnet"/></pnml>"#;
+        let result = PetriNet::from_pnml_string(document);
+
+        assert!(matches!(result, Err(Error::Parse { .. })));
+    }
+
+    #[test]
+    fn pnml_import_rejects_document_without_net() {
+        let result = PetriNet::from_pnml_string("<pnml></pnml>");
+
+        assert!(matches!(result, Err(Error::Parse { .. })));
+    }
+
+    #[test]
+    fn pnml_import_rejects_duplicate_ids() {
+        let document = r#"<pnml>
+  <net id="n" type="ptnet">
+    <page id="page0">
+      <place id="x"/>
+      <transition id="x"/>
+    </page>
+  </net>
+</pnml>"#;
+        let result = PetriNet::from_pnml_string(document);
+
+        assert!(matches!(result, Err(Error::Parse { line: 5, .. })));
+    }
+
+    #[test]
+    fn pnml_import_reads_capacities() {
+        let net = PetriNet::from_pnml_string(PNML_STRING_NET_WITH_CAPACITIES).unwrap();
+        let capacities: Vec<Option<usize>> = net
+            .places_iter()
+            .map(|(_, place)| place.capacity())
+            .collect();
+
+        assert_eq!(capacities, vec![Some(3), None]);
+    }
+
+    #[test]
+    fn pnml_import_marking_above_capacity_returns_error() {
+        let document = r#"<pnml>
+  <net id="n" type="ptnet">
+    <page id="page0">
+      <place id="p1">
+        <initialMarking><text>2</text></initialMarking>
+        <toolspecific tool="netcrab" version="1.0"><capacity><text>1</text></capacity></toolspecific>
+      </place>
+    </page>
+  </net>
+</pnml>"#;
+        let result = PetriNet::from_pnml_string(document);
+
+        assert!(matches!(result, Err(Error::Parse { line: 4, .. })));
+    }
+
+    #[test]
+    fn pnml_import_reads_arc_kinds() {
+        let mut net = PetriNet::new();
+        let p1 = net.add_place("P1");
+        let p2 = net.add_place("P2");
+        let p3 = net.add_place("P3");
+        let p4 = net.add_place("P4");
+        let t1 = net.add_transition("T1");
+        assert!(net.add_arc_place_transition(&p1, &t1).is_ok());
+        assert!(net.add_inhibitor_arc(&p2, &t1, 2).is_ok());
+        assert!(net.add_read_arc(&p3, &t1, 3).is_ok());
+        assert!(net.add_reset_arc(&p4, &t1).is_ok());
+        let imported = PetriNet::from_pnml_string(&net.to_pnml_string().unwrap()).unwrap();
+
+        let arcs: Vec<(String, ArcKind, usize)> = imported
+            .find_extended_arcs()
+            .into_iter()
+            .map(|((place_ref, _), (kind, weight))| (place_ref.label().clone(), kind, weight))
+            .collect();
+        assert_eq!(
+            arcs,
+            vec![
+                ("P2".to_string(), ArcKind::Inhibitor, 2),
+                ("P3".to_string(), ArcKind::Read, 3),
+                ("P4".to_string(), ArcKind::Reset, 1)
+            ]
+        );
+        assert_eq!(imported.find_weighted_arcs_place_transition().len(), 1);
+    }
+
+    #[test]
+    fn pnml_import_rejects_inhibitor_arc_from_transition() {
+        let document = r#"<pnml>
+  <net id="n" type="ptnet">
+    <page id="page0">
+      <place id="p1"/>
+      <transition id="t1"/>
+      <arc id="a1" source="t1" target="p1" type="inhibitor"/>
+    </page>
+  </net>
+</pnml>"#;
+        let result = PetriNet::from_pnml_string(document);
+
+        assert!(matches!(result, Err(Error::Parse { line: 6, .. })));
+    }
+
+    #[test]
+    fn pnml_import_rejects_unknown_arc_type() {
+        let document = r#"<pnml>
+  <net id="n" type="ptnet">
+    <page id="page0">
+      <place id="p1"/>
+      <transition id="t1"/>
+      <arc id="a1" source="p1" target="t1" type="flow"/>
+    </page>
+  </net>
+</pnml>"#;
+        let result = PetriNet::from_pnml_string(document);
+
+        assert!(matches!(result, Err(Error::Parse { line: 6, .. })));
+    }
+}
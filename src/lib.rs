@@ -1,3 +1,8 @@
+pub mod analysis;
+pub mod error;
 pub mod export;
+pub mod import;
 pub mod net_creator;
 pub mod petri_net;
+pub mod simulation;
+pub use crate::error::Error;
@@ -1,28 +1,67 @@
-pub use crate::petri_net::place::Place;
+use crate::error::Error;
+pub use crate::petri_net::arc_kind::ArcKind;
+pub use crate::petri_net::arc_ref::ArcRef;
+pub use crate::petri_net::composition::Composition;
+pub use crate::petri_net::firing_interval::FiringInterval;
+use crate::petri_net::id_generator::IdGenerator;
+pub use crate::petri_net::place::{Place, TokenError};
 pub use crate::petri_net::place_ref::PlaceRef;
+pub use crate::petri_net::stochastic_timing::StochasticTiming;
 pub use crate::petri_net::transition::Transition;
 pub use crate::petri_net::transition_ref::TransitionRef;
 use std::collections::{BTreeMap, BTreeSet};
 
+mod arc_kind;
+mod arc_ref;
+mod composition;
+mod extended_arcs;
+mod firing;
+mod firing_interval;
+mod id_generator;
 mod net_iter;
 mod place;
 mod place_ref;
+#[cfg(feature = "serde")]
+mod serialization;
+mod stochastic_timing;
 mod transition;
 mod transition_ref;
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct PetriNet {
     places: BTreeMap<PlaceRef, Place>,
     transitions: BTreeMap<TransitionRef, Transition>,
+    id_generator: IdGenerator,
 }
 
 impl PetriNet {
     /// Creates an empty Petri net.
+    /// The references to places and transitions get random UUIDs.
     #[must_use]
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// Creates an empty Petri net whose references get deterministic UUIDs.
+    /// The UUIDs are built from the seed and a counter incremented on every new place or transition.
+    ///
+    /// Building the same net twice with the same seed produces equal references
+    /// that are ordered as the nodes were added when the labels are equal.
+    /// Note that references from two nets built with the same seed are interchangeable.
+    #[must_use]
+    pub fn with_deterministic_ids(seed: u64) -> Self {
+        Self {
+            id_generator: IdGenerator::sequential(seed),
+            ..Self::default()
+        }
+    }
+
+    /// Checks if the references of the net get deterministic UUIDs.
+    #[must_use]
+    pub const fn has_deterministic_ids(&self) -> bool {
+        matches!(self.id_generator, IdGenerator::Sequential { .. })
+    }
+
     /// Gets the number of places in the net.
     #[inline]
     #[must_use]
@@ -59,21 +98,27 @@ impl PetriNet {
     pub fn find_unconnected_places(&self) -> BTreeSet<PlaceRef> {
         let mut unconnected_set: BTreeSet<PlaceRef> = BTreeSet::new();
         for (place_ref, place) in &self.places {
-            if place.get_preset().is_empty() && place.get_postset().is_empty() {
+            if place.get_preset().is_empty()
+                && place.get_postset().is_empty()
+                && place.get_extended_postset().is_empty()
+            {
                 unconnected_set.insert(place_ref.clone());
             }
         }
         unconnected_set
     }
 
-    /// Finds all arcs from places to transitions in the net.
-    /// Returns a `BTreeSet` with tuples of references (source, dest).
+    /// Finds all arcs from places to transitions in the net, of every kind.
+    /// Returns a `BTreeMap` with tuples of references (source, dest) as keys and the kinds as values.
     #[must_use]
-    pub fn find_arcs_place_transition(&self) -> BTreeSet<(PlaceRef, TransitionRef)> {
-        let mut arcs: BTreeSet<(PlaceRef, TransitionRef)> = BTreeSet::new();
+    pub fn find_arcs_place_transition(&self) -> BTreeMap<(PlaceRef, TransitionRef), ArcKind> {
+        let mut arcs: BTreeMap<(PlaceRef, TransitionRef), ArcKind> = BTreeMap::new();
         for (place_ref, place) in &self.places {
-            for transition_ref in place.get_postset() {
-                arcs.insert((place_ref.clone(), transition_ref.clone()));
+            for transition_ref in place.get_postset().keys() {
+                arcs.insert((place_ref.clone(), transition_ref.clone()), ArcKind::Normal);
+            }
+            for (transition_ref, (kind, _)) in place.get_extended_postset() {
+                arcs.insert((place_ref.clone(), transition_ref.clone()), *kind);
             }
         }
         arcs
@@ -85,17 +130,48 @@ impl PetriNet {
     pub fn find_arcs_transition_place(&self) -> BTreeSet<(TransitionRef, PlaceRef)> {
         let mut arcs: BTreeSet<(TransitionRef, PlaceRef)> = BTreeSet::new();
         for (transition_ref, transition) in &self.transitions {
-            for place_ref in transition.get_postset() {
+            for place_ref in transition.get_postset().keys() {
                 arcs.insert((transition_ref.clone(), place_ref.clone()));
             }
         }
         arcs
     }
 
+    /// Finds all arcs of kind `ArcKind::Normal` from places to transitions in the net
+    /// together with their weights.
+    /// Returns a `BTreeMap` with tuples of references (source, dest) as keys and the weights as values.
+    #[must_use]
+    pub fn find_weighted_arcs_place_transition(
+        &self,
+    ) -> BTreeMap<(PlaceRef, TransitionRef), usize> {
+        let mut arcs: BTreeMap<(PlaceRef, TransitionRef), usize> = BTreeMap::new();
+        for (place_ref, place) in &self.places {
+            for (transition_ref, weight) in place.get_postset() {
+                arcs.insert((place_ref.clone(), transition_ref.clone()), *weight);
+            }
+        }
+        arcs
+    }
+
+    /// Finds all arcs from transitions to places in the net together with their weights.
+    /// Returns a `BTreeMap` with tuples of references (source, dest) as keys and the weights as values.
+    #[must_use]
+    pub fn find_weighted_arcs_transition_place(
+        &self,
+    ) -> BTreeMap<(TransitionRef, PlaceRef), usize> {
+        let mut arcs: BTreeMap<(TransitionRef, PlaceRef), usize> = BTreeMap::new();
+        for (transition_ref, transition) in &self.transitions {
+            for (place_ref, weight) in transition.get_postset() {
+                arcs.insert((transition_ref.clone(), place_ref.clone()), *weight);
+            }
+        }
+        arcs
+    }
+
     /// Adds a place to the net.
     /// The place label need not be unique for the net.
     pub fn add_place(&mut self, place_label: &str) -> PlaceRef {
-        let place_ref = PlaceRef::new(place_label);
+        let place_ref = PlaceRef::with_uuid(place_label, self.id_generator.next_uuid());
         self.places.insert(place_ref.clone(), Place::new());
         place_ref
     }
@@ -103,7 +179,8 @@ impl PetriNet {
     /// Adds a transition to the net.
     /// The transition label need not be unique for the net.
     pub fn add_transition(&mut self, transition_label: &str) -> TransitionRef {
-        let transition_ref = TransitionRef::new(transition_label);
+        let transition_ref =
+            TransitionRef::with_uuid(transition_label, self.id_generator.next_uuid());
         self.transitions
             .insert(transition_ref.clone(), Transition::new());
         transition_ref
@@ -120,34 +197,271 @@ impl PetriNet {
         &mut self,
         place_ref: &PlaceRef,
         transition_ref: &TransitionRef,
-    ) -> Result<(), &str> {
+    ) -> Result<(), Error> {
+        self.add_weighted_arc_place_transition(place_ref, transition_ref, 1)
+    }
+
+    /// Adds an arc from a transition to a place with multiplicity one.
+    ///
+    /// # Errors
+    ///
+    /// If the `PlaceRef` or the `TransitionRef` is invalid, then an error is returned.
+    /// If the arc already exists, then an error is returned.
+    /// If the arc was added already on one side but not on the other, then an error is returned.
+    pub fn add_arc_transition_place(
+        &mut self,
+        transition_ref: &TransitionRef,
+        place_ref: &PlaceRef,
+    ) -> Result<(), Error> {
+        self.add_weighted_arc_transition_place(transition_ref, place_ref, 1)
+    }
+
+    /// Adds an arc from a place to a transition with multiplicity `weight`.
+    ///
+    /// # Errors
+    ///
+    /// If the weight is zero, then an error is returned.
+    /// If the `PlaceRef` or the `TransitionRef` is invalid, then an error is returned.
+    /// If an arc of any kind between the same nodes already exists, then an error is returned.
+    /// If the arc was added already on one side but not on the other, then an error is returned.
+    pub fn add_weighted_arc_place_transition(
+        &mut self,
+        place_ref: &PlaceRef,
+        transition_ref: &TransitionRef,
+        weight: usize,
+    ) -> Result<(), Error> {
+        let arc_ref = ArcRef::PlaceTransition(place_ref.clone(), transition_ref.clone());
+        Self::check_arc_weight(weight, &arc_ref)?;
         let (place, transition) = self.get_place_transition_pair_mut(place_ref, transition_ref)?;
+        if place.get_extended_postset().contains_key(transition_ref) {
+            return Err(Error::DuplicateArc(arc_ref));
+        }
         // We prefer to clone the references here, since the add operations technically do not need it,
         // but we just want to borrow the references from the user for this operation.
-        let inserted_outgoing = place.add_outgoing(transition_ref.clone());
-        let inserted_incoming = transition.add_incoming(place_ref.clone());
-        Self::check_arc_insertion(inserted_incoming, inserted_outgoing)?;
+        let inserted_outgoing = place.add_outgoing(transition_ref.clone(), weight);
+        let inserted_incoming = transition.add_incoming(place_ref.clone(), weight);
+        Self::check_arc_insertion(inserted_incoming, inserted_outgoing, arc_ref)?;
         Ok(())
     }
 
-    /// Adds an arc from a transition to a place with multiplicity one.
+    /// Adds an arc from a transition to a place with multiplicity `weight`.
     ///
     /// # Errors
     ///
+    /// If the weight is zero, then an error is returned.
     /// If the `PlaceRef` or the `TransitionRef` is invalid, then an error is returned.
     /// If the arc already exists, then an error is returned.
     /// If the arc was added already on one side but not on the other, then an error is returned.
-    pub fn add_arc_transition_place(
+    pub fn add_weighted_arc_transition_place(
         &mut self,
         transition_ref: &TransitionRef,
         place_ref: &PlaceRef,
-    ) -> Result<(), &str> {
+        weight: usize,
+    ) -> Result<(), Error> {
+        let arc_ref = ArcRef::TransitionPlace(transition_ref.clone(), place_ref.clone());
+        Self::check_arc_weight(weight, &arc_ref)?;
         let (place, transition) = self.get_place_transition_pair_mut(place_ref, transition_ref)?;
         // We prefer to clone the references here, since the add operations technically do not need it,
         // but we just want to borrow the references from the user for this operation.
-        let inserted_outgoing = transition.add_outgoing(place_ref.clone());
-        let inserted_incoming = place.add_incoming(transition_ref.clone());
-        Self::check_arc_insertion(inserted_incoming, inserted_outgoing)?;
+        let inserted_outgoing = transition.add_outgoing(place_ref.clone(), weight);
+        let inserted_incoming = place.add_incoming(transition_ref.clone(), weight);
+        Self::check_arc_insertion(inserted_incoming, inserted_outgoing, arc_ref)?;
+        Ok(())
+    }
+
+    /// Gets the weight of the arc from a place to a transition.
+    ///
+    /// # Errors
+    ///
+    /// If the `PlaceRef` or the `TransitionRef` is invalid, then an error is returned.
+    /// If the arc does not exist, then an error is returned.
+    /// If the arc exists only on one side or the weights differ, then an error is returned.
+    pub fn arc_weight_place_transition(
+        &self,
+        place_ref: &PlaceRef,
+        transition_ref: &TransitionRef,
+    ) -> Result<usize, Error> {
+        let place = self.get_place(place_ref)?;
+        let transition = self.get_transition(transition_ref)?;
+        Self::check_arc_weights(
+            place.get_postset().get(transition_ref),
+            transition.get_preset().get(place_ref),
+            ArcRef::PlaceTransition(place_ref.clone(), transition_ref.clone()),
+        )
+    }
+
+    /// Gets the weight of the arc from a transition to a place.
+    ///
+    /// # Errors
+    ///
+    /// If the `PlaceRef` or the `TransitionRef` is invalid, then an error is returned.
+    /// If the arc does not exist, then an error is returned.
+    /// If the arc exists only on one side or the weights differ, then an error is returned.
+    pub fn arc_weight_transition_place(
+        &self,
+        transition_ref: &TransitionRef,
+        place_ref: &PlaceRef,
+    ) -> Result<usize, Error> {
+        let place = self.get_place(place_ref)?;
+        let transition = self.get_transition(transition_ref)?;
+        Self::check_arc_weights(
+            transition.get_postset().get(place_ref),
+            place.get_preset().get(transition_ref),
+            ArcRef::TransitionPlace(transition_ref.clone(), place_ref.clone()),
+        )
+    }
+
+    /// Sets the weight of an existing arc from a place to a transition.
+    ///
+    /// # Errors
+    ///
+    /// If the weight is zero, then an error is returned.
+    /// If the `PlaceRef` or the `TransitionRef` is invalid, then an error is returned.
+    /// If the arc does not exist on both sides, then an error is returned.
+    pub fn set_arc_weight_place_transition(
+        &mut self,
+        place_ref: &PlaceRef,
+        transition_ref: &TransitionRef,
+        weight: usize,
+    ) -> Result<(), Error> {
+        let arc_ref = ArcRef::PlaceTransition(place_ref.clone(), transition_ref.clone());
+        Self::check_arc_weight(weight, &arc_ref)?;
+        let (place, transition) = self.get_place_transition_pair_mut(place_ref, transition_ref)?;
+        Self::check_arc_exists(
+            place.get_postset().contains_key(transition_ref),
+            transition.get_preset().contains_key(place_ref),
+            arc_ref,
+        )?;
+        place.update_outgoing(transition_ref, weight);
+        transition.update_incoming(place_ref, weight);
+        Ok(())
+    }
+
+    /// Sets the weight of an existing arc from a transition to a place.
+    ///
+    /// # Errors
+    ///
+    /// If the weight is zero, then an error is returned.
+    /// If the `PlaceRef` or the `TransitionRef` is invalid, then an error is returned.
+    /// If the arc does not exist on both sides, then an error is returned.
+    pub fn set_arc_weight_transition_place(
+        &mut self,
+        transition_ref: &TransitionRef,
+        place_ref: &PlaceRef,
+        weight: usize,
+    ) -> Result<(), Error> {
+        let arc_ref = ArcRef::TransitionPlace(transition_ref.clone(), place_ref.clone());
+        Self::check_arc_weight(weight, &arc_ref)?;
+        let (place, transition) = self.get_place_transition_pair_mut(place_ref, transition_ref)?;
+        Self::check_arc_exists(
+            transition.get_postset().contains_key(place_ref),
+            place.get_preset().contains_key(transition_ref),
+            arc_ref,
+        )?;
+        transition.update_outgoing(place_ref, weight);
+        place.update_incoming(transition_ref, weight);
+        Ok(())
+    }
+
+    /// Removes a place from the net together with every arc connected to it.
+    /// The `PlaceRef` becomes invalid for this net afterwards.
+    ///
+    /// # Errors
+    ///
+    /// If the `PlaceRef` is invalid, then an error is returned.
+    pub fn remove_place(&mut self, place_ref: &PlaceRef) -> Result<(), Error> {
+        let Some(place) = self.places.remove(place_ref) else {
+            return Err(Error::InvalidPlaceRef(place_ref.clone()));
+        };
+        for transition_ref in place.get_preset().keys() {
+            if let Some(transition) = self.transitions.get_mut(transition_ref) {
+                transition.remove_outgoing(place_ref);
+            }
+        }
+        for transition_ref in place.get_postset().keys() {
+            if let Some(transition) = self.transitions.get_mut(transition_ref) {
+                transition.remove_incoming(place_ref);
+            }
+        }
+        for transition_ref in place.get_extended_postset().keys() {
+            if let Some(transition) = self.transitions.get_mut(transition_ref) {
+                transition.remove_extended_incoming(place_ref);
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes a transition from the net together with every arc connected to it.
+    /// The `TransitionRef` becomes invalid for this net afterwards.
+    ///
+    /// # Errors
+    ///
+    /// If the `TransitionRef` is invalid, then an error is returned.
+    pub fn remove_transition(&mut self, transition_ref: &TransitionRef) -> Result<(), Error> {
+        let Some(transition) = self.transitions.remove(transition_ref) else {
+            return Err(Error::InvalidTransitionRef(transition_ref.clone()));
+        };
+        for place_ref in transition.get_preset().keys() {
+            if let Some(place) = self.places.get_mut(place_ref) {
+                place.remove_outgoing(transition_ref);
+            }
+        }
+        for place_ref in transition.get_postset().keys() {
+            if let Some(place) = self.places.get_mut(place_ref) {
+                place.remove_incoming(transition_ref);
+            }
+        }
+        for place_ref in transition.get_extended_preset().keys() {
+            if let Some(place) = self.places.get_mut(place_ref) {
+                place.remove_extended_outgoing(transition_ref);
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes the arc from a place to a transition.
+    ///
+    /// # Errors
+    ///
+    /// If the `PlaceRef` or the `TransitionRef` is invalid, then an error is returned.
+    /// If the arc does not exist, then an error is returned.
+    /// If the arc existed only on one side, then it is removed and an error is returned.
+    pub fn remove_arc_place_transition(
+        &mut self,
+        place_ref: &PlaceRef,
+        transition_ref: &TransitionRef,
+    ) -> Result<(), Error> {
+        let (place, transition) = self.get_place_transition_pair_mut(place_ref, transition_ref)?;
+        let removed_outgoing = place.remove_outgoing(transition_ref);
+        let removed_incoming = transition.remove_incoming(place_ref);
+        Self::check_arc_exists(
+            removed_outgoing,
+            removed_incoming,
+            ArcRef::PlaceTransition(place_ref.clone(), transition_ref.clone()),
+        )?;
+        Ok(())
+    }
+
+    /// Removes the arc from a transition to a place.
+    ///
+    /// # Errors
+    ///
+    /// If the `PlaceRef` or the `TransitionRef` is invalid, then an error is returned.
+    /// If the arc does not exist, then an error is returned.
+    /// If the arc existed only on one side, then it is removed and an error is returned.
+    pub fn remove_arc_transition_place(
+        &mut self,
+        transition_ref: &TransitionRef,
+        place_ref: &PlaceRef,
+    ) -> Result<(), Error> {
+        let (place, transition) = self.get_place_transition_pair_mut(place_ref, transition_ref)?;
+        let removed_outgoing = transition.remove_outgoing(place_ref);
+        let removed_incoming = place.remove_incoming(transition_ref);
+        Self::check_arc_exists(
+            removed_outgoing,
+            removed_incoming,
+            ArcRef::TransitionPlace(transition_ref.clone(), place_ref.clone()),
+        )?;
         Ok(())
     }
 
@@ -156,14 +470,15 @@ impl PetriNet {
     /// # Errors
     ///
     /// If the `PlaceRef` is invalid, then an error is returned.
-    pub fn marking(&self, place_ref: &PlaceRef) -> Result<usize, &str> {
+    pub fn marking(&self, place_ref: &PlaceRef) -> Result<usize, Error> {
         let place = self.get_place(place_ref)?;
         Ok(place.marking())
     }
 
     /// Gets the marking vector for the net, i.e, the number of tokens for every place in the net.
     /// Returns a `BTreeMap` with the place references as the keys and the number of tokens as values.
-    pub fn marking_vector(&mut self) -> BTreeMap<PlaceRef, usize> {
+    #[must_use]
+    pub fn marking_vector(&self) -> BTreeMap<PlaceRef, usize> {
         let mut marking_vector: BTreeMap<PlaceRef, usize> = BTreeMap::new();
         for (key, value) in &self.places {
             marking_vector.insert(key.clone(), value.marking());
@@ -171,79 +486,328 @@ impl PetriNet {
         marking_vector
     }
 
+    /// Gets the initial marking vector for the net, i.e, the number of tokens for every place
+    /// in the initial marking. The exporters always write the initial marking.
+    /// Returns a `BTreeMap` with the place references as the keys and the number of tokens as values.
+    #[must_use]
+    pub fn initial_marking_vector(&self) -> BTreeMap<PlaceRef, usize> {
+        self.places
+            .iter()
+            .map(|(place_ref, place)| (place_ref.clone(), place.initial_marking()))
+            .collect()
+    }
+
+    /// Gets the number of tokens in a place in the initial marking of the net.
+    ///
+    /// # Errors
+    ///
+    /// If the `PlaceRef` is invalid, then an error is returned.
+    pub fn initial_marking(&self, place_ref: &PlaceRef) -> Result<usize, Error> {
+        let place = self.get_place(place_ref)?;
+        Ok(place.initial_marking())
+    }
+
+    /// Sets the number of tokens in a place in the initial marking of the net.
+    /// The current marking is left unchanged.
+    /// The initial marking of the place is fixed, adding or removing tokens no longer changes it.
+    ///
+    /// # Errors
+    ///
+    /// If the `PlaceRef` is invalid, then an error is returned.
+    /// If the number of tokens exceeds the capacity of the place, then an error is returned.
+    pub fn set_initial_marking(
+        &mut self,
+        place_ref: &PlaceRef,
+        tokens: usize,
+    ) -> Result<(), Error> {
+        let place = self.get_place_mut(place_ref)?;
+        if !place.set_initial_marking(tokens) {
+            return Err(Error::CapacityExceeded(place_ref.clone()));
+        }
+        Ok(())
+    }
+
+    /// Stores the current marking of the net as its initial marking.
+    /// The initial marking of every place is fixed, adding or removing tokens no longer changes it.
+    pub fn store_initial_marking(&mut self) {
+        for place in self.places.values_mut() {
+            // The current marking never exceeds the capacity.
+            place.set_initial_marking(place.marking());
+        }
+    }
+
+    /// Restores the current marking of the net to its initial marking,
+    /// undoing every transition fired since the initial marking was stored.
+    pub fn restore_initial_marking(&mut self) {
+        for place in self.places.values_mut() {
+            place.set_marking(place.initial_marking());
+        }
+    }
+
+    /// Gets the number of tokens of every place of the net in the given marking.
+    /// Places missing from the marking hold no tokens, entries for places not in the net are dropped.
+    pub(crate) fn complete_marking(
+        &self,
+        marking: &BTreeMap<PlaceRef, usize>,
+    ) -> BTreeMap<PlaceRef, usize> {
+        self.places
+            .keys()
+            .map(|place_ref| {
+                (
+                    place_ref.clone(),
+                    marking.get(place_ref).copied().unwrap_or(0),
+                )
+            })
+            .collect()
+    }
+
+    /// Fixes the initial marking of every place at its current marking,
+    /// unless it was fixed already.
+    fn fix_initial_marking(&mut self) {
+        for place in self.places.values_mut() {
+            place.fix_initial_marking();
+        }
+    }
+
     /// Adds `tokens_to_add` tokens to a place in the net.
     ///
+    /// While the net is being built, the initial marking of the place changes together with the current one.
+    /// Once a transition has fired or the initial marking of the place was set or stored,
+    /// only the current marking changes.
+    ///
     /// # Errors
     ///
     /// If the `PlaceRef` is invalid, then an error is returned.
     /// If the addition causes an overflow, then an error is returned.
-    pub fn add_token(&mut self, place_ref: &PlaceRef, tokens_to_add: usize) -> Result<(), &str> {
+    /// If the addition exceeds the capacity of the place, then an error is returned.
+    pub fn add_token(&mut self, place_ref: &PlaceRef, tokens_to_add: usize) -> Result<(), Error> {
         let place = self.get_place_mut(place_ref)?;
-        place.add_token(tokens_to_add)
+        place
+            .add_token(tokens_to_add)
+            .map_err(|error| error.for_place(place_ref))
     }
 
     /// Removes `tokens_to_remove` tokens from a place in the net.
+    /// The initial marking changes as in `add_token`.
     ///
     /// # Errors
     ///
     /// If the `PlaceRef` is invalid, then an error is returned.
-    /// If the subtraction causes an overflow, then an error is returned.
+    /// If the place does not have enough tokens, then an error is returned.
     pub fn remove_token(
         &mut self,
         place_ref: &PlaceRef,
         tokens_to_remove: usize,
-    ) -> Result<(), &str> {
+    ) -> Result<(), Error> {
         let place = self.get_place_mut(place_ref)?;
-        place.remove_token(tokens_to_remove)
+        place
+            .remove_token(tokens_to_remove)
+            .map_err(|error| error.for_place(place_ref))
     }
 
-    fn get_place(&self, place_ref: &PlaceRef) -> Result<&Place, &str> {
+    /// Gets the capacity of a place in the net.
+    /// Returns `None` if the place can hold any number of tokens.
+    ///
+    /// # Errors
+    ///
+    /// If the `PlaceRef` is invalid, then an error is returned.
+    pub fn capacity(&self, place_ref: &PlaceRef) -> Result<Option<usize>, Error> {
+        let place = self.get_place(place_ref)?;
+        Ok(place.capacity())
+    }
+
+    /// Sets the capacity of a place in the net, i.e. the maximum number of tokens it can hold.
+    /// Passing `None` removes the limit.
+    ///
+    /// A transition whose firing would exceed the capacity of an output place is not enabled.
+    ///
+    /// # Errors
+    ///
+    /// If the `PlaceRef` is invalid, then an error is returned.
+    /// If the place currently holds more tokens than the capacity, then an error is returned.
+    pub fn set_capacity(
+        &mut self,
+        place_ref: &PlaceRef,
+        capacity: Option<usize>,
+    ) -> Result<(), Error> {
+        let place = self.get_place_mut(place_ref)?;
+        if !place.set_capacity(capacity) {
+            return Err(Error::CapacityExceeded(place_ref.clone()));
+        }
+        Ok(())
+    }
+
+    /// Gets the firing interval of a transition in the net.
+    /// Transitions have the untimed interval `[0, ∞)` unless another one is set.
+    ///
+    /// # Errors
+    ///
+    /// If the `TransitionRef` is invalid, then an error is returned.
+    pub fn firing_interval(&self, transition_ref: &TransitionRef) -> Result<FiringInterval, Error> {
+        let transition = self.get_transition(transition_ref)?;
+        Ok(transition.firing_interval())
+    }
+
+    /// Sets the firing interval of a transition in the net.
+    /// The interval is only taken into account by the timed simulation,
+    /// the untimed firing rule ignores it.
+    ///
+    /// # Errors
+    ///
+    /// If the `TransitionRef` is invalid, then an error is returned.
+    pub fn set_firing_interval(
+        &mut self,
+        transition_ref: &TransitionRef,
+        interval: FiringInterval,
+    ) -> Result<(), Error> {
+        let Some(transition) = self.transitions.get_mut(transition_ref) else {
+            return Err(Error::InvalidTransitionRef(transition_ref.clone()));
+        };
+        transition.set_firing_interval(interval);
+        Ok(())
+    }
+
+    /// Gets the stochastic timing of a transition in the net.
+    /// Transitions are exponential with rate one unless another timing is set.
+    ///
+    /// # Errors
+    ///
+    /// If the `TransitionRef` is invalid, then an error is returned.
+    pub fn stochastic_timing(
+        &self,
+        transition_ref: &TransitionRef,
+    ) -> Result<StochasticTiming, Error> {
+        let transition = self.get_transition(transition_ref)?;
+        Ok(transition.stochastic_timing())
+    }
+
+    /// Sets the stochastic timing of a transition in the net.
+    /// The timing is only taken into account by the Markov chain analysis,
+    /// the untimed firing rule ignores it.
+    ///
+    /// # Errors
+    ///
+    /// If the `TransitionRef` is invalid, then an error is returned.
+    /// If the rate or the weight is not positive and finite, then an error is returned.
+    pub fn set_stochastic_timing(
+        &mut self,
+        transition_ref: &TransitionRef,
+        timing: StochasticTiming,
+    ) -> Result<(), Error> {
+        let Some(transition) = self.transitions.get_mut(transition_ref) else {
+            return Err(Error::InvalidTransitionRef(transition_ref.clone()));
+        };
+        if !timing.is_valid() {
+            return Err(Error::InvalidRate(transition_ref.clone()));
+        }
+        transition.set_stochastic_timing(timing);
+        Ok(())
+    }
+
+    fn get_place(&self, place_ref: &PlaceRef) -> Result<&Place, Error> {
         let Some(place) = self.places.get(place_ref) else {
-            return Err("Place reference is invalid. It is not present in the net.");
+            return Err(Error::InvalidPlaceRef(place_ref.clone()));
         };
         Ok(place)
     }
 
-    fn get_place_mut(&mut self, place_ref: &PlaceRef) -> Result<&mut Place, &str> {
+    fn get_place_mut(&mut self, place_ref: &PlaceRef) -> Result<&mut Place, Error> {
         let Some(place) = self.places.get_mut(place_ref) else {
-            return Err("Place reference is invalid. It is not present in the net.");
+            return Err(Error::InvalidPlaceRef(place_ref.clone()));
         };
         Ok(place)
     }
 
+    fn get_transition(&self, transition_ref: &TransitionRef) -> Result<&Transition, Error> {
+        let Some(transition) = self.transitions.get(transition_ref) else {
+            return Err(Error::InvalidTransitionRef(transition_ref.clone()));
+        };
+        Ok(transition)
+    }
+
     fn get_place_transition_pair_mut(
         &mut self,
         place_ref: &PlaceRef,
         transition_ref: &TransitionRef,
-    ) -> Result<(&mut Place, &mut Transition), &str> {
+    ) -> Result<(&mut Place, &mut Transition), Error> {
         let Some(place) = self.places.get_mut(place_ref) else {
-            return Err("Place reference is invalid. It is not present in the net.");
+            return Err(Error::InvalidPlaceRef(place_ref.clone()));
         };
 
         let Some(transition) = self.transitions.get_mut(transition_ref) else {
-            return Err("Transition reference is invalid. It is not present in the net.");
+            return Err(Error::InvalidTransitionRef(transition_ref.clone()));
         };
 
         Ok((place, transition))
     }
 
-    const fn check_arc_insertion(
+    fn check_arc_insertion(
         inserted_incoming: bool,
         inserted_outgoing: bool,
-    ) -> Result<(), &'static str> {
+        arc_ref: ArcRef,
+    ) -> Result<(), Error> {
         if !inserted_outgoing && !inserted_incoming {
-            return Err("Cannot add the arc. The arc already exists.");
+            return Err(Error::DuplicateArc(arc_ref));
         }
         if !inserted_outgoing || !inserted_incoming {
-            return Err("The arc existed only in one side. The net was in an inconsistent state.");
+            return Err(Error::InconsistentArc(arc_ref));
+        }
+        Ok(())
+    }
+
+    fn check_arc_exists(
+        exists_outgoing: bool,
+        exists_incoming: bool,
+        arc_ref: ArcRef,
+    ) -> Result<(), Error> {
+        if !exists_outgoing && !exists_incoming {
+            return Err(Error::ArcNotFound(arc_ref));
+        }
+        if !exists_outgoing || !exists_incoming {
+            return Err(Error::InconsistentArc(arc_ref));
+        }
+        Ok(())
+    }
+
+    fn check_arc_weight(weight: usize, arc_ref: &ArcRef) -> Result<(), Error> {
+        if weight == 0 {
+            return Err(Error::InvalidArcWeight(arc_ref.clone()));
+        }
+        Ok(())
+    }
+
+    /// Checks that the net contains no arc of the given kinds.
+    /// Used by the operations that only support some kinds of arcs.
+    pub(crate) fn check_no_arc_kinds(&self, unsupported: &[ArcKind]) -> Result<(), Error> {
+        for (place_ref, place) in &self.places {
+            for (transition_ref, (kind, _)) in place.get_extended_postset() {
+                if unsupported.contains(kind) {
+                    let arc_ref =
+                        ArcRef::PlaceTransition(place_ref.clone(), transition_ref.clone());
+                    return Err(Error::UnsupportedArc(arc_ref, *kind));
+                }
+            }
         }
         Ok(())
     }
+
+    fn check_arc_weights(
+        weight_outgoing: Option<&usize>,
+        weight_incoming: Option<&usize>,
+        arc_ref: ArcRef,
+    ) -> Result<usize, Error> {
+        match (weight_outgoing, weight_incoming) {
+            (None, None) => Err(Error::ArcNotFound(arc_ref)),
+            (Some(outgoing), Some(incoming)) if *outgoing == *incoming => Ok(*outgoing),
+            _ => Err(Error::InconsistentArc(arc_ref)),
+        }
+    }
 }
 
 #[cfg(test)]
 mod net_tests {
     use super::*;
+    use crate::net_creator::*;
 
     #[test]
     fn new_default_has_no_places() {
@@ -322,8 +886,8 @@ mod net_tests {
 
         let arcs = net.find_arcs_place_transition();
         assert_eq!(arcs.len(), 2);
-        assert!(arcs.contains(&(place_1, transition_1)));
-        assert!(arcs.contains(&(place_2, transition_2)));
+        assert!(arcs.contains_key(&(place_1, transition_1)));
+        assert!(arcs.contains_key(&(place_2, transition_2)));
     }
 
     #[test]
@@ -500,4 +1064,531 @@ mod net_tests {
         let result = net.add_arc_transition_place(&transition_2, &place_3);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn net_add_weighted_arc_place_transition_stores_the_weight() {
+        let mut net = PetriNet::new();
+        let place_ref = net.add_place("Example place");
+        let transition_ref = net.add_transition("Example transition");
+
+        let result = net.add_weighted_arc_place_transition(&place_ref, &transition_ref, 3);
+        assert!(result.is_ok());
+
+        let result = net.arc_weight_place_transition(&place_ref, &transition_ref);
+        assert_eq!(result.unwrap(), 3);
+    }
+
+    #[test]
+    fn net_add_weighted_arc_transition_place_stores_the_weight() {
+        let mut net = PetriNet::new();
+        let place_ref = net.add_place("Example place");
+        let transition_ref = net.add_transition("Example transition");
+
+        let result = net.add_weighted_arc_transition_place(&transition_ref, &place_ref, 4);
+        assert!(result.is_ok());
+
+        let result = net.arc_weight_transition_place(&transition_ref, &place_ref);
+        assert_eq!(result.unwrap(), 4);
+    }
+
+    #[test]
+    fn net_add_arc_place_transition_has_weight_one() {
+        let mut net = PetriNet::new();
+        let place_ref = net.add_place("Example place");
+        let transition_ref = net.add_transition("Example transition");
+
+        assert!(
+            net.add_arc_place_transition(&place_ref, &transition_ref)
+                .is_ok()
+        );
+
+        let result = net.arc_weight_place_transition(&place_ref, &transition_ref);
+        assert_eq!(result.unwrap(), 1);
+    }
+
+    #[test]
+    fn net_add_weighted_arc_returns_err_if_weight_is_zero() {
+        let mut net = PetriNet::new();
+        let place_ref = net.add_place("Example place");
+        let transition_ref = net.add_transition("Example transition");
+
+        let result = net.add_weighted_arc_place_transition(&place_ref, &transition_ref, 0);
+        assert!(result.is_err());
+        let result = net.add_weighted_arc_transition_place(&transition_ref, &place_ref, 0);
+        assert!(result.is_err());
+        assert!(net.find_arcs_place_transition().is_empty());
+        assert!(net.find_arcs_transition_place().is_empty());
+    }
+
+    #[test]
+    fn net_add_weighted_arc_returns_err_if_arc_already_exists() {
+        let mut net = PetriNet::new();
+        let place_ref = net.add_place("Example place");
+        let transition_ref = net.add_transition("Example transition");
+
+        let result = net.add_weighted_arc_place_transition(&place_ref, &transition_ref, 2);
+        assert!(result.is_ok());
+        let result = net.add_weighted_arc_place_transition(&place_ref, &transition_ref, 5);
+        assert!(result.is_err());
+
+        let result = net.arc_weight_place_transition(&place_ref, &transition_ref);
+        assert_eq!(result.unwrap(), 2);
+    }
+
+    #[test]
+    fn net_arc_weight_returns_err_if_arc_does_not_exist() {
+        let mut net = PetriNet::new();
+        let place_ref = net.add_place("Example place");
+        let transition_ref = net.add_transition("Example transition");
+
+        assert!(
+            net.arc_weight_place_transition(&place_ref, &transition_ref)
+                .is_err()
+        );
+        assert!(
+            net.arc_weight_transition_place(&transition_ref, &place_ref)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn net_set_arc_weight_place_transition_updates_the_weight() {
+        let mut net = PetriNet::new();
+        let place_ref = net.add_place("Example place");
+        let transition_ref = net.add_transition("Example transition");
+
+        assert!(
+            net.add_arc_place_transition(&place_ref, &transition_ref)
+                .is_ok()
+        );
+        let result = net.set_arc_weight_place_transition(&place_ref, &transition_ref, 6);
+        assert!(result.is_ok());
+
+        let result = net.arc_weight_place_transition(&place_ref, &transition_ref);
+        assert_eq!(result.unwrap(), 6);
+    }
+
+    #[test]
+    fn net_set_arc_weight_transition_place_returns_err_if_arc_does_not_exist() {
+        let mut net = PetriNet::new();
+        let place_ref = net.add_place("Example place");
+        let transition_ref = net.add_transition("Example transition");
+
+        assert!(
+            net.add_arc_place_transition(&place_ref, &transition_ref)
+                .is_ok()
+        );
+        let result = net.set_arc_weight_transition_place(&transition_ref, &place_ref, 2);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn net_find_weighted_arcs_lists_all_arcs_with_weights() {
+        let mut net = PetriNet::new();
+        let place_1 = net.add_place("P1");
+        let place_2 = net.add_place("P2");
+        let transition = net.add_transition("T1");
+
+        let result = net.add_weighted_arc_place_transition(&place_1, &transition, 2);
+        assert!(result.is_ok());
+        let result = net.add_weighted_arc_transition_place(&transition, &place_2, 3);
+        assert!(result.is_ok());
+
+        let arcs = net.find_weighted_arcs_place_transition();
+        assert_eq!(arcs.len(), 1);
+        assert_eq!(arcs.get(&(place_1, transition.clone())), Some(&2));
+
+        let arcs = net.find_weighted_arcs_transition_place();
+        assert_eq!(arcs.len(), 1);
+        assert_eq!(arcs.get(&(transition, place_2)), Some(&3));
+    }
+
+    #[test]
+    fn net_remove_place_updates_size_and_invalidates_ref() {
+        let mut net = PetriNet::new();
+        let place_ref = net.add_place("Example place");
+
+        assert!(net.remove_place(&place_ref).is_ok());
+        assert_eq!(net.get_cardinality_places(), 0);
+        assert!(!net.check_place_ref(&place_ref));
+        assert!(net.marking(&place_ref).is_err());
+    }
+
+    #[test]
+    fn net_remove_place_returns_err_if_place_ref_invalid() {
+        let mut net = PetriNet::new();
+        let place_ref = net.add_place("Example place");
+
+        assert!(net.remove_place(&place_ref).is_ok());
+        assert!(net.remove_place(&place_ref).is_err());
+    }
+
+    #[test]
+    fn net_remove_place_removes_connected_arcs_on_both_sides() {
+        let mut net = PetriNet::new();
+        let place_1 = net.add_place("P1");
+        let place_2 = net.add_place("P2");
+        let place_3 = net.add_place("P3");
+        let transition_1 = net.add_transition("T1");
+        let transition_2 = net.add_transition("T2");
+        assert!(
+            net.add_arc_place_transition(&place_1, &transition_1)
+                .is_ok()
+        );
+        assert!(
+            net.add_arc_transition_place(&transition_1, &place_2)
+                .is_ok()
+        );
+        assert!(
+            net.add_arc_place_transition(&place_2, &transition_2)
+                .is_ok()
+        );
+        assert!(
+            net.add_arc_transition_place(&transition_2, &place_3)
+                .is_ok()
+        );
+
+        assert!(net.remove_place(&place_2).is_ok());
+
+        let arcs = net.find_arcs_place_transition();
+        assert_eq!(arcs.len(), 1);
+        assert!(arcs.contains_key(&(place_1, transition_1.clone())));
+        let arcs = net.find_arcs_transition_place();
+        assert_eq!(arcs.len(), 1);
+        assert!(arcs.contains(&(transition_2.clone(), place_3)));
+        for (_, transition) in net.transitions_iter() {
+            assert!(!transition.get_preset().contains_key(&place_2));
+            assert!(!transition.get_postset().contains_key(&place_2));
+        }
+    }
+
+    #[test]
+    fn net_remove_transition_removes_connected_arcs_on_both_sides() {
+        let mut net = PetriNet::new();
+        let place_ref = net.add_place("Example place");
+        let transition_ref = net.add_transition("Example transition");
+        assert!(
+            net.add_arc_place_transition(&place_ref, &transition_ref)
+                .is_ok()
+        );
+        assert!(
+            net.add_arc_transition_place(&transition_ref, &place_ref)
+                .is_ok()
+        );
+
+        assert!(net.remove_transition(&transition_ref).is_ok());
+        assert_eq!(net.get_cardinality_transitions(), 0);
+        assert!(!net.check_transition_ref(&transition_ref));
+        assert!(net.find_arcs_place_transition().is_empty());
+        assert!(net.find_arcs_transition_place().is_empty());
+        assert_eq!(net.find_unconnected_places().len(), 1);
+    }
+
+    #[test]
+    fn net_remove_transition_returns_err_if_transition_ref_invalid() {
+        let mut net = PetriNet::new();
+        let transition_ref = TransitionRef::new("Not in the net");
+
+        assert!(net.remove_transition(&transition_ref).is_err());
+    }
+
+    #[test]
+    fn net_remove_arc_place_transition_keeps_the_other_direction() {
+        let mut net = PetriNet::new();
+        let place_ref = net.add_place("Example place");
+        let transition_ref = net.add_transition("Example transition");
+        assert!(
+            net.add_arc_place_transition(&place_ref, &transition_ref)
+                .is_ok()
+        );
+        assert!(
+            net.add_arc_transition_place(&transition_ref, &place_ref)
+                .is_ok()
+        );
+
+        assert!(
+            net.remove_arc_place_transition(&place_ref, &transition_ref)
+                .is_ok()
+        );
+        assert!(net.find_arcs_place_transition().is_empty());
+        assert_eq!(net.find_arcs_transition_place().len(), 1);
+
+        // The arc can be added again after the removal.
+        assert!(
+            net.add_arc_place_transition(&place_ref, &transition_ref)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn net_remove_arc_transition_place_returns_err_if_arc_does_not_exist() {
+        let mut net = PetriNet::new();
+        let place_ref = net.add_place("Example place");
+        let transition_ref = net.add_transition("Example transition");
+
+        assert!(
+            net.remove_arc_transition_place(&transition_ref, &place_ref)
+                .is_err()
+        );
+        assert!(
+            net.add_arc_transition_place(&transition_ref, &place_ref)
+                .is_ok()
+        );
+        assert!(
+            net.remove_arc_transition_place(&transition_ref, &place_ref)
+                .is_ok()
+        );
+        assert!(
+            net.remove_arc_transition_place(&transition_ref, &place_ref)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn net_add_arc_returns_duplicate_arc_error_with_the_refs() {
+        let mut net = PetriNet::new();
+        let place_ref = net.add_place("Example place");
+        let transition_ref = net.add_transition("Example transition");
+        assert!(
+            net.add_arc_place_transition(&place_ref, &transition_ref)
+                .is_ok()
+        );
+
+        let result = net.add_arc_place_transition(&place_ref, &transition_ref);
+        let Err(Error::DuplicateArc(ArcRef::PlaceTransition(source, dest))) = result else {
+            panic!("Adding the same arc twice should return a duplicate arc error");
+        };
+        assert_eq!(source, place_ref);
+        assert_eq!(dest, transition_ref);
+    }
+
+    #[test]
+    fn net_invalid_place_ref_error_can_be_kept_while_modifying_the_net() {
+        let mut net = PetriNet::new();
+        let place_ref = net.add_place("Example place");
+        assert!(net.remove_place(&place_ref).is_ok());
+
+        let error = net.marking(&place_ref).unwrap_err();
+        net.add_place("Another place");
+
+        assert!(matches!(error, Error::InvalidPlaceRef(invalid) if invalid == place_ref));
+    }
+
+    #[test]
+    fn net_remove_token_returns_insufficient_tokens_error() {
+        let mut net = PetriNet::new();
+        let place_ref = net.add_place("Example place");
+        assert!(net.add_token(&place_ref, 1).is_ok());
+
+        let result = net.remove_token(&place_ref, 2);
+        assert!(matches!(result, Err(Error::InsufficientTokens(_))));
+        assert_eq!(net.marking(&place_ref).unwrap(), 1);
+    }
+
+    #[test]
+    fn net_add_token_returns_token_overflow_error() {
+        let mut net = PetriNet::new();
+        let place_ref = net.add_place("Example place");
+        assert!(net.add_token(&place_ref, usize::MAX).is_ok());
+
+        let result = net.add_token(&place_ref, 1);
+        assert!(matches!(result, Err(Error::TokenOverflow(_))));
+    }
+
+    #[test]
+    fn net_fire_keeps_initial_marking() {
+        let (mut net, place_refs, transition_refs) = create_net_chain_topology(2);
+        assert!(net.add_token(&place_refs[0], 1).is_ok());
+        assert!(net.fire(&transition_refs[0]).is_ok());
+
+        assert_eq!(net.marking(&place_refs[0]).unwrap(), 0);
+        assert_eq!(net.initial_marking(&place_refs[0]).unwrap(), 1);
+        assert_eq!(net.initial_marking(&place_refs[1]).unwrap(), 0);
+
+        net.restore_initial_marking();
+        assert_eq!(net.marking_vector(), net.initial_marking_vector());
+        assert_eq!(net.marking(&place_refs[0]).unwrap(), 1);
+    }
+
+    #[test]
+    fn net_tokens_changed_after_fire_keep_initial_marking() {
+        let (mut net, place_refs, transition_refs) = create_net_chain_topology(2);
+        assert!(net.set_capacity(&place_refs[0], Some(2)).is_ok());
+        assert!(net.add_token(&place_refs[0], 2).is_ok());
+        assert!(net.fire(&transition_refs[0]).is_ok());
+        assert!(net.fire(&transition_refs[0]).is_ok());
+
+        assert!(net.remove_token(&place_refs[1], 1).is_ok());
+        assert!(net.add_token(&place_refs[0], 2).is_ok());
+        assert_eq!(net.marking(&place_refs[0]).unwrap(), 2);
+        assert_eq!(net.marking(&place_refs[1]).unwrap(), 1);
+        assert_eq!(net.initial_marking(&place_refs[0]).unwrap(), 2);
+        assert_eq!(net.initial_marking(&place_refs[1]).unwrap(), 0);
+    }
+
+    #[test]
+    fn net_set_initial_marking_stops_following_added_tokens() {
+        let mut net = PetriNet::new();
+        let place_ref = net.add_place("Example place");
+        assert!(net.add_token(&place_ref, 1).is_ok());
+        assert_eq!(net.initial_marking(&place_ref).unwrap(), 1);
+
+        assert!(net.set_initial_marking(&place_ref, 3).is_ok());
+        assert!(net.add_token(&place_ref, 1).is_ok());
+        assert_eq!(net.marking(&place_ref).unwrap(), 2);
+        assert_eq!(net.initial_marking(&place_ref).unwrap(), 3);
+    }
+
+    #[test]
+    fn net_store_initial_marking_copies_current_marking() {
+        let (mut net, place_refs, transition_refs) = create_net_chain_topology(2);
+        assert!(net.add_token(&place_refs[0], 1).is_ok());
+        assert!(net.fire(&transition_refs[0]).is_ok());
+        net.store_initial_marking();
+        net.restore_initial_marking();
+
+        assert_eq!(net.initial_marking(&place_refs[0]).unwrap(), 0);
+        assert_eq!(net.initial_marking(&place_refs[1]).unwrap(), 1);
+        assert_eq!(net.marking(&place_refs[1]).unwrap(), 1);
+    }
+
+    #[test]
+    fn net_set_initial_marking_leaves_current_marking_unchanged() {
+        let mut net = PetriNet::new();
+        let place_ref = net.add_place("Example place");
+        assert!(net.set_capacity(&place_ref, Some(3)).is_ok());
+
+        assert!(net.set_initial_marking(&place_ref, 2).is_ok());
+        assert_eq!(net.initial_marking(&place_ref).unwrap(), 2);
+        assert_eq!(net.marking(&place_ref).unwrap(), 0);
+        let result = net.set_initial_marking(&place_ref, 4);
+        assert!(matches!(result, Err(Error::CapacityExceeded(_))));
+        let result = net.initial_marking(&PlaceRef::new("Missing place"));
+        assert!(matches!(result, Err(Error::InvalidPlaceRef(_))));
+    }
+
+    #[test]
+    fn net_new_has_random_ids() {
+        let net = PetriNet::new();
+
+        assert!(!net.has_deterministic_ids());
+    }
+
+    #[test]
+    fn net_with_deterministic_ids_produces_equal_refs() {
+        let build = || {
+            let mut net = PetriNet::with_deterministic_ids(3);
+            let place_ref = net.add_place("P");
+            let transition_ref = net.add_transition("T");
+            (net, place_ref, transition_ref)
+        };
+        let (net_1, place_ref_1, transition_ref_1) = build();
+        let (net_2, place_ref_2, transition_ref_2) = build();
+
+        assert!(net_1.has_deterministic_ids());
+        assert_eq!(place_ref_1, place_ref_2);
+        assert_eq!(transition_ref_1, transition_ref_2);
+        assert!(net_2.check_place_ref(&place_ref_1));
+        assert!(net_1.check_transition_ref(&transition_ref_2));
+    }
+
+    #[test]
+    fn net_with_deterministic_ids_orders_equal_labels_by_insertion() {
+        let mut net = PetriNet::with_deterministic_ids(0);
+        let place_refs: Vec<PlaceRef> = (0..10).map(|_| net.add_place("same")).collect();
+
+        let ordered: Vec<&PlaceRef> = net.places_iter().map(|(place_ref, _)| place_ref).collect();
+        assert_eq!(ordered, place_refs.iter().collect::<Vec<&PlaceRef>>());
+    }
+
+    #[test]
+    fn net_with_different_seeds_produces_different_refs() {
+        let mut net_1 = PetriNet::with_deterministic_ids(1);
+        let mut net_2 = PetriNet::with_deterministic_ids(2);
+
+        assert_ne!(net_1.add_place("P"), net_2.add_place("P"));
+    }
+
+    #[test]
+    fn net_add_token_returns_capacity_exceeded_error() {
+        let mut net = PetriNet::new();
+        let place_ref = net.add_place("Example place");
+        assert!(net.set_capacity(&place_ref, Some(2)).is_ok());
+        assert_eq!(net.capacity(&place_ref).unwrap(), Some(2));
+
+        assert!(net.add_token(&place_ref, 2).is_ok());
+        let result = net.add_token(&place_ref, 1);
+        assert!(matches!(result, Err(Error::CapacityExceeded(_))));
+        assert_eq!(net.marking(&place_ref).unwrap(), 2);
+    }
+
+    #[test]
+    fn net_set_capacity_below_marking_returns_error() {
+        let mut net = PetriNet::new();
+        let place_ref = net.add_place("Example place");
+        assert!(net.add_token(&place_ref, 3).is_ok());
+
+        let result = net.set_capacity(&place_ref, Some(2));
+        assert!(matches!(result, Err(Error::CapacityExceeded(_))));
+        assert_eq!(net.capacity(&place_ref).unwrap(), None);
+    }
+
+    #[test]
+    fn net_capacity_returns_err_if_place_ref_invalid() {
+        let mut net = PetriNet::new();
+        let place_ref = PlaceRef::new("Not in the net");
+
+        assert!(net.capacity(&place_ref).is_err());
+        assert!(net.set_capacity(&place_ref, Some(1)).is_err());
+    }
+
+    #[test]
+    fn net_firing_interval_defaults_to_untimed() {
+        let mut net = PetriNet::new();
+        let transition_ref = net.add_transition("T1");
+
+        assert_eq!(
+            net.firing_interval(&transition_ref).unwrap(),
+            FiringInterval::default()
+        );
+        let interval = FiringInterval::new(1, 3).unwrap();
+        assert!(net.set_firing_interval(&transition_ref, interval).is_ok());
+        assert_eq!(net.firing_interval(&transition_ref).unwrap(), interval);
+    }
+
+    #[test]
+    fn net_stochastic_timing_defaults_to_exponential_rate_one() {
+        let mut net = PetriNet::new();
+        let transition_ref = net.add_transition("T1");
+
+        assert_eq!(
+            net.stochastic_timing(&transition_ref).unwrap(),
+            StochasticTiming::default()
+        );
+        let timing = StochasticTiming::Immediate(2.5);
+        assert!(net.set_stochastic_timing(&transition_ref, timing).is_ok());
+        assert_eq!(net.stochastic_timing(&transition_ref).unwrap(), timing);
+    }
+
+    #[test]
+    fn net_set_stochastic_timing_returns_err_if_rate_invalid() {
+        let mut net = PetriNet::new();
+        let transition_ref = net.add_transition("T1");
+        let result = net.set_stochastic_timing(&transition_ref, StochasticTiming::Exponential(0.0));
+
+        assert!(matches!(result, Err(Error::InvalidRate(_))));
+        assert_eq!(
+            net.stochastic_timing(&transition_ref).unwrap(),
+            StochasticTiming::default()
+        );
+    }
+
+    #[test]
+    fn net_set_firing_interval_returns_err_if_transition_ref_invalid() {
+        let mut net = PetriNet::new();
+        let transition_ref = TransitionRef::new("Not in the net");
+        let result = net.set_firing_interval(&transition_ref, FiringInterval::unbounded(1));
+
+        assert!(matches!(result, Err(Error::InvalidTransitionRef(_))));
+    }
 }
@@ -0,0 +1,47 @@
+/// The kind of an arc from a place to a transition.
+///
+/// The kind determines how the place takes part in the firing of the transition.
+/// Arcs from transitions to places are always `ArcKind::Normal`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ArcKind {
+    /// The transition requires and consumes as many tokens as the weight of the arc.
+    #[default]
+    Normal,
+    /// The transition is only enabled while the place holds fewer tokens than the weight of the arc.
+    /// No tokens are consumed.
+    Inhibitor,
+    /// The transition requires as many tokens as the weight of the arc, but does not consume them.
+    Read,
+    /// The transition removes every token from the place, regardless of the weight of the arc.
+    /// The arc does not affect whether the transition is enabled.
+    Reset,
+}
+
+impl std::fmt::Display for ArcKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Normal => write!(f, "normal"),
+            Self::Inhibitor => write!(f, "inhibitor"),
+            Self::Read => write!(f, "read"),
+            Self::Reset => write!(f, "reset"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod arc_kind_tests {
+    use super::*;
+
+    #[test]
+    fn arc_kind_default_is_normal() {
+        assert_eq!(ArcKind::default(), ArcKind::Normal);
+    }
+
+    #[test]
+    fn arc_kind_display_trait_prints_lowercase_name() {
+        assert_eq!(format!("{}", ArcKind::Inhibitor), "inhibitor");
+        assert_eq!(format!("{}", ArcKind::Read), "read");
+        assert_eq!(format!("{}", ArcKind::Reset), "reset");
+    }
+}
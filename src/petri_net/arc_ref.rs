@@ -0,0 +1,43 @@
+use crate::petri_net::place_ref::PlaceRef;
+use crate::petri_net::transition_ref::TransitionRef;
+
+/// A reference to an arc in the Petri net.
+///
+/// An arc is identified by its source and its destination.
+/// The variant determines the direction of the arc.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub enum ArcRef {
+    /// An arc from a place to a transition.
+    PlaceTransition(PlaceRef, TransitionRef),
+    /// An arc from a transition to a place.
+    TransitionPlace(TransitionRef, PlaceRef),
+}
+
+impl std::fmt::Display for ArcRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::PlaceTransition(place_ref, transition_ref) => {
+                write!(f, "({place_ref}, {transition_ref})")
+            }
+            Self::TransitionPlace(transition_ref, place_ref) => {
+                write!(f, "({transition_ref}, {place_ref})")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod arc_ref_tests {
+    use super::*;
+
+    #[test]
+    fn arc_ref_display_trait_prints_source_and_dest() {
+        let place_ref = PlaceRef::new("P1");
+        let transition_ref = TransitionRef::new("T1");
+
+        let arc_ref = ArcRef::PlaceTransition(place_ref.clone(), transition_ref.clone());
+        assert_eq!(format!("{arc_ref}"), "(P1, T1)");
+        let arc_ref = ArcRef::TransitionPlace(transition_ref, place_ref);
+        assert_eq!(format!("{arc_ref}"), "(T1, P1)");
+    }
+}
@@ -0,0 +1,670 @@
+//! # Parallel composition
+//!
+//! Glues two nets that model separate components into a single net.
+//! The components interact in two ways:
+//!
+//! - Synchronisation: the transitions of both nets with the same label are actions shared
+//!   by the components. Every pair of a transition of one net and a transition of the other net
+//!   with the same label becomes a single transition that consumes and produces the tokens of both.
+//!   Transitions whose label only appears in one of the nets are copied unchanged.
+//! - Place fusion: pairs of places given explicitly become a single place.
+//!
+//! The other nodes get a prefix per component, so that the labels of both nets do not clash.
+
+use crate::error::Error;
+use crate::petri_net::id_generator::IdGenerator;
+use crate::petri_net::{
+    ArcKind, FiringInterval, PetriNet, Place, PlaceRef, StochasticTiming, Transition, TransitionRef,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The result of composing two nets, with the references of the new nodes
+/// that correspond to every node of the two original nets.
+pub struct Composition {
+    net: PetriNet,
+    left_places: BTreeMap<PlaceRef, PlaceRef>,
+    right_places: BTreeMap<PlaceRef, PlaceRef>,
+    left_transitions: BTreeMap<TransitionRef, Vec<TransitionRef>>,
+    right_transitions: BTreeMap<TransitionRef, Vec<TransitionRef>>,
+    place_labels: BTreeMap<String, Origin>,
+    transition_labels: BTreeMap<String, Origin>,
+}
+
+/// The net that a node of the composed net comes from.
+/// Fused places and synchronised transitions come from both nets.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Origin {
+    Left,
+    Right,
+    Both,
+}
+
+impl Composition {
+    /// Gets the composed net.
+    #[must_use]
+    pub const fn net(&self) -> &PetriNet {
+        &self.net
+    }
+
+    /// Consumes the composition and returns the composed net.
+    #[must_use]
+    pub fn into_net(self) -> PetriNet {
+        self.net
+    }
+
+    /// Gets the place of the composed net that corresponds to a place of the first net.
+    /// Returns `None` if the place was not in the first net.
+    #[must_use]
+    pub fn left_place(&self, place_ref: &PlaceRef) -> Option<&PlaceRef> {
+        self.left_places.get(place_ref)
+    }
+
+    /// Gets the place of the composed net that corresponds to a place of the second net.
+    /// Returns `None` if the place was not in the second net.
+    #[must_use]
+    pub fn right_place(&self, place_ref: &PlaceRef) -> Option<&PlaceRef> {
+        self.right_places.get(place_ref)
+    }
+
+    /// Gets the transitions of the composed net that correspond to a transition of the first net.
+    /// A synchronised transition has one copy for every partner in the second net.
+    /// Returns `None` if the transition was not in the first net.
+    #[must_use]
+    pub fn left_transitions(&self, transition_ref: &TransitionRef) -> Option<&[TransitionRef]> {
+        self.left_transitions.get(transition_ref).map(Vec::as_slice)
+    }
+
+    /// Gets the transitions of the composed net that correspond to a transition of the second net.
+    /// A synchronised transition has one copy for every partner in the first net.
+    /// Returns `None` if the transition was not in the second net.
+    #[must_use]
+    pub fn right_transitions(&self, transition_ref: &TransitionRef) -> Option<&[TransitionRef]> {
+        self.right_transitions
+            .get(transition_ref)
+            .map(Vec::as_slice)
+    }
+
+    /// Adds a copy of the place with the tokens and the capacity of the original one.
+    fn add_place(&mut self, label: &str, place: &Place, origin: Origin) -> Result<PlaceRef, Error> {
+        claim_label(&mut self.place_labels, label, origin)?;
+        let place_ref = self.net.add_place(label);
+        self.net.add_token(&place_ref, place.marking())?;
+        self.net
+            .set_initial_marking(&place_ref, place.initial_marking())?;
+        self.net.set_capacity(&place_ref, place.capacity())?;
+        Ok(place_ref)
+    }
+
+    /// Adds a transition with the arcs and the timings given.
+    fn add_transition(
+        &mut self,
+        label: &str,
+        arcs: &Arcs,
+        interval: FiringInterval,
+        timing: StochasticTiming,
+        origin: Origin,
+    ) -> Result<TransitionRef, Error> {
+        claim_label(&mut self.transition_labels, label, origin)?;
+        let transition_ref = self.net.add_transition(label);
+        for (place_ref, weight) in &arcs.inputs {
+            self.net
+                .add_weighted_arc_place_transition(place_ref, &transition_ref, *weight)?;
+        }
+        for (place_ref, (kind, weight)) in &arcs.extended_inputs {
+            self.net
+                .add_arc_with_kind(place_ref, &transition_ref, *kind, *weight)?;
+        }
+        for (place_ref, weight) in &arcs.outputs {
+            self.net
+                .add_weighted_arc_transition_place(&transition_ref, place_ref, *weight)?;
+        }
+        self.net.set_firing_interval(&transition_ref, interval)?;
+        self.net.set_stochastic_timing(&transition_ref, timing)?;
+        Ok(transition_ref)
+    }
+
+    /// Copies the transitions of one of the nets whose label is not synchronised.
+    fn copy_transitions(
+        &mut self,
+        net: &PetriNet,
+        prefix: &str,
+        synchronised_labels: &BTreeSet<String>,
+        origin: Origin,
+    ) -> Result<(), Error> {
+        let is_left = origin == Origin::Left;
+        for (transition_ref, transition) in &net.transitions {
+            if synchronised_labels.contains(transition_ref.label()) {
+                continue;
+            }
+            let places = if is_left {
+                &self.left_places
+            } else {
+                &self.right_places
+            };
+            let arcs = Arcs::new(transition, places);
+            let label = format!("{prefix}{}", transition_ref.label());
+            let new_ref = self.add_transition(
+                &label,
+                &arcs,
+                transition.firing_interval(),
+                transition.stochastic_timing(),
+                origin,
+            )?;
+            let transitions = if is_left {
+                &mut self.left_transitions
+            } else {
+                &mut self.right_transitions
+            };
+            transitions.insert(transition_ref.clone(), vec![new_ref]);
+        }
+        Ok(())
+    }
+
+    /// Adds a synchronised transition for every pair of transitions with the same label.
+    fn synchronise(&mut self, left_net: &PetriNet, right_net: &PetriNet) -> Result<(), Error> {
+        for (left_ref, left) in &left_net.transitions {
+            let partners = right_net
+                .transitions
+                .iter()
+                .filter(|(right_ref, _)| right_ref.label() == left_ref.label());
+            for (right_ref, right) in partners {
+                let incompatible =
+                    || Error::IncompatibleSynchronisation(left_ref.clone(), right_ref.clone());
+                let mut arcs = Arcs::new(left, &self.left_places);
+                if !arcs.merge(Arcs::new(right, &self.right_places))? {
+                    return Err(incompatible());
+                }
+                let interval = intersect(left.firing_interval(), right.firing_interval())
+                    .ok_or_else(incompatible)?;
+                if left.stochastic_timing() != right.stochastic_timing() {
+                    return Err(incompatible());
+                }
+                let new_ref = self.add_transition(
+                    left_ref.label(),
+                    &arcs,
+                    interval,
+                    left.stochastic_timing(),
+                    Origin::Both,
+                )?;
+                self.left_transitions
+                    .entry(left_ref.clone())
+                    .or_default()
+                    .push(new_ref.clone());
+                self.right_transitions
+                    .entry(right_ref.clone())
+                    .or_default()
+                    .push(new_ref);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The arcs of a transition, translated to the places of the composed net.
+struct Arcs {
+    inputs: BTreeMap<PlaceRef, usize>,
+    outputs: BTreeMap<PlaceRef, usize>,
+    extended_inputs: BTreeMap<PlaceRef, (ArcKind, usize)>,
+}
+
+impl Arcs {
+    fn new(transition: &Transition, places: &BTreeMap<PlaceRef, PlaceRef>) -> Self {
+        let translate = |arcs: &BTreeMap<PlaceRef, usize>| {
+            arcs.iter()
+                .filter_map(|(place_ref, weight)| Some((places.get(place_ref)?.clone(), *weight)))
+                .collect()
+        };
+        Self {
+            inputs: translate(transition.get_preset()),
+            outputs: translate(transition.get_postset()),
+            extended_inputs: transition
+                .get_extended_preset()
+                .iter()
+                .filter_map(|(place_ref, arc)| Some((places.get(place_ref)?.clone(), *arc)))
+                .collect(),
+        }
+    }
+
+    /// Adds the arcs of another transition. The weights of normal arcs on the same place are added.
+    /// Returns `false` if both transitions are connected to the same place
+    /// and one of the arcs is not a normal arc, since such arcs cannot be combined.
+    fn merge(&mut self, other: Self) -> Result<bool, Error> {
+        for (place_ref, arc) in other.extended_inputs {
+            if self.inputs.contains_key(&place_ref)
+                || self.extended_inputs.insert(place_ref, arc).is_some()
+            {
+                return Ok(false);
+            }
+        }
+        for (place_ref, weight) in other.inputs {
+            if self.extended_inputs.contains_key(&place_ref) {
+                return Ok(false);
+            }
+            Self::add_weight(&mut self.inputs, place_ref, weight)?;
+        }
+        for (place_ref, weight) in other.outputs {
+            Self::add_weight(&mut self.outputs, place_ref, weight)?;
+        }
+        Ok(true)
+    }
+
+    fn add_weight(
+        arcs: &mut BTreeMap<PlaceRef, usize>,
+        place_ref: PlaceRef,
+        weight: usize,
+    ) -> Result<(), Error> {
+        let total = arcs.entry(place_ref).or_insert(0);
+        *total = total.checked_add(weight).ok_or(Error::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
+
+/// Records that the label is given to a node from the origin.
+/// Returns an error if the label was already given to a node from another origin.
+fn claim_label(
+    labels: &mut BTreeMap<String, Origin>,
+    label: &str,
+    origin: Origin,
+) -> Result<(), Error> {
+    match labels.insert(label.to_string(), origin) {
+        Some(previous) if previous != origin => Err(Error::LabelCollision(label.to_string())),
+        _ => Ok(()),
+    }
+}
+
+/// Intersects two firing intervals. Returns `None` if they are disjoint.
+fn intersect(first: FiringInterval, second: FiringInterval) -> Option<FiringInterval> {
+    let earliest = first.earliest().max(second.earliest());
+    match (first.latest(), second.latest()) {
+        (None, None) => Some(FiringInterval::unbounded(earliest)),
+        (Some(latest), None) | (None, Some(latest)) => FiringInterval::new(earliest, latest),
+        (Some(first_latest), Some(second_latest)) => {
+            FiringInterval::new(earliest, first_latest.min(second_latest))
+        }
+    }
+}
+
+impl PetriNet {
+    /// Composes the net with another net in parallel and returns the composed net,
+    /// together with the correspondence between the original nodes and the new ones.
+    /// Both nets are left unchanged.
+    ///
+    /// Transitions with the same label in both nets are synchronised:
+    /// every such pair becomes a transition labelled with the shared label,
+    /// whose arcs are the arcs of both transitions. Normal arcs on a fused place add their weights.
+    /// A synchronised transition fires within the intersection of the two firing intervals.
+    /// Both transitions must have the same stochastic timing, which the synchronised transition keeps.
+    ///
+    /// `fused_places` maps places of this net to places of `other` that become a single place,
+    /// labelled as the place of this net. Fused places must have the same marking,
+    /// the same initial marking and the same capacity.
+    ///
+    /// Every other node is copied with its label prefixed by `left_prefix` for the nodes of this net
+    /// and by `right_prefix` for the nodes of `other`. The prefixes must differ,
+    /// and nodes that come from different nets must not end up with the same label.
+    /// The composed net gets deterministic references if this net has deterministic references.
+    ///
+    /// # Errors
+    ///
+    /// If a `PlaceRef` in `fused_places` is invalid, then an error is returned.
+    /// If two fused places differ in their markings or capacities,
+    /// or a place of `other` is fused with several places, then an error is returned.
+    /// If two synchronised transitions have disjoint firing intervals or different stochastic timings,
+    /// or a fused place is connected to both of them and one of the arcs is not a normal arc,
+    /// then an error is returned.
+    /// If the prefixes are equal, or two places or two transitions from different nets
+    /// would get the same label, then an error is returned.
+    pub fn compose(
+        &self,
+        other: &Self,
+        left_prefix: &str,
+        right_prefix: &str,
+        fused_places: &BTreeMap<PlaceRef, PlaceRef>,
+    ) -> Result<Composition, Error> {
+        if left_prefix == right_prefix {
+            return Err(Error::EqualPrefixes(left_prefix.to_string()));
+        }
+        let mut fused_with: BTreeMap<&PlaceRef, &PlaceRef> = BTreeMap::new();
+        for (left_ref, right_ref) in fused_places {
+            let (left, right) = (self.get_place(left_ref)?, other.get_place(right_ref)?);
+            let is_compatible = left.marking() == right.marking()
+                && left.initial_marking() == right.initial_marking()
+                && left.capacity() == right.capacity();
+            if !is_compatible || fused_with.insert(right_ref, left_ref).is_some() {
+                return Err(Error::IncompatibleFusion(
+                    left_ref.clone(),
+                    right_ref.clone(),
+                ));
+            }
+        }
+
+        let id_generator = match self.id_generator {
+            IdGenerator::Random => IdGenerator::Random,
+            IdGenerator::Sequential { seed, .. } => IdGenerator::sequential(seed),
+        };
+        let mut composition = Composition {
+            net: Self {
+                id_generator,
+                ..Self::default()
+            },
+            left_places: BTreeMap::new(),
+            right_places: BTreeMap::new(),
+            left_transitions: BTreeMap::new(),
+            right_transitions: BTreeMap::new(),
+            place_labels: BTreeMap::new(),
+            transition_labels: BTreeMap::new(),
+        };
+
+        for (place_ref, place) in &self.places {
+            let (label, origin) = if fused_places.contains_key(place_ref) {
+                (place_ref.label().clone(), Origin::Both)
+            } else {
+                (format!("{left_prefix}{}", place_ref.label()), Origin::Left)
+            };
+            let new_ref = composition.add_place(&label, place, origin)?;
+            composition.left_places.insert(place_ref.clone(), new_ref);
+        }
+        for (place_ref, place) in &other.places {
+            let new_ref = if let Some(left_ref) = fused_with.get(place_ref) {
+                composition.left_places[*left_ref].clone()
+            } else {
+                let label = format!("{right_prefix}{}", place_ref.label());
+                composition.add_place(&label, place, Origin::Right)?
+            };
+            composition.right_places.insert(place_ref.clone(), new_ref);
+        }
+
+        let labels = |net: &Self| -> BTreeSet<String> {
+            net.transitions
+                .keys()
+                .map(|transition_ref| transition_ref.label().clone())
+                .collect()
+        };
+        let (left_labels, right_labels) = (labels(self), labels(other));
+        composition.copy_transitions(self, left_prefix, &right_labels, Origin::Left)?;
+        composition.copy_transitions(other, right_prefix, &left_labels, Origin::Right)?;
+        composition.synchronise(self, other)?;
+        Ok(composition)
+    }
+}
+
+#[cfg(test)]
+mod composition_tests {
+    use super::*;
+
+    /// Creates a cycle `first -> first_to_second -> second -> second_to_first -> first`
+    /// with a token in `first`.
+    fn create_cycle(
+        first: &str,
+        first_to_second: &str,
+        second: &str,
+        second_to_first: &str,
+    ) -> (PetriNet, [PlaceRef; 2], [TransitionRef; 2]) {
+        let mut net = PetriNet::new();
+        let place_refs = [net.add_place(first), net.add_place(second)];
+        let transition_refs = [
+            net.add_transition(first_to_second),
+            net.add_transition(second_to_first),
+        ];
+        for index in 0..2 {
+            let other_index = 1 - index;
+            assert!(
+                net.add_arc_place_transition(&place_refs[index], &transition_refs[index])
+                    .is_ok()
+            );
+            assert!(
+                net.add_arc_transition_place(&transition_refs[index], &place_refs[other_index])
+                    .is_ok()
+            );
+        }
+        assert!(net.add_token(&place_refs[0], 1).is_ok());
+        (net, place_refs, transition_refs)
+    }
+
+    #[test]
+    fn compose_synchronises_transitions_with_same_label() {
+        let (producer, producer_places, producer_transitions) =
+            create_cycle("Ready", "produce", "Full", "send");
+        let (consumer, consumer_places, consumer_transitions) =
+            create_cycle("Empty", "send", "Got", "consume");
+        let composition = producer
+            .compose(&consumer, "producer.", "consumer.", &BTreeMap::new())
+            .unwrap();
+        let net = composition.net();
+
+        assert_eq!(net.get_cardinality_places(), 4);
+        assert_eq!(net.get_cardinality_transitions(), 3);
+        let send = composition
+            .left_transitions(&producer_transitions[1])
+            .unwrap();
+        assert_eq!(send.len(), 1);
+        assert_eq!(
+            composition.right_transitions(&consumer_transitions[0]),
+            Some(send)
+        );
+        assert_eq!(send[0].label(), "send");
+        let full = composition.left_place(&producer_places[1]).unwrap();
+        let empty = composition.right_place(&consumer_places[0]).unwrap();
+        assert_eq!(full.label(), "producer.Full");
+        assert_eq!(empty.label(), "consumer.Empty");
+        assert_eq!(net.arc_weight_place_transition(full, &send[0]).unwrap(), 1);
+        assert_eq!(net.arc_weight_place_transition(empty, &send[0]).unwrap(), 1);
+        assert_eq!(
+            composition
+                .left_transitions(&producer_transitions[0])
+                .unwrap()[0]
+                .label(),
+            "producer.produce"
+        );
+        assert_eq!(net.marking(empty).unwrap(), 1);
+        assert_eq!(producer.get_cardinality_transitions(), 2);
+    }
+
+    #[test]
+    fn compose_synchronises_every_pair_of_partners() {
+        let (mut left, left_places, left_transitions) = create_cycle("A", "a", "B", "b");
+        let other_ref = left.add_transition("a");
+        assert!(
+            left.add_arc_place_transition(&left_places[1], &other_ref)
+                .is_ok()
+        );
+        let (right, _, right_transitions) = create_cycle("C", "a", "D", "d");
+        let composition = left.compose(&right, "l_", "r_", &BTreeMap::new()).unwrap();
+
+        assert_eq!(composition.net().get_cardinality_transitions(), 4);
+        assert_eq!(
+            composition
+                .right_transitions(&right_transitions[0])
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(
+            composition
+                .left_transitions(&left_transitions[0])
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq!(composition.left_transitions(&other_ref).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn compose_fuses_places() {
+        let mut producer = PetriNet::new();
+        let produce = producer.add_transition("produce");
+        let buffer = producer.add_place("buffer");
+        assert!(producer.add_arc_transition_place(&produce, &buffer).is_ok());
+        assert!(producer.set_capacity(&buffer, Some(3)).is_ok());
+        let mut consumer = PetriNet::new();
+        let slot = consumer.add_place("slot");
+        let consume = consumer.add_transition("consume");
+        assert!(consumer.add_arc_place_transition(&slot, &consume).is_ok());
+        assert!(consumer.set_capacity(&slot, Some(3)).is_ok());
+        let composition = producer
+            .compose(
+                &consumer,
+                "p_",
+                "c_",
+                &BTreeMap::from([(buffer.clone(), slot.clone())]),
+            )
+            .unwrap();
+        let net = composition.net();
+
+        assert_eq!(net.get_cardinality_places(), 1);
+        let fused = composition.left_place(&buffer).unwrap();
+        assert_eq!(composition.right_place(&slot), Some(fused));
+        assert_eq!(fused.label(), "buffer");
+        assert_eq!(net.capacity(fused).unwrap(), Some(3));
+        let produce = &composition.left_transitions(&produce).unwrap()[0];
+        let consume = &composition.right_transitions(&consume).unwrap()[0];
+        assert_eq!(net.arc_weight_transition_place(produce, fused).unwrap(), 1);
+        assert_eq!(net.arc_weight_place_transition(fused, consume).unwrap(), 1);
+    }
+
+    #[test]
+    fn compose_keeps_markings_and_timings() {
+        let (mut left, left_places, left_transitions) = create_cycle("A", "a", "B", "sync");
+        assert!(left.fire(&left_transitions[0]).is_ok());
+        let interval = FiringInterval::new(2, 5).unwrap();
+        assert!(
+            left.set_firing_interval(&left_transitions[1], interval)
+                .is_ok()
+        );
+        let (mut right, _, right_transitions) = create_cycle("C", "sync", "D", "d");
+        let other_interval = FiringInterval::unbounded(4);
+        assert!(
+            right
+                .set_firing_interval(&right_transitions[0], other_interval)
+                .is_ok()
+        );
+        let composition = left.compose(&right, "l_", "r_", &BTreeMap::new()).unwrap();
+        let net = composition.net();
+
+        let place_ref = composition.left_place(&left_places[0]).unwrap();
+        assert_eq!(net.marking(place_ref).unwrap(), 0);
+        assert_eq!(net.initial_marking(place_ref).unwrap(), 1);
+        let other_ref = composition.left_place(&left_places[1]).unwrap();
+        assert_eq!(net.marking(other_ref).unwrap(), 1);
+        assert_eq!(net.initial_marking(other_ref).unwrap(), 0);
+        let sync = &composition.left_transitions(&left_transitions[1]).unwrap()[0];
+        assert_eq!(
+            net.firing_interval(sync).unwrap(),
+            FiringInterval::new(4, 5).unwrap()
+        );
+    }
+
+    #[test]
+    fn compose_disjoint_intervals_returns_error() {
+        let (mut left, _, left_transitions) = create_cycle("A", "a", "B", "sync");
+        let interval = FiringInterval::new(0, 1).unwrap();
+        assert!(
+            left.set_firing_interval(&left_transitions[1], interval)
+                .is_ok()
+        );
+        let (mut right, _, right_transitions) = create_cycle("C", "sync", "D", "d");
+        let other_interval = FiringInterval::unbounded(2);
+        assert!(
+            right
+                .set_firing_interval(&right_transitions[0], other_interval)
+                .is_ok()
+        );
+        let result = left.compose(&right, "l_", "r_", &BTreeMap::new());
+
+        assert!(matches!(
+            result,
+            Err(Error::IncompatibleSynchronisation(_, _))
+        ));
+    }
+
+    #[test]
+    fn compose_different_stochastic_timings_returns_error() {
+        let (mut left, _, left_transitions) = create_cycle("A", "a", "B", "sync");
+        assert!(
+            left.set_stochastic_timing(&left_transitions[1], StochasticTiming::Exponential(2.0))
+                .is_ok()
+        );
+        let (right, _, _) = create_cycle("C", "sync", "D", "d");
+        let result = left.compose(&right, "l_", "r_", &BTreeMap::new());
+
+        assert!(matches!(
+            result,
+            Err(Error::IncompatibleSynchronisation(_, _))
+        ));
+    }
+
+    #[test]
+    fn compose_equal_prefixes_returns_error() {
+        let (left, _, _) = create_cycle("A", "a", "B", "b");
+        let (right, _, _) = create_cycle("C", "c", "D", "d");
+
+        let result = left.compose(&right, "x_", "x_", &BTreeMap::new());
+        assert!(matches!(result, Err(Error::EqualPrefixes(prefix)) if prefix == "x_"));
+        let result = left.compose(&right, "", "", &BTreeMap::new());
+        assert!(matches!(result, Err(Error::EqualPrefixes(prefix)) if prefix.is_empty()));
+    }
+
+    #[test]
+    fn compose_label_collision_returns_error() {
+        let (left, left_places, _) = create_cycle("A", "a", "B", "b");
+        let (right, _, _) = create_cycle("l_A", "c", "D", "d");
+        let result = left.compose(&right, "l_", "", &BTreeMap::new());
+        assert!(matches!(result, Err(Error::LabelCollision(label)) if label == "l_A"));
+
+        let (right, right_places, _) = create_cycle("C", "c", "A", "d");
+        let fused = BTreeMap::from([(left_places[0].clone(), right_places[0].clone())]);
+        let result = left.compose(&right, "l_", "", &fused);
+        assert!(matches!(result, Err(Error::LabelCollision(label)) if label == "A"));
+
+        let (left, _, _) = create_cycle("A", "ync", "B", "sync");
+        let (right, _, _) = create_cycle("C", "sync", "D", "d");
+        let result = left.compose(&right, "s", "r_", &BTreeMap::new());
+        assert!(matches!(result, Err(Error::LabelCollision(label)) if label == "sync"));
+    }
+
+    #[test]
+    fn compose_incompatible_fusion_returns_error() {
+        let (left, left_places, _) = create_cycle("A", "a", "B", "b");
+        let (right, right_places, _) = create_cycle("C", "c", "D", "d");
+
+        let marked_with_unmarked =
+            BTreeMap::from([(left_places[0].clone(), right_places[1].clone())]);
+        let result = left.compose(&right, "l_", "r_", &marked_with_unmarked);
+        assert!(matches!(result, Err(Error::IncompatibleFusion(_, _))));
+
+        let fused_twice = BTreeMap::from([
+            (left_places[0].clone(), right_places[0].clone()),
+            (left_places[1].clone(), right_places[0].clone()),
+        ]);
+        let result = left.compose(&right, "l_", "r_", &fused_twice);
+        assert!(matches!(result, Err(Error::IncompatibleFusion(_, _))));
+
+        let swapped = BTreeMap::from([(right_places[0].clone(), left_places[0].clone())]);
+        let result = left.compose(&right, "l_", "r_", &swapped);
+        assert!(matches!(result, Err(Error::InvalidPlaceRef(_))));
+    }
+
+    #[test]
+    fn compose_inhibitor_arc_on_fused_place_returns_error() {
+        let (mut left, _, left_transitions) = create_cycle("A", "sync", "B", "b");
+        let (mut right, _, right_transitions) = create_cycle("C", "sync", "D", "d");
+        let left_shared = left.add_place("shared");
+        let right_shared = right.add_place("shared");
+        assert!(
+            left.add_inhibitor_arc(&left_shared, &left_transitions[0], 1)
+                .is_ok()
+        );
+        assert!(
+            right
+                .add_arc_place_transition(&right_shared, &right_transitions[0])
+                .is_ok()
+        );
+        let fused = BTreeMap::from([(left_shared, right_shared)]);
+        let result = left.compose(&right, "l_", "r_", &fused);
+
+        assert!(matches!(
+            result,
+            Err(Error::IncompatibleSynchronisation(_, _))
+        ));
+    }
+}
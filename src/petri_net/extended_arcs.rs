@@ -0,0 +1,389 @@
+use crate::error::Error;
+use crate::petri_net::PetriNet;
+use crate::petri_net::arc_kind::ArcKind;
+use crate::petri_net::arc_ref::ArcRef;
+use crate::petri_net::place_ref::PlaceRef;
+use crate::petri_net::transition_ref::TransitionRef;
+use std::collections::BTreeMap;
+
+impl PetriNet {
+    /// Finds all arcs from places to transitions whose kind is not `ArcKind::Normal`
+    /// together with their kinds and weights.
+    /// Returns a `BTreeMap` with tuples of references (source, dest) as keys
+    /// and tuples (kind, weight) as values.
+    #[must_use]
+    pub fn find_extended_arcs(&self) -> BTreeMap<(PlaceRef, TransitionRef), (ArcKind, usize)> {
+        let mut arcs: BTreeMap<(PlaceRef, TransitionRef), (ArcKind, usize)> = BTreeMap::new();
+        for (place_ref, place) in &self.places {
+            for (transition_ref, arc) in place.get_extended_postset() {
+                arcs.insert((place_ref.clone(), transition_ref.clone()), *arc);
+            }
+        }
+        arcs
+    }
+
+    /// Checks whether the net contains at least one arc of the given kind.
+    #[must_use]
+    pub fn has_arcs_of_kind(&self, kind: ArcKind) -> bool {
+        self.places.values().any(|place| match kind {
+            ArcKind::Normal => !place.get_postset().is_empty(),
+            _ => place
+                .get_extended_postset()
+                .values()
+                .any(|(arc_kind, _)| *arc_kind == kind),
+        })
+    }
+
+    /// Adds an arc of the given kind from a place to a transition with multiplicity `weight`.
+    /// Arcs of kind `ArcKind::Normal` are added as with `add_weighted_arc_place_transition`.
+    ///
+    /// See `ArcKind` for the effect of each kind on the firing of the transition.
+    ///
+    /// # Errors
+    ///
+    /// If the weight is zero, then an error is returned.
+    /// If the `PlaceRef` or the `TransitionRef` is invalid, then an error is returned.
+    /// If an arc of any kind from the place to the transition already exists,
+    /// then an error is returned.
+    /// If the arc was added already on one side but not on the other, then an error is returned.
+    pub fn add_arc_with_kind(
+        &mut self,
+        place_ref: &PlaceRef,
+        transition_ref: &TransitionRef,
+        kind: ArcKind,
+        weight: usize,
+    ) -> Result<(), Error> {
+        if kind == ArcKind::Normal {
+            return self.add_weighted_arc_place_transition(place_ref, transition_ref, weight);
+        }
+        let arc_ref = ArcRef::PlaceTransition(place_ref.clone(), transition_ref.clone());
+        Self::check_arc_weight(weight, &arc_ref)?;
+        let (place, transition) = self.get_place_transition_pair_mut(place_ref, transition_ref)?;
+        if place.get_postset().contains_key(transition_ref) {
+            return Err(Error::DuplicateArc(arc_ref));
+        }
+        let inserted_outgoing = place.add_extended_outgoing(transition_ref.clone(), kind, weight);
+        let inserted_incoming = transition.add_extended_incoming(place_ref.clone(), kind, weight);
+        Self::check_arc_insertion(inserted_incoming, inserted_outgoing, arc_ref)?;
+        Ok(())
+    }
+
+    /// Adds an inhibitor arc from a place to a transition with multiplicity `weight`.
+    ///
+    /// The transition is only enabled while the place holds fewer tokens than the weight,
+    /// i.e. with weight one the place must be empty.
+    /// Firing the transition does not change the marking of the place.
+    ///
+    /// # Errors
+    ///
+    /// See `add_arc_with_kind`.
+    pub fn add_inhibitor_arc(
+        &mut self,
+        place_ref: &PlaceRef,
+        transition_ref: &TransitionRef,
+        weight: usize,
+    ) -> Result<(), Error> {
+        self.add_arc_with_kind(place_ref, transition_ref, ArcKind::Inhibitor, weight)
+    }
+
+    /// Adds a read arc from a place to a transition with multiplicity `weight`.
+    ///
+    /// The transition is only enabled while the place holds at least as many tokens as the weight.
+    /// Firing the transition does not change the marking of the place.
+    ///
+    /// # Errors
+    ///
+    /// See `add_arc_with_kind`.
+    pub fn add_read_arc(
+        &mut self,
+        place_ref: &PlaceRef,
+        transition_ref: &TransitionRef,
+        weight: usize,
+    ) -> Result<(), Error> {
+        self.add_arc_with_kind(place_ref, transition_ref, ArcKind::Read, weight)
+    }
+
+    /// Adds a reset arc from a place to a transition.
+    ///
+    /// Firing the transition removes every token from the place.
+    /// The arc does not affect whether the transition is enabled, its weight is one.
+    ///
+    /// # Errors
+    ///
+    /// See `add_arc_with_kind`.
+    pub fn add_reset_arc(
+        &mut self,
+        place_ref: &PlaceRef,
+        transition_ref: &TransitionRef,
+    ) -> Result<(), Error> {
+        self.add_arc_with_kind(place_ref, transition_ref, ArcKind::Reset, 1)
+    }
+
+    /// Gets the kind of the arc from a place to a transition.
+    ///
+    /// # Errors
+    ///
+    /// If the `PlaceRef` or the `TransitionRef` is invalid, then an error is returned.
+    /// If there is no arc of any kind from the place to the transition, then an error is returned.
+    pub fn arc_kind(
+        &self,
+        place_ref: &PlaceRef,
+        transition_ref: &TransitionRef,
+    ) -> Result<ArcKind, Error> {
+        let place = self.get_place(place_ref)?;
+        if place.get_postset().contains_key(transition_ref) {
+            return Ok(ArcKind::Normal);
+        }
+        Ok(self.extended_arc(place_ref, transition_ref)?.0)
+    }
+
+    /// Gets the kind and the weight of an arc from a place to a transition
+    /// whose kind is not `ArcKind::Normal`.
+    ///
+    /// # Errors
+    ///
+    /// If the `PlaceRef` or the `TransitionRef` is invalid, then an error is returned.
+    /// If the arc does not exist, then an error is returned.
+    /// If the arc exists only on one side or differs between both sides, then an error is returned.
+    pub fn extended_arc(
+        &self,
+        place_ref: &PlaceRef,
+        transition_ref: &TransitionRef,
+    ) -> Result<(ArcKind, usize), Error> {
+        let place = self.get_place(place_ref)?;
+        let transition = self.get_transition(transition_ref)?;
+        let arc_ref = ArcRef::PlaceTransition(place_ref.clone(), transition_ref.clone());
+        match (
+            place.get_extended_postset().get(transition_ref),
+            transition.get_extended_preset().get(place_ref),
+        ) {
+            (None, None) => Err(Error::ArcNotFound(arc_ref)),
+            (Some(outgoing), Some(incoming)) if outgoing == incoming => Ok(*outgoing),
+            _ => Err(Error::InconsistentArc(arc_ref)),
+        }
+    }
+
+    /// Removes the arc from a place to a transition whose kind is not `ArcKind::Normal`.
+    ///
+    /// # Errors
+    ///
+    /// If the `PlaceRef` or the `TransitionRef` is invalid, then an error is returned.
+    /// If the arc does not exist, then an error is returned.
+    /// If the arc existed only on one side, then it is removed and an error is returned.
+    pub fn remove_extended_arc(
+        &mut self,
+        place_ref: &PlaceRef,
+        transition_ref: &TransitionRef,
+    ) -> Result<(), Error> {
+        let (place, transition) = self.get_place_transition_pair_mut(place_ref, transition_ref)?;
+        let removed_outgoing = place.remove_extended_outgoing(transition_ref);
+        let removed_incoming = transition.remove_extended_incoming(place_ref);
+        Self::check_arc_exists(
+            removed_outgoing,
+            removed_incoming,
+            ArcRef::PlaceTransition(place_ref.clone(), transition_ref.clone()),
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod extended_arcs_tests {
+    use super::*;
+    use crate::net_creator::*;
+
+    #[test]
+    fn net_add_inhibitor_arc_stores_the_kind_and_weight() {
+        let (mut net, place_refs, transition_refs) = create_basic_unconnected_net(1, 1);
+        let result = net.add_inhibitor_arc(&place_refs[0], &transition_refs[0], 2);
+
+        assert!(result.is_ok());
+        assert!(net.has_arcs_of_kind(ArcKind::Inhibitor));
+        assert!(!net.has_arcs_of_kind(ArcKind::Normal));
+        assert_eq!(
+            net.extended_arc(&place_refs[0], &transition_refs[0])
+                .unwrap(),
+            (ArcKind::Inhibitor, 2)
+        );
+        assert!(net.find_weighted_arcs_place_transition().is_empty());
+        assert_eq!(
+            net.find_extended_arcs(),
+            BTreeMap::from([(
+                (place_refs[0].clone(), transition_refs[0].clone()),
+                (ArcKind::Inhibitor, 2)
+            )])
+        );
+    }
+
+    #[test]
+    fn net_find_arcs_place_transition_reports_the_kind() {
+        let (mut net, place_refs, transition_refs) = create_basic_unconnected_net(4, 1);
+        let transition_ref = &transition_refs[0];
+        assert!(
+            net.add_arc_place_transition(&place_refs[0], transition_ref)
+                .is_ok()
+        );
+        assert!(
+            net.add_inhibitor_arc(&place_refs[1], transition_ref, 1)
+                .is_ok()
+        );
+        assert!(net.add_read_arc(&place_refs[2], transition_ref, 1).is_ok());
+        assert!(net.add_reset_arc(&place_refs[3], transition_ref).is_ok());
+
+        let kinds: Vec<ArcKind> = net.find_arcs_place_transition().into_values().collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ArcKind::Normal,
+                ArcKind::Inhibitor,
+                ArcKind::Read,
+                ArcKind::Reset
+            ]
+        );
+        assert_eq!(
+            net.arc_kind(&place_refs[2], transition_ref).unwrap(),
+            ArcKind::Read
+        );
+        assert_eq!(
+            net.arc_kind(&place_refs[0], transition_ref).unwrap(),
+            ArcKind::Normal
+        );
+    }
+
+    #[test]
+    fn net_add_arc_with_kind_normal_adds_an_ordinary_arc() {
+        let (mut net, place_refs, transition_refs) = create_basic_unconnected_net(1, 1);
+        let result = net.add_arc_with_kind(&place_refs[0], &transition_refs[0], ArcKind::Normal, 3);
+
+        assert!(result.is_ok());
+        assert_eq!(
+            net.arc_weight_place_transition(&place_refs[0], &transition_refs[0])
+                .unwrap(),
+            3
+        );
+        assert!(net.find_extended_arcs().is_empty());
+    }
+
+    #[test]
+    fn net_add_inhibitor_arc_returns_err_if_weight_is_zero() {
+        let (mut net, place_refs, transition_refs) = create_basic_unconnected_net(1, 1);
+        let result = net.add_inhibitor_arc(&place_refs[0], &transition_refs[0], 0);
+
+        assert!(matches!(result, Err(Error::InvalidArcWeight(_))));
+        assert!(!net.has_arcs_of_kind(ArcKind::Inhibitor));
+    }
+
+    #[test]
+    fn net_add_extended_arc_returns_err_if_duplicate() {
+        let (mut net, place_refs, transition_refs) = create_basic_unconnected_net(1, 1);
+        assert!(
+            net.add_inhibitor_arc(&place_refs[0], &transition_refs[0], 1)
+                .is_ok()
+        );
+        let result = net.add_read_arc(&place_refs[0], &transition_refs[0], 1);
+
+        assert!(matches!(result, Err(Error::DuplicateArc(_))));
+    }
+
+    #[test]
+    fn net_extended_and_ordinary_arc_between_same_nodes_are_rejected() {
+        let (mut net, place_refs, transition_refs) = create_basic_unconnected_net(2, 1);
+        assert!(
+            net.add_arc_place_transition(&place_refs[0], &transition_refs[0])
+                .is_ok()
+        );
+        let result = net.add_reset_arc(&place_refs[0], &transition_refs[0]);
+        assert!(matches!(result, Err(Error::DuplicateArc(_))));
+
+        assert!(
+            net.add_inhibitor_arc(&place_refs[1], &transition_refs[0], 1)
+                .is_ok()
+        );
+        let result = net.add_arc_place_transition(&place_refs[1], &transition_refs[0]);
+        assert!(matches!(result, Err(Error::DuplicateArc(_))));
+    }
+
+    #[test]
+    fn net_add_extended_arc_returns_err_if_refs_invalid() {
+        let (mut net, place_refs, transition_refs) = create_basic_unconnected_net(1, 1);
+        let place_ref = PlaceRef::new("Not in the net");
+        let transition_ref = TransitionRef::new("Not in the net");
+
+        let result = net.add_inhibitor_arc(&place_ref, &transition_refs[0], 1);
+        assert!(matches!(result, Err(Error::InvalidPlaceRef(_))));
+        let result = net.add_read_arc(&place_refs[0], &transition_ref, 1);
+        assert!(matches!(result, Err(Error::InvalidTransitionRef(_))));
+    }
+
+    #[test]
+    fn net_remove_extended_arc() {
+        let (mut net, place_refs, transition_refs) = create_basic_unconnected_net(1, 1);
+        assert!(
+            net.add_reset_arc(&place_refs[0], &transition_refs[0])
+                .is_ok()
+        );
+
+        assert!(
+            net.remove_extended_arc(&place_refs[0], &transition_refs[0])
+                .is_ok()
+        );
+        assert!(!net.has_arcs_of_kind(ArcKind::Reset));
+        let result = net.remove_extended_arc(&place_refs[0], &transition_refs[0]);
+        assert!(matches!(result, Err(Error::ArcNotFound(_))));
+        let result = net.arc_kind(&place_refs[0], &transition_refs[0]);
+        assert!(matches!(result, Err(Error::ArcNotFound(_))));
+    }
+
+    #[test]
+    fn net_remove_place_removes_its_extended_arcs() {
+        let (mut net, place_refs, transition_refs) = create_basic_unconnected_net(1, 1);
+        assert!(
+            net.add_inhibitor_arc(&place_refs[0], &transition_refs[0], 1)
+                .is_ok()
+        );
+
+        assert!(net.remove_place(&place_refs[0]).is_ok());
+        let (_, transition) = net.transitions_iter().next().unwrap();
+        assert!(transition.get_extended_preset().is_empty());
+    }
+
+    #[test]
+    fn net_remove_transition_removes_its_extended_arcs() {
+        let (mut net, place_refs, transition_refs) = create_basic_unconnected_net(1, 1);
+        assert!(
+            net.add_read_arc(&place_refs[0], &transition_refs[0], 1)
+                .is_ok()
+        );
+
+        assert!(net.remove_transition(&transition_refs[0]).is_ok());
+        assert!(net.find_extended_arcs().is_empty());
+    }
+
+    #[test]
+    fn net_place_with_only_an_extended_arc_is_connected() {
+        let (mut net, place_refs, transition_refs) = create_basic_unconnected_net(2, 1);
+        assert!(
+            net.add_inhibitor_arc(&place_refs[0], &transition_refs[0], 1)
+                .is_ok()
+        );
+
+        let unconnected = net.find_unconnected_places();
+        assert_eq!(unconnected, [place_refs[1].clone()].into());
+    }
+
+    #[test]
+    fn net_check_no_arc_kinds_reports_the_arc() {
+        let (mut net, place_refs, transition_refs) = create_basic_unconnected_net(1, 1);
+        assert!(
+            net.add_reset_arc(&place_refs[0], &transition_refs[0])
+                .is_ok()
+        );
+
+        assert!(net.check_no_arc_kinds(&[ArcKind::Inhibitor]).is_ok());
+        let result = net.check_no_arc_kinds(&[ArcKind::Inhibitor, ArcKind::Reset]);
+        assert!(matches!(
+            result,
+            Err(Error::UnsupportedArc(_, ArcKind::Reset))
+        ));
+    }
+}
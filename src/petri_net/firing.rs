@@ -0,0 +1,542 @@
+use crate::error::Error;
+use crate::petri_net::PetriNet;
+use crate::petri_net::arc_kind::ArcKind;
+use crate::petri_net::place::Place;
+use crate::petri_net::place_ref::PlaceRef;
+use crate::petri_net::transition::Transition;
+use crate::petri_net::transition_ref::TransitionRef;
+use std::collections::{BTreeMap, BTreeSet};
+
+impl PetriNet {
+    /// Finds all transitions that are enabled in the current marking.
+    /// Returns a `BTreeSet` with the transition references.
+    #[must_use]
+    pub fn enabled_transitions(&self) -> BTreeSet<TransitionRef> {
+        self.transitions
+            .iter()
+            .filter(|(_, transition)| self.is_transition_enabled(transition))
+            .map(|(transition_ref, _)| transition_ref.clone())
+            .collect()
+    }
+
+    /// Checks whether a transition is enabled in the current marking,
+    /// i.e. if every place in its preset holds at least as many tokens as the weight of the arc
+    /// and firing it does not exceed the capacity of any place in its postset.
+    ///
+    /// # Errors
+    ///
+    /// If the `TransitionRef` is invalid, then an error is returned.
+    pub fn is_enabled(&self, transition_ref: &TransitionRef) -> Result<bool, Error> {
+        let transition = self.get_transition(transition_ref)?;
+        Ok(self.is_transition_enabled(transition))
+    }
+
+    /// Fires a transition, consuming the tokens from the places in its preset
+    /// and producing tokens in the places of its postset.
+    /// Places connected by a reset arc are emptied before producing the tokens.
+    ///
+    /// Only the current marking changes. The initial marking of every place is fixed beforehand,
+    /// so adding or removing tokens after firing no longer changes it.
+    /// The operation is atomic: if it fails, the marking of the net is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// If the `TransitionRef` is invalid, then an error is returned.
+    /// If the transition is not enabled, then an error is returned.
+    /// If producing the tokens causes an overflow, then an error is returned.
+    pub fn fire(&mut self, transition_ref: &TransitionRef) -> Result<(), Error> {
+        let transition = self.get_transition(transition_ref)?;
+        let next_marking = self.next_marking(transition_ref, transition, |place_ref| {
+            self.places.get(place_ref).map_or(0, Place::marking)
+        })?;
+
+        // The marking before the first firing becomes the initial marking for good.
+        self.fix_initial_marking();
+        // Every place reference was checked above, the new values can be written safely.
+        for (place_ref, tokens) in next_marking {
+            if let Some(place) = self.places.get_mut(&place_ref) {
+                place.set_marking(tokens);
+            }
+        }
+        Ok(())
+    }
+
+    /// Finds all transitions that are enabled in the given marking.
+    /// Places missing from the marking are considered to hold no tokens.
+    /// Returns a `BTreeSet` with the transition references.
+    #[must_use]
+    pub fn enabled_transitions_at(
+        &self,
+        marking: &BTreeMap<PlaceRef, usize>,
+    ) -> BTreeSet<TransitionRef> {
+        self.transitions
+            .iter()
+            .filter(|(_, transition)| {
+                self.is_transition_enabled_at(transition, |place_ref| {
+                    marking.get(place_ref).copied().unwrap_or(0)
+                })
+            })
+            .map(|(transition_ref, _)| transition_ref.clone())
+            .collect()
+    }
+
+    /// Checks whether a transition is enabled in the given marking.
+    /// Places missing from the marking are considered to hold no tokens.
+    ///
+    /// # Errors
+    ///
+    /// If the `TransitionRef` is invalid, then an error is returned.
+    pub fn is_enabled_at(
+        &self,
+        transition_ref: &TransitionRef,
+        marking: &BTreeMap<PlaceRef, usize>,
+    ) -> Result<bool, Error> {
+        let transition = self.get_transition(transition_ref)?;
+        Ok(self.is_transition_enabled_at(transition, |place_ref| {
+            marking.get(place_ref).copied().unwrap_or(0)
+        }))
+    }
+
+    /// Fires a transition in the given marking without modifying the net.
+    /// Places missing from the marking are considered to hold no tokens.
+    /// Returns the marking reached after firing the transition.
+    ///
+    /// # Errors
+    ///
+    /// If the `TransitionRef` is invalid, then an error is returned.
+    /// If the transition is not enabled in the marking, then an error is returned.
+    /// If producing the tokens causes an overflow, then an error is returned.
+    pub fn fire_at(
+        &self,
+        transition_ref: &TransitionRef,
+        marking: &BTreeMap<PlaceRef, usize>,
+    ) -> Result<BTreeMap<PlaceRef, usize>, Error> {
+        let transition = self.get_transition(transition_ref)?;
+        let changes = self.next_marking(transition_ref, transition, |place_ref| {
+            marking.get(place_ref).copied().unwrap_or(0)
+        })?;
+        let mut next_marking = marking.clone();
+        next_marking.extend(changes);
+        Ok(next_marking)
+    }
+
+    fn is_transition_enabled(&self, transition: &Transition) -> bool {
+        self.is_transition_enabled_at(transition, |place_ref| {
+            self.places.get(place_ref).map_or(0, Place::marking)
+        })
+    }
+
+    /// Checks whether the transition is enabled.
+    /// The current number of tokens of a place is obtained through `marking`.
+    ///
+    /// Besides having enough tokens in its preset, the transition must satisfy
+    /// the conditions of its inhibitor and read arcs and may not
+    /// exceed the capacity of any place in its postset.
+    fn is_transition_enabled_at<F>(&self, transition: &Transition, marking: F) -> bool
+    where
+        F: Fn(&PlaceRef) -> usize,
+    {
+        let has_tokens = transition
+            .get_preset()
+            .iter()
+            .all(|(place_ref, weight)| marking(place_ref) >= *weight);
+
+        has_tokens
+            && Self::satisfies_extended_arcs(transition, &marking)
+            && transition.get_postset().iter().all(|(place_ref, weight)| {
+                let Some(place) = self.places.get(place_ref) else {
+                    return true;
+                };
+                if place.capacity().is_none() {
+                    return true;
+                }
+                let is_reset = matches!(
+                    transition.get_extended_preset().get(place_ref),
+                    Some((ArcKind::Reset, _))
+                );
+                let consumed = transition.get_preset().get(place_ref).copied().unwrap_or(0);
+                let remaining = if is_reset {
+                    0
+                } else {
+                    marking(place_ref) - consumed
+                };
+                remaining
+                    .checked_add(*weight)
+                    .is_some_and(|tokens| place.can_hold(tokens))
+            })
+    }
+
+    /// Checks the conditions of the arcs in the extended preset of the transition:
+    /// every place with an inhibitor arc holds fewer tokens than the weight of the arc
+    /// and every place with a read arc holds at least as many tokens as the weight of the arc.
+    /// Reset arcs impose no condition.
+    fn satisfies_extended_arcs<F>(transition: &Transition, marking: &F) -> bool
+    where
+        F: Fn(&PlaceRef) -> usize,
+    {
+        transition
+            .get_extended_preset()
+            .iter()
+            .all(|(place_ref, (kind, weight))| match kind {
+                ArcKind::Inhibitor => marking(place_ref) < *weight,
+                ArcKind::Read => marking(place_ref) >= *weight,
+                ArcKind::Normal | ArcKind::Reset => true,
+            })
+    }
+
+    /// Computes the number of tokens after firing the transition
+    /// for every place connected to it, without modifying the net.
+    /// The current number of tokens of a place is obtained through `marking`.
+    ///
+    /// Tokens are consumed first, then the places with a reset arc are emptied
+    /// and finally the tokens are produced.
+    fn next_marking<F>(
+        &self,
+        transition_ref: &TransitionRef,
+        transition: &Transition,
+        marking: F,
+    ) -> Result<BTreeMap<PlaceRef, usize>, Error>
+    where
+        F: Fn(&PlaceRef) -> usize,
+    {
+        if !Self::satisfies_extended_arcs(transition, &marking) {
+            return Err(Error::TransitionNotEnabled(transition_ref.clone()));
+        }
+        let mut next_marking: BTreeMap<PlaceRef, usize> = BTreeMap::new();
+        for (place_ref, weight) in transition.get_preset() {
+            let Some(tokens) = marking(place_ref).checked_sub(*weight) else {
+                return Err(Error::TransitionNotEnabled(transition_ref.clone()));
+            };
+            next_marking.insert(place_ref.clone(), tokens);
+        }
+        for (place_ref, (kind, _)) in transition.get_extended_preset() {
+            if *kind == ArcKind::Reset {
+                next_marking.insert(place_ref.clone(), 0);
+            }
+        }
+        for (place_ref, weight) in transition.get_postset() {
+            let tokens = next_marking
+                .get(place_ref)
+                .copied()
+                .unwrap_or_else(|| marking(place_ref));
+            let Some(tokens) = tokens.checked_add(*weight) else {
+                return Err(Error::TokenOverflow(place_ref.clone()));
+            };
+            if self
+                .places
+                .get(place_ref)
+                .is_some_and(|place| !place.can_hold(tokens))
+            {
+                return Err(Error::TransitionNotEnabled(transition_ref.clone()));
+            }
+            next_marking.insert(place_ref.clone(), tokens);
+        }
+        Ok(next_marking)
+    }
+}
+
+#[cfg(test)]
+mod firing_tests {
+    use super::*;
+    use crate::net_creator::*;
+
+    #[test]
+    fn net_enabled_transitions_empty_net() {
+        let net = PetriNet::new();
+
+        assert!(net.enabled_transitions().is_empty());
+    }
+
+    #[test]
+    fn net_enabled_transitions_transition_without_preset_is_enabled() {
+        let (net, _, transition_refs) = create_basic_unconnected_net(0, 3);
+        let enabled = net.enabled_transitions();
+
+        assert_eq!(enabled.len(), 3);
+        for transition_ref in &transition_refs {
+            assert!(enabled.contains(transition_ref));
+        }
+    }
+
+    #[test]
+    fn net_enabled_transitions_chain_topology() {
+        let (mut net, place_refs, transition_refs) = create_net_chain_topology(3);
+        assert!(net.enabled_transitions().is_empty());
+
+        assert!(net.add_token(&place_refs[1], 1).is_ok());
+        let enabled = net.enabled_transitions();
+        assert_eq!(enabled.len(), 1);
+        assert!(enabled.contains(&transition_refs[1]));
+    }
+
+    #[test]
+    fn net_is_enabled_respects_arc_weight() {
+        let mut net = PetriNet::new();
+        let place_ref = net.add_place("P1");
+        let transition_ref = net.add_transition("T1");
+        let result = net.add_weighted_arc_place_transition(&place_ref, &transition_ref, 3);
+        assert!(result.is_ok());
+
+        assert!(net.add_token(&place_ref, 2).is_ok());
+        assert!(!net.is_enabled(&transition_ref).unwrap());
+
+        assert!(net.add_token(&place_ref, 1).is_ok());
+        assert!(net.is_enabled(&transition_ref).unwrap());
+    }
+
+    #[test]
+    fn net_is_enabled_returns_err_if_transition_ref_invalid() {
+        let net = PetriNet::new();
+        let transition_ref = TransitionRef::new("Not in the net");
+
+        assert!(net.is_enabled(&transition_ref).is_err());
+    }
+
+    #[test]
+    fn net_fire_moves_tokens_along_the_chain() {
+        let (mut net, place_refs, transition_refs) = create_net_chain_topology(3);
+        assert!(net.add_token(&place_refs[0], 1).is_ok());
+
+        assert!(net.fire(&transition_refs[0]).is_ok());
+        assert_eq!(net.marking(&place_refs[0]).unwrap(), 0);
+        assert_eq!(net.marking(&place_refs[1]).unwrap(), 1);
+
+        assert!(net.fire(&transition_refs[1]).is_ok());
+        assert_eq!(net.marking(&place_refs[1]).unwrap(), 0);
+        assert_eq!(net.marking(&place_refs[2]).unwrap(), 1);
+    }
+
+    #[test]
+    fn net_fire_uses_arc_weights() {
+        let mut net = PetriNet::new();
+        let place_1 = net.add_place("P1");
+        let place_2 = net.add_place("P2");
+        let transition_ref = net.add_transition("T1");
+        let result = net.add_weighted_arc_place_transition(&place_1, &transition_ref, 2);
+        assert!(result.is_ok());
+        let result = net.add_weighted_arc_transition_place(&transition_ref, &place_2, 5);
+        assert!(result.is_ok());
+        assert!(net.add_token(&place_1, 3).is_ok());
+
+        assert!(net.fire(&transition_ref).is_ok());
+        assert_eq!(net.marking(&place_1).unwrap(), 1);
+        assert_eq!(net.marking(&place_2).unwrap(), 5);
+    }
+
+    #[test]
+    fn net_fire_self_loop_keeps_the_marking() {
+        let (mut net, place_ref, transition_ref) = create_net_loop_topology();
+        assert!(net.add_token(&place_ref, 1).is_ok());
+
+        assert!(net.fire(&transition_ref).is_ok());
+        assert_eq!(net.marking(&place_ref).unwrap(), 1);
+    }
+
+    #[test]
+    fn net_fire_returns_err_if_not_enabled() {
+        let (mut net, place_refs, transition_refs) = create_net_chain_topology(2);
+
+        let result = net.fire(&transition_refs[0]);
+        assert!(matches!(result, Err(Error::TransitionNotEnabled(_))));
+        assert_eq!(net.marking(&place_refs[0]).unwrap(), 0);
+        assert_eq!(net.marking(&place_refs[1]).unwrap(), 0);
+    }
+
+    #[test]
+    fn net_fire_leaves_marking_unchanged_on_overflow() {
+        let mut net = PetriNet::new();
+        let place_1 = net.add_place("P1");
+        let place_2 = net.add_place("P2");
+        let transition_ref = net.add_transition("T1");
+        assert!(
+            net.add_arc_place_transition(&place_1, &transition_ref)
+                .is_ok()
+        );
+        assert!(
+            net.add_arc_transition_place(&transition_ref, &place_2)
+                .is_ok()
+        );
+        assert!(net.add_token(&place_1, 1).is_ok());
+        assert!(net.add_token(&place_2, usize::MAX).is_ok());
+
+        let result = net.fire(&transition_ref);
+        assert!(matches!(result, Err(Error::TokenOverflow(place_ref)) if place_ref == place_2));
+        assert_eq!(net.marking(&place_1).unwrap(), 1);
+        assert_eq!(net.marking(&place_2).unwrap(), usize::MAX);
+    }
+
+    #[test]
+    fn net_fire_at_does_not_modify_the_net() {
+        let (net, place_refs, transition_refs) = create_net_chain_topology(2);
+        let mut marking = net.marking_vector();
+        marking.insert(place_refs[0].clone(), 1);
+
+        let result = net.fire_at(&transition_refs[0], &marking);
+        assert!(result.is_ok());
+        let next_marking = result.unwrap();
+        assert_eq!(next_marking.get(&place_refs[0]), Some(&0));
+        assert_eq!(next_marking.get(&place_refs[1]), Some(&1));
+        assert_eq!(net.marking(&place_refs[0]).unwrap(), 0);
+    }
+
+    #[test]
+    fn net_fire_at_returns_err_if_not_enabled_in_marking() {
+        let (mut net, place_refs, transition_refs) = create_net_chain_topology(2);
+        assert!(net.add_token(&place_refs[0], 1).is_ok());
+        let marking = BTreeMap::new();
+
+        assert!(!net.is_enabled_at(&transition_refs[0], &marking).unwrap());
+        assert!(net.fire_at(&transition_refs[0], &marking).is_err());
+    }
+
+    #[test]
+    fn net_enabled_transitions_at_uses_the_given_marking() {
+        let (net, place_refs, transition_refs) = create_net_chain_topology(3);
+        let mut marking = net.marking_vector();
+        marking.insert(place_refs[1].clone(), 1);
+
+        let enabled = net.enabled_transitions_at(&marking);
+        assert_eq!(enabled.len(), 1);
+        assert!(enabled.contains(&transition_refs[1]));
+        assert!(net.enabled_transitions().is_empty());
+    }
+
+    #[test]
+    fn net_capacity_of_output_place_disables_transition() {
+        let (mut net, place_refs, transition_refs) = create_net_chain_topology(2);
+        assert!(net.set_capacity(&place_refs[1], Some(1)).is_ok());
+        assert!(net.add_token(&place_refs[0], 2).is_ok());
+
+        assert!(net.is_enabled(&transition_refs[0]).unwrap());
+        assert!(net.fire(&transition_refs[0]).is_ok());
+        assert!(!net.is_enabled(&transition_refs[0]).unwrap());
+        assert!(net.enabled_transitions().is_empty());
+
+        let result = net.fire(&transition_refs[0]);
+        assert!(matches!(result, Err(Error::TransitionNotEnabled(_))));
+        assert_eq!(net.marking(&place_refs[0]).unwrap(), 1);
+        assert_eq!(net.marking(&place_refs[1]).unwrap(), 1);
+    }
+
+    #[test]
+    fn net_capacity_self_loop_at_capacity_stays_enabled() {
+        let (mut net, place_ref, transition_ref) = create_net_loop_topology();
+        assert!(net.add_token(&place_ref, 1).is_ok());
+        assert!(net.set_capacity(&place_ref, Some(1)).is_ok());
+
+        assert!(net.is_enabled(&transition_ref).unwrap());
+        assert!(net.fire(&transition_ref).is_ok());
+        assert_eq!(net.marking(&place_ref).unwrap(), 1);
+    }
+
+    #[test]
+    fn net_fire_at_respects_capacity() {
+        let (mut net, place_refs, transition_refs) = create_net_chain_topology(3);
+        assert!(net.set_capacity(&place_refs[1], Some(1)).is_ok());
+        let marking = BTreeMap::from([(place_refs[0].clone(), 1), (place_refs[1].clone(), 1)]);
+
+        assert!(!net.is_enabled_at(&transition_refs[0], &marking).unwrap());
+        assert!(
+            net.enabled_transitions_at(&marking)
+                .contains(&transition_refs[1])
+        );
+        assert!(net.fire_at(&transition_refs[0], &marking).is_err());
+    }
+
+    #[test]
+    fn net_inhibitor_arc_disables_transition_while_place_is_marked() {
+        let (mut net, place_refs, transition_refs) = create_net_chain_topology(2);
+        let inhibitor = net.add_place("Inhibitor");
+        assert!(
+            net.add_inhibitor_arc(&inhibitor, &transition_refs[0], 1)
+                .is_ok()
+        );
+        assert!(net.add_token(&place_refs[0], 1).is_ok());
+        assert!(net.is_enabled(&transition_refs[0]).unwrap());
+
+        assert!(net.add_token(&inhibitor, 1).is_ok());
+        assert!(!net.is_enabled(&transition_refs[0]).unwrap());
+        assert!(net.fire(&transition_refs[0]).is_err());
+        assert_eq!(net.marking(&place_refs[0]).unwrap(), 1);
+    }
+
+    #[test]
+    fn net_inhibitor_arc_weight_is_the_threshold() {
+        let mut net = PetriNet::new();
+        let place_ref = net.add_place("P1");
+        let transition_ref = net.add_transition("T1");
+        assert!(
+            net.add_inhibitor_arc(&place_ref, &transition_ref, 3)
+                .is_ok()
+        );
+        assert!(net.add_token(&place_ref, 2).is_ok());
+
+        assert!(net.is_enabled(&transition_ref).unwrap());
+        assert!(net.fire(&transition_ref).is_ok());
+        assert_eq!(net.marking(&place_ref).unwrap(), 2);
+
+        let marking = BTreeMap::from([(place_ref, 3)]);
+        assert!(!net.is_enabled_at(&transition_ref, &marking).unwrap());
+        assert!(net.enabled_transitions_at(&marking).is_empty());
+        assert!(net.fire_at(&transition_ref, &marking).is_err());
+    }
+
+    #[test]
+    fn net_read_arc_requires_tokens_without_consuming_them() {
+        let mut net = PetriNet::new();
+        let condition = net.add_place("Condition");
+        let output = net.add_place("Output");
+        let transition_ref = net.add_transition("T1");
+        assert!(net.add_read_arc(&condition, &transition_ref, 2).is_ok());
+        assert!(
+            net.add_arc_transition_place(&transition_ref, &output)
+                .is_ok()
+        );
+        assert!(net.add_token(&condition, 1).is_ok());
+        assert!(!net.is_enabled(&transition_ref).unwrap());
+        assert!(net.fire(&transition_ref).is_err());
+
+        assert!(net.add_token(&condition, 1).is_ok());
+        assert!(net.fire(&transition_ref).is_ok());
+        assert!(net.fire(&transition_ref).is_ok());
+        assert_eq!(net.marking(&condition).unwrap(), 2);
+        assert_eq!(net.marking(&output).unwrap(), 2);
+    }
+
+    #[test]
+    fn net_reset_arc_empties_the_place() {
+        let mut net = PetriNet::new();
+        let channel = net.add_place("Channel");
+        let closed = net.add_place("Closed");
+        let transition_ref = net.add_transition("Close");
+        assert!(net.add_reset_arc(&channel, &transition_ref).is_ok());
+        assert!(
+            net.add_arc_transition_place(&transition_ref, &closed)
+                .is_ok()
+        );
+
+        assert!(net.is_enabled(&transition_ref).unwrap());
+        assert!(net.add_token(&channel, 5).is_ok());
+        assert!(net.fire(&transition_ref).is_ok());
+        assert_eq!(net.marking(&channel).unwrap(), 0);
+        assert_eq!(net.marking(&closed).unwrap(), 1);
+    }
+
+    #[test]
+    fn net_reset_arc_empties_the_place_before_producing() {
+        let mut net = PetriNet::new();
+        let place_ref = net.add_place("P1");
+        let transition_ref = net.add_transition("T1");
+        assert!(net.add_reset_arc(&place_ref, &transition_ref).is_ok());
+        assert!(
+            net.add_weighted_arc_transition_place(&transition_ref, &place_ref, 2)
+                .is_ok()
+        );
+        assert!(net.add_token(&place_ref, 3).is_ok());
+        assert!(net.set_capacity(&place_ref, Some(3)).is_ok());
+
+        assert!(net.is_enabled(&transition_ref).unwrap());
+        let marking = net.fire_at(&transition_ref, &net.marking_vector()).unwrap();
+        assert_eq!(marking.get(&place_ref), Some(&2));
+    }
+}
@@ -0,0 +1,104 @@
+/// The static firing interval `[earliest, latest]` of a transition in a time Petri net.
+///
+/// Times are measured in discrete time units from the moment the transition became enabled.
+/// The transition may fire once it has been enabled for `earliest` units
+/// and must fire or be disabled before it has been enabled for more than `latest` units.
+/// An interval without `latest` never forces the transition to fire.
+///
+/// The default interval `[0, ∞)` is the behavior of an untimed transition.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FiringInterval {
+    earliest: u64,
+    latest: Option<u64>,
+}
+
+impl FiringInterval {
+    /// Creates the interval `[earliest, latest]`.
+    /// Returns `None` if `earliest` is greater than `latest`.
+    #[must_use]
+    pub const fn new(earliest: u64, latest: u64) -> Option<Self> {
+        if earliest > latest {
+            return None;
+        }
+        Some(Self {
+            earliest,
+            latest: Some(latest),
+        })
+    }
+
+    /// Creates the interval `[earliest, ∞)`, without an upper bound.
+    #[must_use]
+    pub const fn unbounded(earliest: u64) -> Self {
+        Self {
+            earliest,
+            latest: None,
+        }
+    }
+
+    /// Gets the earliest firing time of the interval.
+    #[must_use]
+    pub const fn earliest(&self) -> u64 {
+        self.earliest
+    }
+
+    /// Gets the latest firing time of the interval, `None` if it has no upper bound.
+    #[must_use]
+    pub const fn latest(&self) -> Option<u64> {
+        self.latest
+    }
+
+    /// Checks whether the time lies inside the interval.
+    #[must_use]
+    pub const fn contains(&self, time: u64) -> bool {
+        match self.latest {
+            Some(latest) => self.earliest <= time && time <= latest,
+            None => self.earliest <= time,
+        }
+    }
+}
+
+impl std::fmt::Display for FiringInterval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.latest {
+            Some(latest) => write!(f, "[{}, {latest}]", self.earliest),
+            None => write!(f, "[{}, ∞)", self.earliest),
+        }
+    }
+}
+
+#[cfg(test)]
+mod firing_interval_tests {
+    use super::*;
+
+    #[test]
+    fn firing_interval_new_rejects_empty_interval() {
+        assert!(FiringInterval::new(3, 2).is_none());
+        assert!(FiringInterval::new(2, 2).is_some());
+    }
+
+    #[test]
+    fn firing_interval_contains_its_bounds() {
+        let interval = FiringInterval::new(2, 5).unwrap();
+
+        assert!(!interval.contains(1));
+        assert!(interval.contains(2));
+        assert!(interval.contains(5));
+        assert!(!interval.contains(6));
+        assert!(FiringInterval::unbounded(2).contains(u64::MAX));
+    }
+
+    #[test]
+    fn firing_interval_default_is_untimed() {
+        let interval = FiringInterval::default();
+
+        assert_eq!(interval.earliest(), 0);
+        assert_eq!(interval.latest(), None);
+    }
+
+    #[test]
+    fn firing_interval_display_trait() {
+        assert_eq!(format!("{}", FiringInterval::new(1, 4).unwrap()), "[1, 4]");
+        assert_eq!(format!("{}", FiringInterval::unbounded(3)), "[3, ∞)");
+    }
+}
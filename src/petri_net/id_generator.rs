@@ -0,0 +1,71 @@
+use uuid::Uuid;
+
+/// The strategy used by a net to generate the UUIDs of new places and transitions.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IdGenerator {
+    /// Random UUIDs (version 4), unique across nets.
+    #[default]
+    Random,
+    /// UUIDs built from a seed and a counter incremented on every new node.
+    /// Building the same net twice with the same seed yields the same references.
+    Sequential { seed: u64, counter: u64 },
+}
+
+impl IdGenerator {
+    /// Creates a generator of sequential UUIDs starting from the given seed.
+    pub const fn sequential(seed: u64) -> Self {
+        Self::Sequential { seed, counter: 0 }
+    }
+
+    /// Returns the UUID for the next node added to the net.
+    pub fn next_uuid(&mut self) -> Uuid {
+        match self {
+            Self::Random => Uuid::new_v4(),
+            Self::Sequential { seed, counter } => {
+                *counter = counter.wrapping_add(1);
+                Uuid::from_u128((u128::from(*seed) << 64) | u128::from(*counter))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod id_generator_tests {
+    use super::*;
+
+    #[test]
+    fn id_generator_random_returns_different_uuids() {
+        let mut generator = IdGenerator::Random;
+
+        assert_ne!(generator.next_uuid(), generator.next_uuid());
+    }
+
+    #[test]
+    fn id_generator_sequential_is_reproducible() {
+        let mut first = IdGenerator::sequential(7);
+        let mut second = IdGenerator::sequential(7);
+
+        for _ in 0..10 {
+            assert_eq!(first.next_uuid(), second.next_uuid());
+        }
+    }
+
+    #[test]
+    fn id_generator_sequential_uuids_increase() {
+        let mut generator = IdGenerator::sequential(0);
+        let uuid_1 = generator.next_uuid();
+        let uuid_2 = generator.next_uuid();
+
+        assert_eq!(uuid_1, Uuid::from_u128(1));
+        assert!(uuid_1 < uuid_2);
+    }
+
+    #[test]
+    fn id_generator_sequential_seeds_do_not_overlap() {
+        let mut first = IdGenerator::sequential(1);
+        let mut second = IdGenerator::sequential(2);
+
+        assert_ne!(first.next_uuid(), second.next_uuid());
+    }
+}
@@ -1,11 +1,67 @@
+use crate::error::Error;
+use crate::petri_net::arc_kind::ArcKind;
+use crate::petri_net::place_ref::PlaceRef;
 use crate::petri_net::transition_ref::TransitionRef;
-use std::collections::BTreeSet;
+use std::collections::BTreeMap;
+
+/// The reason why the tokens of a single place could not be changed.
+///
+/// `PetriNet::add_token` and `PetriNet::remove_token` convert it into an `Error`
+/// that carries the reference to the place.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenError {
+    /// Adding the tokens would overflow the marking.
+    Overflow,
+    /// Adding the tokens would exceed the capacity of the place.
+    CapacityExceeded,
+    /// The place does not have as many tokens as requested.
+    InsufficientTokens,
+}
+
+impl TokenError {
+    /// Converts the error into an `Error` for the place with the given reference.
+    pub(crate) fn for_place(self, place_ref: &PlaceRef) -> Error {
+        let place_ref = place_ref.clone();
+        match self {
+            Self::Overflow => Error::TokenOverflow(place_ref),
+            Self::CapacityExceeded => Error::CapacityExceeded(place_ref),
+            Self::InsufficientTokens => Error::InsufficientTokens(place_ref),
+        }
+    }
+}
 
-#[derive(Default)]
+impl std::fmt::Display for TokenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Overflow => write!(f, "Overflow when adding tokens to the place."),
+            Self::CapacityExceeded => {
+                write!(
+                    f,
+                    "Adding the tokens would exceed the capacity of the place."
+                )
+            }
+            Self::InsufficientTokens => {
+                write!(f, "Cannot remove more tokens than available at the place.")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TokenError {}
+
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Place {
     marking: usize,
-    preset: BTreeSet<TransitionRef>,
-    postset: BTreeSet<TransitionRef>,
+    /// `None` while the initial marking follows the current marking.
+    #[cfg_attr(feature = "serde", serde(default))]
+    initial_marking: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    capacity: Option<usize>,
+    preset: BTreeMap<TransitionRef, usize>,
+    postset: BTreeMap<TransitionRef, usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    extended_postset: BTreeMap<TransitionRef, (ArcKind, usize)>,
 }
 
 impl Place {
@@ -29,79 +85,220 @@ impl Place {
         self.marking
     }
 
+    /// Gets the number of tokens at the given place in the initial marking.
+    ///
+    /// The initial marking follows the current marking until it is fixed,
+    /// either by setting it explicitly or by changing the marking with `set_marking`.
+    #[inline]
+    #[must_use]
+    pub const fn initial_marking(&self) -> usize {
+        match self.initial_marking {
+            Some(initial_marking) => initial_marking,
+            None => self.marking,
+        }
+    }
+
+    /// Sets the number of tokens at the place in the initial marking and fixes it,
+    /// so that adding or removing tokens afterwards only changes the current marking.
+    /// The current marking is left unchanged.
+    /// Returns `false` if the number exceeds the capacity, the initial marking is then left unchanged.
+    pub const fn set_initial_marking(&mut self, initial_marking: usize) -> bool {
+        if !self.can_hold(initial_marking) {
+            return false;
+        }
+        self.initial_marking = Some(initial_marking);
+        true
+    }
+
+    /// Gets the initial marking if it is fixed.
+    /// Returns `None` while the initial marking follows the current marking.
+    #[cfg(feature = "serde")]
+    pub(crate) const fn fixed_initial_marking(&self) -> Option<usize> {
+        self.initial_marking
+    }
+
+    /// Fixes the initial marking at the current marking, if it is not fixed yet.
+    pub(crate) const fn fix_initial_marking(&mut self) {
+        if self.initial_marking.is_none() {
+            self.initial_marking = Some(self.marking);
+        }
+    }
+
+    /// Gets the maximum number of tokens that the place can hold.
+    /// Returns `None` if the place is unbounded.
+    #[inline]
+    #[must_use]
+    pub const fn capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+
+    /// Sets the maximum number of tokens that the place can hold, `None` removes the limit.
+    /// Returns `false` if the current or the initial marking exceeds the new capacity,
+    /// the capacity is then left unchanged.
+    pub const fn set_capacity(&mut self, capacity: Option<usize>) -> bool {
+        if let Some(capacity) = capacity
+            && (self.marking > capacity || self.initial_marking() > capacity)
+        {
+            return false;
+        }
+        self.capacity = capacity;
+        true
+    }
+
+    /// Checks whether the place can hold the given number of tokens.
+    #[inline]
+    #[must_use]
+    pub const fn can_hold(&self, tokens: usize) -> bool {
+        match self.capacity {
+            Some(capacity) => tokens <= capacity,
+            None => true,
+        }
+    }
+
     /// Adds `tokens_to_add` tokens to the place.
+    /// The initial marking follows the change unless it is fixed.
+    /// The markings are left unchanged if an error is returned.
     ///
     /// # Errors
     ///
     /// If the addition causes an overflow, then an error is returned.
-    pub const fn add_token(&mut self, tokens_to_add: usize) -> Result<(), &str> {
-        self.marking = match self.marking.checked_add(tokens_to_add) {
-            Some(value) => value,
-            None => return Err("Overflow when adding tokens to this place"),
+    /// If the addition exceeds the capacity, then an error is returned.
+    pub const fn add_token(&mut self, tokens_to_add: usize) -> Result<(), TokenError> {
+        let Some(value) = self.marking.checked_add(tokens_to_add) else {
+            return Err(TokenError::Overflow);
         };
+        if !self.can_hold(value) {
+            return Err(TokenError::CapacityExceeded);
+        }
+        self.marking = value;
         Ok(())
     }
 
     /// Removes `tokens_to_remove` tokens from the place.
+    /// The initial marking follows the change unless it is fixed.
+    /// The markings are left unchanged if an error is returned.
     ///
     /// # Errors
     ///
-    /// If the subtraction causes an overflow, then an error is returned.
-    pub const fn remove_token(&mut self, tokens_to_remove: usize) -> Result<(), &str> {
-        if self.marking() < tokens_to_remove {
-            return Err("Cannot remove more tokens than available at this place");
-        }
-        self.marking = match self.marking.checked_sub(tokens_to_remove) {
-            Some(value) => value,
-            None => return Err("Overflow when removing tokens from this place"),
+    /// If there are not enough tokens, then an error is returned.
+    pub const fn remove_token(&mut self, tokens_to_remove: usize) -> Result<(), TokenError> {
+        let Some(value) = self.marking.checked_sub(tokens_to_remove) else {
+            return Err(TokenError::InsufficientTokens);
         };
+        self.marking = value;
         Ok(())
     }
 
-    /// Gets an immutable reference to the set of transitions
-    /// whose edges point to this place.
+    /// Sets the number of tokens at the place, overwriting the previous marking.
+    /// The initial marking is fixed before, so it keeps the previous marking if it was not fixed yet.
+    pub(crate) const fn set_marking(&mut self, marking: usize) {
+        self.fix_initial_marking();
+        self.marking = marking;
+    }
+
+    /// Gets an immutable reference to the map of transitions
+    /// whose edges point to this place and the weight of each edge.
     #[must_use]
-    pub const fn get_preset(&self) -> &BTreeSet<TransitionRef> {
+    pub const fn get_preset(&self) -> &BTreeMap<TransitionRef, usize> {
         &self.preset
     }
 
-    /// Gets a mutable reference to the set of transitions
-    /// whose edges point to this place.
-    pub const fn get_preset_mut(&mut self) -> &mut BTreeSet<TransitionRef> {
+    /// Gets a mutable reference to the map of transitions
+    /// whose edges point to this place and the weight of each edge.
+    pub const fn get_preset_mut(&mut self) -> &mut BTreeMap<TransitionRef, usize> {
         &mut self.preset
     }
 
-    /// Gets an immutable reference to the set of transitions
-    /// to which edges from this place point to.
+    /// Gets an immutable reference to the map of transitions
+    /// to which edges from this place point to and the weight of each edge.
     #[must_use]
-    pub const fn get_postset(&self) -> &BTreeSet<TransitionRef> {
+    pub const fn get_postset(&self) -> &BTreeMap<TransitionRef, usize> {
         &self.postset
     }
 
-    /// Gets a mutable reference to the set of transitions
-    /// to which edges from this place point to.
-    pub const fn get_postset_mut(&mut self) -> &mut BTreeSet<TransitionRef> {
+    /// Gets a mutable reference to the map of transitions
+    /// to which edges from this place point to and the weight of each edge.
+    pub const fn get_postset_mut(&mut self) -> &mut BTreeMap<TransitionRef, usize> {
         &mut self.postset
     }
 
-    /// Adds an incoming `Transition`, update the preset accordingly.
-    pub fn add_incoming(&mut self, reference: TransitionRef) -> bool {
-        self.preset.insert(reference)
+    /// Gets an immutable reference to the map of transitions
+    /// to which arcs of a kind other than `ArcKind::Normal` from this place point to,
+    /// together with the kind and the weight of each arc.
+    #[must_use]
+    pub const fn get_extended_postset(&self) -> &BTreeMap<TransitionRef, (ArcKind, usize)> {
+        &self.extended_postset
+    }
+
+    /// Adds an incoming `Transition` with the given arc weight, update the preset accordingly.
+    /// Returns `false` if the transition was already in the preset, the weight is then left unchanged.
+    pub fn add_incoming(&mut self, reference: TransitionRef, weight: usize) -> bool {
+        if self.preset.contains_key(&reference) {
+            return false;
+        }
+        self.preset.insert(reference, weight);
+        true
     }
 
     /// Removes an incoming `Transition`, update the preset accordingly.
     pub fn remove_incoming(&mut self, reference: &TransitionRef) -> bool {
-        self.preset.remove(reference)
+        self.preset.remove(reference).is_some()
+    }
+
+    /// Updates the weight of the arc from an incoming `Transition`.
+    /// Returns `false` if the transition is not in the preset.
+    pub fn update_incoming(&mut self, reference: &TransitionRef, weight: usize) -> bool {
+        let Some(value) = self.preset.get_mut(reference) else {
+            return false;
+        };
+        *value = weight;
+        true
+    }
+
+    /// Adds an outgoing `Transition` with an arc of the given kind and weight,
+    /// update the extended postset accordingly.
+    /// Returns `false` if the transition was already in the extended postset, the arc is then left unchanged.
+    pub fn add_extended_outgoing(
+        &mut self,
+        reference: TransitionRef,
+        kind: ArcKind,
+        weight: usize,
+    ) -> bool {
+        if self.extended_postset.contains_key(&reference) {
+            return false;
+        }
+        self.extended_postset.insert(reference, (kind, weight));
+        true
     }
 
-    /// Adds an outgoing `Transition`, update the postset accordingly.
-    pub fn add_outgoing(&mut self, reference: TransitionRef) -> bool {
-        self.postset.insert(reference)
+    /// Removes an outgoing `Transition` from the extended postset.
+    pub fn remove_extended_outgoing(&mut self, reference: &TransitionRef) -> bool {
+        self.extended_postset.remove(reference).is_some()
+    }
+
+    /// Adds an outgoing `Transition` with the given arc weight, update the postset accordingly.
+    /// Returns `false` if the transition was already in the postset, the weight is then left unchanged.
+    pub fn add_outgoing(&mut self, reference: TransitionRef, weight: usize) -> bool {
+        if self.postset.contains_key(&reference) {
+            return false;
+        }
+        self.postset.insert(reference, weight);
+        true
     }
 
     /// Removes an outgoing `Transition`, update the postset accordingly.
     pub fn remove_outgoing(&mut self, reference: &TransitionRef) -> bool {
-        self.postset.remove(reference)
+        self.postset.remove(reference).is_some()
+    }
+
+    /// Updates the weight of the arc to an outgoing `Transition`.
+    /// Returns `false` if the transition is not in the postset.
+    pub fn update_outgoing(&mut self, reference: &TransitionRef, weight: usize) -> bool {
+        let Some(value) = self.postset.get_mut(reference) else {
+            return false;
+        };
+        *value = weight;
+        true
     }
 }
 
@@ -109,6 +306,22 @@ impl Place {
 mod place_tests {
     use super::*;
 
+    #[test]
+    fn place_add_extended_outgoing_returns_false_if_already_present() {
+        let mut place = Place::new();
+        let reference = TransitionRef::new("Example transition");
+
+        assert!(place.add_extended_outgoing(reference.clone(), ArcKind::Inhibitor, 1));
+        assert!(!place.add_extended_outgoing(reference.clone(), ArcKind::Read, 2));
+        assert_eq!(
+            place.get_extended_postset().get(&reference),
+            Some(&(ArcKind::Inhibitor, 1))
+        );
+        assert!(place.get_postset().is_empty());
+        assert!(place.remove_extended_outgoing(&reference));
+        assert!(!place.remove_extended_outgoing(&reference));
+    }
+
     #[test]
     fn place_new_has_empty_preset() {
         let place = Place::new();
@@ -130,6 +343,34 @@ mod place_tests {
         assert!(place.is_empty());
     }
 
+    #[test]
+    fn place_initial_marking_follows_tokens_until_fixed() {
+        let mut place = Place::default();
+        assert!(place.add_token(3).is_ok());
+        assert!(place.remove_token(1).is_ok());
+        assert_eq!(place.initial_marking(), 2);
+
+        place.set_marking(5);
+        assert_eq!(place.marking(), 5);
+        assert_eq!(place.initial_marking(), 2);
+        assert!(place.remove_token(4).is_ok());
+        assert_eq!(place.marking(), 1);
+        assert_eq!(place.initial_marking(), 2);
+        assert_eq!(place.remove_token(2), Err(TokenError::InsufficientTokens));
+    }
+
+    #[test]
+    fn place_set_initial_marking_respects_capacity() {
+        let mut place = Place::default();
+        assert!(place.set_capacity(Some(2)));
+
+        assert!(!place.set_initial_marking(3));
+        assert!(place.set_initial_marking(2));
+        assert_eq!(place.initial_marking(), 2);
+        assert_eq!(place.marking(), 0);
+        assert!(!place.set_capacity(Some(1)));
+    }
+
     #[test]
     fn place_default_marking_is_zero() {
         let place = Place::default();
@@ -172,7 +413,7 @@ mod place_tests {
         let mut place = Place::default();
         let result = place.remove_token(1);
 
-        assert!(result.is_err());
+        assert_eq!(result, Err(TokenError::InsufficientTokens));
         assert!(place.is_empty());
     }
 
@@ -187,12 +428,44 @@ mod place_tests {
         assert_eq!(place.marking(), 3);
     }
 
+    #[test]
+    fn place_add_token_returns_err_on_overflow() {
+        let mut place = Place::default();
+
+        assert!(place.add_token(usize::MAX).is_ok());
+        assert_eq!(place.add_token(1), Err(TokenError::Overflow));
+        assert_eq!(place.marking(), usize::MAX);
+    }
+
+    #[test]
+    fn place_add_token_returns_err_when_capacity_exceeded() {
+        let mut place = Place::new();
+        assert!(place.set_capacity(Some(3)));
+
+        assert!(place.add_token(3).is_ok());
+        assert_eq!(place.add_token(1), Err(TokenError::CapacityExceeded));
+        assert_eq!(place.marking(), 3);
+    }
+
+    #[test]
+    fn place_set_capacity_below_marking_returns_false() {
+        let mut place = Place::new();
+        assert!(place.add_token(2).is_ok());
+
+        assert!(!place.set_capacity(Some(1)));
+        assert_eq!(place.capacity(), None);
+        assert!(place.set_capacity(Some(2)));
+        assert_eq!(place.capacity(), Some(2));
+        assert!(place.set_capacity(None));
+        assert_eq!(place.capacity(), None);
+    }
+
     #[test]
     fn place_add_incoming_transition_returns_true_when_success() {
         let mut place = Place::new();
         let reference = TransitionRef::new("Example transition");
 
-        assert!(place.add_incoming(reference));
+        assert!(place.add_incoming(reference, 1));
     }
 
     #[test]
@@ -200,8 +473,8 @@ mod place_tests {
         let mut place = Place::new();
         let reference = TransitionRef::new("Example transition");
 
-        assert!(place.add_incoming(reference.clone()));
-        assert!(!place.add_incoming(reference));
+        assert!(place.add_incoming(reference.clone(), 1));
+        assert!(!place.add_incoming(reference, 1));
     }
 
     #[test]
@@ -209,7 +482,7 @@ mod place_tests {
         let mut place = Place::new();
         let reference = TransitionRef::new("Example transition");
 
-        assert!(place.add_incoming(reference.clone()));
+        assert!(place.add_incoming(reference.clone(), 1));
         assert!(place.remove_incoming(&reference));
     }
 
@@ -218,8 +491,46 @@ mod place_tests {
         let mut place = Place::new();
         let reference = TransitionRef::new("Example transition");
 
-        assert!(place.add_incoming(reference));
+        assert!(place.add_incoming(reference, 1));
         let reference = TransitionRef::new("Example not found");
         assert!(!place.remove_incoming(&reference));
     }
+
+    #[test]
+    fn place_add_incoming_transition_stores_the_weight() {
+        let mut place = Place::new();
+        let reference = TransitionRef::new("Example transition");
+
+        assert!(place.add_incoming(reference.clone(), 3));
+        assert_eq!(place.get_preset().get(&reference), Some(&3));
+    }
+
+    #[test]
+    fn place_add_incoming_transition_does_not_overwrite_the_weight() {
+        let mut place = Place::new();
+        let reference = TransitionRef::new("Example transition");
+
+        assert!(place.add_incoming(reference.clone(), 3));
+        assert!(!place.add_incoming(reference.clone(), 5));
+        assert_eq!(place.get_preset().get(&reference), Some(&3));
+    }
+
+    #[test]
+    fn place_update_outgoing_transition_changes_the_weight() {
+        let mut place = Place::new();
+        let reference = TransitionRef::new("Example transition");
+
+        assert!(place.add_outgoing(reference.clone(), 1));
+        assert!(place.update_outgoing(&reference, 4));
+        assert_eq!(place.get_postset().get(&reference), Some(&4));
+    }
+
+    #[test]
+    fn place_update_outgoing_transition_returns_false_when_not_found() {
+        let mut place = Place::new();
+        let reference = TransitionRef::new("Example transition");
+
+        assert!(!place.update_outgoing(&reference, 4));
+        assert!(place.get_postset().is_empty());
+    }
 }
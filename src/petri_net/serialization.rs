@@ -0,0 +1,364 @@
+//! Serialization of the net with `serde`, available with the `serde` feature.
+//!
+//! References are serialized as strings of the form `label#uuid` so that they can be used
+//! as keys of maps in formats like JSON, e.g. in the marking vector.
+//! The net is serialized as its list of places, transitions and arcs,
+//! so that deserializing it rebuilds both sides of every arc consistently.
+
+use crate::petri_net::id_generator::IdGenerator;
+use crate::petri_net::{
+    ArcKind, FiringInterval, PetriNet, Place, PlaceRef, StochasticTiming, Transition, TransitionRef,
+};
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{BTreeMap, BTreeSet};
+use uuid::Uuid;
+
+/// Splits a serialized reference into its label and its UUID.
+fn parse_ref(string: &str) -> Option<(&str, Uuid)> {
+    let (label, uuid) = string.rsplit_once('#')?;
+    let uuid = Uuid::parse_str(uuid).ok()?;
+    Some((label, uuid))
+}
+
+impl Serialize for PlaceRef {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("{}#{}", self.label(), self.uuid()))
+    }
+}
+
+impl<'de> Deserialize<'de> for PlaceRef {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let string = String::deserialize(deserializer)?;
+        let (label, uuid) = parse_ref(&string)
+            .ok_or_else(|| D::Error::custom(format!("invalid place reference `{string}`")))?;
+        Ok(Self::with_uuid(label, uuid))
+    }
+}
+
+impl Serialize for TransitionRef {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("{}#{}", self.label(), self.uuid()))
+    }
+}
+
+impl<'de> Deserialize<'de> for TransitionRef {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let string = String::deserialize(deserializer)?;
+        let (label, uuid) = parse_ref(&string)
+            .ok_or_else(|| D::Error::custom(format!("invalid transition reference `{string}`")))?;
+        Ok(Self::with_uuid(label, uuid))
+    }
+}
+
+/// The serialized form of the net.
+/// Every arc is stored once, together with its weight.
+/// The initial marking is only stored for the places where it is fixed,
+/// the other places take their current marking as initial marking.
+#[derive(Serialize, Deserialize)]
+struct SerializedNet {
+    places: BTreeMap<PlaceRef, usize>,
+    #[serde(default)]
+    initial_marking: Option<BTreeMap<PlaceRef, usize>>,
+    #[serde(default)]
+    capacities: BTreeMap<PlaceRef, usize>,
+    transitions: BTreeSet<TransitionRef>,
+    #[serde(default)]
+    intervals: BTreeMap<TransitionRef, FiringInterval>,
+    #[serde(default)]
+    timings: BTreeMap<TransitionRef, StochasticTiming>,
+    arcs_place_transition: Vec<(PlaceRef, TransitionRef, usize)>,
+    arcs_transition_place: Vec<(TransitionRef, PlaceRef, usize)>,
+    #[serde(default)]
+    extended_arcs: Vec<(PlaceRef, TransitionRef, ArcKind, usize)>,
+    id_generator: IdGenerator,
+}
+
+impl Serialize for PetriNet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let arcs_place_transition = self
+            .find_weighted_arcs_place_transition()
+            .into_iter()
+            .map(|((place_ref, transition_ref), weight)| (place_ref, transition_ref, weight))
+            .collect();
+        let arcs_transition_place = self
+            .find_weighted_arcs_transition_place()
+            .into_iter()
+            .map(|((transition_ref, place_ref), weight)| (transition_ref, place_ref, weight))
+            .collect();
+        let extended_arcs = self
+            .find_extended_arcs()
+            .into_iter()
+            .map(|((place_ref, transition_ref), (kind, weight))| {
+                (place_ref, transition_ref, kind, weight)
+            })
+            .collect();
+
+        let capacities = self
+            .places
+            .iter()
+            .filter_map(|(place_ref, place)| Some((place_ref.clone(), place.capacity()?)))
+            .collect();
+
+        let intervals = self
+            .transitions
+            .iter()
+            .filter(|(_, transition)| transition.firing_interval() != FiringInterval::default())
+            .map(|(transition_ref, transition)| {
+                (transition_ref.clone(), transition.firing_interval())
+            })
+            .collect();
+
+        let timings = self
+            .transitions
+            .iter()
+            .filter(|(_, transition)| transition.stochastic_timing() != StochasticTiming::default())
+            .map(|(transition_ref, transition)| {
+                (transition_ref.clone(), transition.stochastic_timing())
+            })
+            .collect();
+
+        let initial_marking: BTreeMap<PlaceRef, usize> = self
+            .places
+            .iter()
+            .filter_map(|(place_ref, place)| {
+                place
+                    .fixed_initial_marking()
+                    .map(|tokens| (place_ref.clone(), tokens))
+            })
+            .collect();
+        let initial_marking = (!initial_marking.is_empty()).then_some(initial_marking);
+
+        SerializedNet {
+            places: self.marking_vector(),
+            initial_marking,
+            capacities,
+            transitions: self.transitions.keys().cloned().collect(),
+            intervals,
+            timings,
+            arcs_place_transition,
+            arcs_transition_place,
+            extended_arcs,
+            id_generator: self.id_generator.clone(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PetriNet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let serialized = SerializedNet::deserialize(deserializer)?;
+        let mut net = Self {
+            id_generator: serialized.id_generator,
+            ..Self::default()
+        };
+
+        for (place_ref, marking) in serialized.places {
+            net.places.insert(place_ref.clone(), Place::new());
+            let capacity = serialized.capacities.get(&place_ref).copied();
+            net.set_capacity(&place_ref, capacity)
+                .map_err(D::Error::custom)?;
+            net.add_token(&place_ref, marking)
+                .map_err(D::Error::custom)?;
+        }
+        for (place_ref, tokens) in serialized.initial_marking.unwrap_or_default() {
+            net.set_initial_marking(&place_ref, tokens)
+                .map_err(D::Error::custom)?;
+        }
+        for transition_ref in serialized.transitions {
+            let mut transition = Transition::new();
+            if let Some(interval) = serialized.intervals.get(&transition_ref) {
+                transition.set_firing_interval(*interval);
+            }
+            net.transitions.insert(transition_ref, transition);
+        }
+        for (transition_ref, timing) in serialized.timings {
+            net.set_stochastic_timing(&transition_ref, timing)
+                .map_err(D::Error::custom)?;
+        }
+        for (place_ref, transition_ref, weight) in serialized.arcs_place_transition {
+            net.add_weighted_arc_place_transition(&place_ref, &transition_ref, weight)
+                .map_err(D::Error::custom)?;
+        }
+        for (transition_ref, place_ref, weight) in serialized.arcs_transition_place {
+            net.add_weighted_arc_transition_place(&transition_ref, &place_ref, weight)
+                .map_err(D::Error::custom)?;
+        }
+        for (place_ref, transition_ref, kind, weight) in serialized.extended_arcs {
+            net.add_arc_with_kind(&place_ref, &transition_ref, kind, weight)
+                .map_err(D::Error::custom)?;
+        }
+        Ok(net)
+    }
+}
+
+#[cfg(test)]
+mod serialization_tests {
+    use super::*;
+    use crate::net_creator::*;
+
+    fn create_example_net() -> (PetriNet, PlaceRef, PlaceRef, TransitionRef) {
+        let mut net = PetriNet::new();
+        let p1 = net.add_place("P1");
+        let p2 = net.add_place("P#2");
+        let t1 = net.add_transition("T1");
+        assert!(net.add_weighted_arc_place_transition(&p1, &t1, 2).is_ok());
+        assert!(net.add_weighted_arc_transition_place(&t1, &p2, 3).is_ok());
+        assert!(net.add_token(&p1, 4).is_ok());
+        assert!(net.set_capacity(&p2, Some(6)).is_ok());
+        assert!(net.add_inhibitor_arc(&p2, &t1, 1).is_ok());
+        let p3 = net.add_place("P3");
+        assert!(net.add_reset_arc(&p3, &t1).is_ok());
+        let interval = FiringInterval::new(1, 2).unwrap();
+        assert!(net.set_firing_interval(&t1, interval).is_ok());
+        let timing = StochasticTiming::Exponential(0.5);
+        assert!(net.set_stochastic_timing(&t1, timing).is_ok());
+        (net, p1, p2, t1)
+    }
+
+    fn assert_same_net(net: &PetriNet, other: &PetriNet) {
+        assert_eq!(net.marking_vector(), other.marking_vector());
+        assert_eq!(net.initial_marking_vector(), other.initial_marking_vector());
+        for (place_ref, place) in net.places_iter() {
+            assert_eq!(other.capacity(place_ref).unwrap(), place.capacity());
+        }
+        assert_eq!(
+            net.find_weighted_arcs_place_transition(),
+            other.find_weighted_arcs_place_transition()
+        );
+        assert_eq!(
+            net.find_weighted_arcs_transition_place(),
+            other.find_weighted_arcs_transition_place()
+        );
+        assert_eq!(net.find_extended_arcs(), other.find_extended_arcs());
+        for (transition_ref, transition) in net.transitions_iter() {
+            assert_eq!(
+                other.firing_interval(transition_ref).unwrap(),
+                transition.firing_interval()
+            );
+            assert_eq!(
+                other.stochastic_timing(transition_ref).unwrap(),
+                transition.stochastic_timing()
+            );
+        }
+        let transitions: Vec<&TransitionRef> = net.transitions_iter().map(|(t, _)| t).collect();
+        let other_transitions: Vec<&TransitionRef> =
+            other.transitions_iter().map(|(t, _)| t).collect();
+        assert_eq!(transitions, other_transitions);
+    }
+
+    #[test]
+    fn place_ref_json_round_trip() {
+        let place_ref = PlaceRef::with_uuid("Example#place", Uuid::from_u128(1));
+        let json = serde_json::to_string(&place_ref).unwrap();
+
+        assert_eq!(
+            json,
+            r#""Example#place#00000000-0000-0000-0000-000000000001""#
+        );
+        let deserialized: PlaceRef = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, place_ref);
+    }
+
+    #[test]
+    fn transition_ref_invalid_string_returns_error() {
+        let result: Result<TransitionRef, _> = serde_json::from_str(r#""T1#not-a-uuid""#);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn marking_vector_json_round_trip() {
+        let (net, p1, p2, _) = create_example_net();
+        let json = serde_json::to_string(&net.marking_vector()).unwrap();
+        let marking: BTreeMap<PlaceRef, usize> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(marking.get(&p1), Some(&4));
+        assert_eq!(marking.get(&p2), Some(&0));
+    }
+
+    #[test]
+    fn net_json_round_trip() {
+        let (net, p1, _, t1) = create_example_net();
+        let json = serde_json::to_string(&net).unwrap();
+        let deserialized: PetriNet = serde_json::from_str(&json).unwrap();
+
+        assert_same_net(&net, &deserialized);
+        assert_eq!(
+            deserialized.arc_weight_place_transition(&p1, &t1).unwrap(),
+            2
+        );
+    }
+
+    #[test]
+    fn net_json_round_trip_keeps_initial_marking() {
+        let (mut net, place_refs, transition_refs) = create_net_chain_topology(3);
+        assert!(net.add_token(&place_refs[0], 1).is_ok());
+        assert!(net.fire(&transition_refs[0]).is_ok());
+        let json = serde_json::to_string(&net).unwrap();
+        let mut deserialized: PetriNet = serde_json::from_str(&json).unwrap();
+
+        assert_same_net(&net, &deserialized);
+        deserialized.restore_initial_marking();
+        assert_eq!(deserialized.marking(&place_refs[0]).unwrap(), 1);
+    }
+
+    #[test]
+    fn net_bincode_round_trip() {
+        let (net, _, _) = create_net_chain_topology(5);
+        let bytes = bincode::serialize(&net).unwrap();
+        let deserialized: PetriNet = bincode::deserialize(&bytes).unwrap();
+
+        assert_same_net(&net, &deserialized);
+    }
+
+    #[test]
+    fn net_deserialization_rebuilds_both_sides_of_arcs() {
+        let (net, p1, p2, t1) = create_example_net();
+        let json = serde_json::to_string(&net).unwrap();
+        let deserialized: PetriNet = serde_json::from_str(&json).unwrap();
+
+        let (_, place) = deserialized.places_iter().find(|(p, _)| **p == p1).unwrap();
+        assert_eq!(place.get_postset().get(&t1), Some(&2));
+        let (_, place) = deserialized.places_iter().find(|(p, _)| **p == p2).unwrap();
+        assert_eq!(place.get_preset().get(&t1), Some(&3));
+        let (_, transition) = deserialized.transitions_iter().next().unwrap();
+        assert_eq!(transition.get_preset().get(&p1), Some(&2));
+        assert_eq!(transition.get_postset().get(&p2), Some(&3));
+    }
+
+    #[test]
+    fn net_deserialization_keeps_deterministic_ids() {
+        let mut net = PetriNet::with_deterministic_ids(5);
+        net.add_place("P1");
+        let json = serde_json::to_string(&net).unwrap();
+        let mut deserialized: PetriNet = serde_json::from_str(&json).unwrap();
+
+        assert!(deserialized.has_deterministic_ids());
+        assert_eq!(deserialized.add_place("P2"), net.add_place("P2"));
+    }
+
+    #[test]
+    fn net_deserialization_arc_to_unknown_place_returns_error() {
+        let (net, _, _, _) = create_example_net();
+        let mut json: serde_json::Value = serde_json::to_value(&net).unwrap();
+        json["places"] = serde_json::json!({});
+
+        let result: Result<PetriNet, _> = serde_json::from_value(json);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn place_and_transition_json_round_trip() {
+        let (net, p1, _, t1) = create_example_net();
+        let (_, place) = net.places_iter().find(|(p, _)| **p == p1).unwrap();
+        let (_, transition) = net.transitions_iter().next().unwrap();
+
+        let place: Place = serde_json::from_str(&serde_json::to_string(place).unwrap()).unwrap();
+        let transition: Transition =
+            serde_json::from_str(&serde_json::to_string(transition).unwrap()).unwrap();
+        assert_eq!(place.marking(), 4);
+        assert_eq!(place.get_postset().get(&t1), Some(&2));
+        assert_eq!(transition.get_preset().get(&p1), Some(&2));
+    }
+}
@@ -0,0 +1,66 @@
+/// The firing behavior of a transition in a generalized stochastic Petri net (GSPN).
+///
+/// Immediate transitions have priority over exponential transitions:
+/// in a marking that enables an immediate transition, no exponential transition fires.
+///
+/// The default is an exponential transition with rate one.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StochasticTiming {
+    /// The transition fires after an exponentially distributed delay with the given rate.
+    Exponential(f64),
+    /// The transition fires without delay.
+    /// Conflicts between immediate transitions are resolved with probabilities proportional to the weights.
+    Immediate(f64),
+}
+
+impl Default for StochasticTiming {
+    fn default() -> Self {
+        Self::Exponential(1.0)
+    }
+}
+
+impl StochasticTiming {
+    /// Checks whether the transition is immediate.
+    #[must_use]
+    pub const fn is_immediate(&self) -> bool {
+        matches!(self, Self::Immediate(_))
+    }
+
+    /// Gets the rate of an exponential transition or the weight of an immediate transition.
+    #[must_use]
+    pub const fn value(&self) -> f64 {
+        match self {
+            Self::Exponential(value) | Self::Immediate(value) => *value,
+        }
+    }
+
+    /// Checks whether the rate or the weight is positive and finite.
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.value().is_finite() && self.value() > 0.0
+    }
+}
+
+#[cfg(test)]
+mod stochastic_timing_tests {
+    use super::*;
+
+    #[test]
+    fn stochastic_timing_default_is_exponential_with_rate_one() {
+        let timing = StochasticTiming::default();
+
+        assert!(!timing.is_immediate());
+        assert!((timing.value() - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn stochastic_timing_is_valid_requires_positive_finite_value() {
+        assert!(StochasticTiming::Exponential(0.5).is_valid());
+        assert!(StochasticTiming::Immediate(2.0).is_valid());
+        assert!(!StochasticTiming::Exponential(0.0).is_valid());
+        assert!(!StochasticTiming::Immediate(-1.0).is_valid());
+        assert!(!StochasticTiming::Exponential(f64::NAN).is_valid());
+        assert!(!StochasticTiming::Exponential(f64::INFINITY).is_valid());
+    }
+}
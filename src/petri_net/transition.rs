@@ -1,10 +1,20 @@
+use crate::petri_net::arc_kind::ArcKind;
+use crate::petri_net::firing_interval::FiringInterval;
 use crate::petri_net::place_ref::PlaceRef;
-use std::collections::BTreeSet;
+use crate::petri_net::stochastic_timing::StochasticTiming;
+use std::collections::BTreeMap;
 
-#[derive(Default)]
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Transition {
-    preset: BTreeSet<PlaceRef>,
-    postset: BTreeSet<PlaceRef>,
+    preset: BTreeMap<PlaceRef, usize>,
+    postset: BTreeMap<PlaceRef, usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    extended_preset: BTreeMap<PlaceRef, (ArcKind, usize)>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    interval: FiringInterval,
+    #[cfg_attr(feature = "serde", serde(default))]
+    timing: StochasticTiming,
 }
 
 impl Transition {
@@ -14,50 +24,131 @@ impl Transition {
         Self::default()
     }
 
-    /// Gets an immutable reference to the set of places
-    /// whose edges point to this transition.
+    /// Gets an immutable reference to the map of places
+    /// whose edges point to this transition and the weight of each edge.
     #[must_use]
-    pub const fn get_preset(&self) -> &BTreeSet<PlaceRef> {
+    pub const fn get_preset(&self) -> &BTreeMap<PlaceRef, usize> {
         &self.preset
     }
 
-    /// Gets a mutable reference to the set of places
-    /// whose edges point to this transition.
-    pub const fn get_preset_mut(&mut self) -> &mut BTreeSet<PlaceRef> {
+    /// Gets a mutable reference to the map of places
+    /// whose edges point to this transition and the weight of each edge.
+    pub const fn get_preset_mut(&mut self) -> &mut BTreeMap<PlaceRef, usize> {
         &mut self.preset
     }
 
-    /// Gets an immutable reference to the set of places
-    /// to which edges from this transition point to.
+    /// Gets an immutable reference to the map of places
+    /// to which edges from this transition point to and the weight of each edge.
     #[must_use]
-    pub const fn get_postset(&self) -> &BTreeSet<PlaceRef> {
+    pub const fn get_postset(&self) -> &BTreeMap<PlaceRef, usize> {
         &self.postset
     }
 
-    /// Gets a mutable reference to the set of places
-    /// to which edges from this transition point to.
-    pub const fn get_postset_mut(&mut self) -> &mut BTreeSet<PlaceRef> {
+    /// Gets a mutable reference to the map of places
+    /// to which edges from this transition point to and the weight of each edge.
+    pub const fn get_postset_mut(&mut self) -> &mut BTreeMap<PlaceRef, usize> {
         &mut self.postset
     }
 
-    /// Adds an incoming `Place`, update the preset accordingly.
-    pub fn add_incoming(&mut self, reference: PlaceRef) -> bool {
-        self.preset.insert(reference)
+    /// Gets an immutable reference to the map of places
+    /// whose arcs of a kind other than `ArcKind::Normal` point to this transition,
+    /// together with the kind and the weight of each arc.
+    #[must_use]
+    pub const fn get_extended_preset(&self) -> &BTreeMap<PlaceRef, (ArcKind, usize)> {
+        &self.extended_preset
+    }
+
+    /// Gets the firing interval of the transition.
+    #[must_use]
+    pub const fn firing_interval(&self) -> FiringInterval {
+        self.interval
+    }
+
+    /// Sets the firing interval of the transition.
+    pub const fn set_firing_interval(&mut self, interval: FiringInterval) {
+        self.interval = interval;
+    }
+
+    /// Gets the stochastic timing of the transition.
+    #[must_use]
+    pub const fn stochastic_timing(&self) -> StochasticTiming {
+        self.timing
+    }
+
+    /// Sets the stochastic timing of the transition.
+    pub const fn set_stochastic_timing(&mut self, timing: StochasticTiming) {
+        self.timing = timing;
+    }
+
+    /// Adds an incoming `Place` with the given arc weight, update the preset accordingly.
+    /// Returns `false` if the place was already in the preset, the weight is then left unchanged.
+    pub fn add_incoming(&mut self, reference: PlaceRef, weight: usize) -> bool {
+        if self.preset.contains_key(&reference) {
+            return false;
+        }
+        self.preset.insert(reference, weight);
+        true
     }
 
     /// Removes an incoming `Place`, update the preset accordingly.
     pub fn remove_incoming(&mut self, reference: &PlaceRef) -> bool {
-        self.preset.remove(reference)
+        self.preset.remove(reference).is_some()
+    }
+
+    /// Updates the weight of the arc from an incoming `Place`.
+    /// Returns `false` if the place is not in the preset.
+    pub fn update_incoming(&mut self, reference: &PlaceRef, weight: usize) -> bool {
+        let Some(value) = self.preset.get_mut(reference) else {
+            return false;
+        };
+        *value = weight;
+        true
     }
 
-    /// Adds an outgoing `Place`, update the postset accordingly.
-    pub fn add_outgoing(&mut self, reference: PlaceRef) -> bool {
-        self.postset.insert(reference)
+    /// Adds an incoming `Place` with an arc of the given kind and weight,
+    /// update the extended preset accordingly.
+    /// Returns `false` if the place was already in the extended preset, the arc is then left unchanged.
+    pub fn add_extended_incoming(
+        &mut self,
+        reference: PlaceRef,
+        kind: ArcKind,
+        weight: usize,
+    ) -> bool {
+        if self.extended_preset.contains_key(&reference) {
+            return false;
+        }
+        self.extended_preset.insert(reference, (kind, weight));
+        true
+    }
+
+    /// Removes an incoming `Place` from the extended preset.
+    pub fn remove_extended_incoming(&mut self, reference: &PlaceRef) -> bool {
+        self.extended_preset.remove(reference).is_some()
+    }
+
+    /// Adds an outgoing `Place` with the given arc weight, update the postset accordingly.
+    /// Returns `false` if the place was already in the postset, the weight is then left unchanged.
+    pub fn add_outgoing(&mut self, reference: PlaceRef, weight: usize) -> bool {
+        if self.postset.contains_key(&reference) {
+            return false;
+        }
+        self.postset.insert(reference, weight);
+        true
     }
 
     /// Removes an outgoing `Place`, update the postset accordingly.
     pub fn remove_outgoing(&mut self, reference: &PlaceRef) -> bool {
-        self.postset.remove(reference)
+        self.postset.remove(reference).is_some()
+    }
+
+    /// Updates the weight of the arc to an outgoing `Place`.
+    /// Returns `false` if the place is not in the postset.
+    pub fn update_outgoing(&mut self, reference: &PlaceRef, weight: usize) -> bool {
+        let Some(value) = self.postset.get_mut(reference) else {
+            return false;
+        };
+        *value = weight;
+        true
     }
 }
 
@@ -65,6 +156,22 @@ impl Transition {
 mod transition_tests {
     use super::*;
 
+    #[test]
+    fn transition_add_extended_incoming_returns_false_if_already_present() {
+        let mut transition = Transition::new();
+        let reference = PlaceRef::new("Example place");
+
+        assert!(transition.add_extended_incoming(reference.clone(), ArcKind::Reset, 1));
+        assert!(!transition.add_extended_incoming(reference.clone(), ArcKind::Read, 2));
+        assert_eq!(
+            transition.get_extended_preset().get(&reference),
+            Some(&(ArcKind::Reset, 1))
+        );
+        assert!(transition.get_preset().is_empty());
+        assert!(transition.remove_extended_incoming(&reference));
+        assert!(!transition.remove_extended_incoming(&reference));
+    }
+
     #[test]
     fn transition_new_has_empty_preset() {
         let transition = Transition::new();
@@ -84,7 +191,7 @@ mod transition_tests {
         let mut transition = Transition::new();
         let reference = PlaceRef::new("Example place");
 
-        assert!(transition.add_incoming(reference));
+        assert!(transition.add_incoming(reference, 1));
     }
 
     #[test]
@@ -92,8 +199,8 @@ mod transition_tests {
         let mut transition = Transition::new();
         let reference = PlaceRef::new("Example place");
 
-        assert!(transition.add_incoming(reference.clone()));
-        assert!(!transition.add_incoming(reference));
+        assert!(transition.add_incoming(reference.clone(), 1));
+        assert!(!transition.add_incoming(reference, 1));
     }
 
     #[test]
@@ -101,7 +208,7 @@ mod transition_tests {
         let mut transition = Transition::new();
         let reference = PlaceRef::new("Example place");
 
-        assert!(transition.add_incoming(reference.clone()));
+        assert!(transition.add_incoming(reference.clone(), 1));
         assert!(transition.remove_incoming(&reference));
     }
 
@@ -110,8 +217,27 @@ mod transition_tests {
         let mut transition = Transition::new();
         let reference = PlaceRef::new("Example place");
 
-        assert!(transition.add_incoming(reference));
+        assert!(transition.add_incoming(reference, 1));
         let reference = PlaceRef::new("Example not found");
         assert!(!transition.remove_incoming(&reference));
     }
+
+    #[test]
+    fn transition_add_outgoing_place_stores_the_weight() {
+        let mut transition = Transition::new();
+        let reference = PlaceRef::new("Example place");
+
+        assert!(transition.add_outgoing(reference.clone(), 2));
+        assert_eq!(transition.get_postset().get(&reference), Some(&2));
+    }
+
+    #[test]
+    fn transition_update_incoming_place_changes_the_weight() {
+        let mut transition = Transition::new();
+        let reference = PlaceRef::new("Example place");
+
+        assert!(transition.add_incoming(reference.clone(), 1));
+        assert!(transition.update_incoming(&reference, 7));
+        assert_eq!(transition.get_preset().get(&reference), Some(&7));
+    }
 }
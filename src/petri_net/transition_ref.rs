@@ -4,7 +4,7 @@ use uuid::Uuid;
 ///
 /// Contains a label and a UUID.
 /// The ordering is done first by the label and then by the UUID.
-#[derive(Clone, Eq, PartialEq, PartialOrd, Ord)]
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 pub struct TransitionRef {
     label: String,
     uuid: Uuid,
@@ -20,12 +20,30 @@ impl TransitionRef {
         }
     }
 
+    /// Creates a new transition reference with the given UUID.
+    ///
+    /// Useful to rebuild references deterministically, e.g. for snapshot tests.
+    #[must_use]
+    pub fn with_uuid(transition_label: &str, uuid: Uuid) -> Self {
+        Self {
+            label: transition_label.to_string(),
+            uuid,
+        }
+    }
+
     /// Returns the transition label for this reference.
     #[inline]
     #[must_use]
     pub const fn label(&self) -> &String {
         &self.label
     }
+
+    /// Returns the UUID that distinguishes references with the same label.
+    #[inline]
+    #[must_use]
+    pub const fn uuid(&self) -> &Uuid {
+        &self.uuid
+    }
 }
 
 impl std::fmt::Display for TransitionRef {
@@ -79,4 +97,13 @@ mod transition_ref_tests {
 
         assert_eq!(format!("{transition_ref}"), "Example reference");
     }
+
+    #[test]
+    fn transition_ref_with_uuid_sets_label_and_uuid() {
+        let uuid = Uuid::from_u128(42);
+        let transition_ref = TransitionRef::with_uuid("Example reference", uuid);
+
+        assert_eq!(transition_ref.label(), "Example reference");
+        assert_eq!(transition_ref.uuid(), &uuid);
+    }
 }
@@ -0,0 +1,248 @@
+//! # Random simulation
+//!
+//! Plays the token game by repeatedly firing a transition chosen uniformly at random
+//! among the transitions enabled in the current marking.
+//! The choices come from a small seeded pseudo-random number generator,
+//! so two simulations of the same net with the same seed produce the same trace.
+
+use crate::error::Error;
+use crate::petri_net::{PetriNet, PlaceRef, TransitionRef};
+use std::collections::BTreeMap;
+
+/// The reason why `RandomSimulation::run` stopped.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RandomStop {
+    /// The maximum number of firings was reached.
+    StepLimit,
+    /// No transition is enabled in the current marking.
+    Deadlock,
+    /// The predicate over the marking became true.
+    PredicateSatisfied,
+}
+
+/// The pseudo-random number generator `SplitMix64`.
+/// It is fast and reproducible, but not suitable for cryptographic purposes.
+struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    const fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    const fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number in `0..bound`. The bound must not be zero.
+    fn next_below(&mut self, bound: usize) -> usize {
+        let bound = u64::try_from(bound).unwrap_or(u64::MAX);
+        // The modulo bias is negligible for the small bounds used here.
+        usize::try_from(self.next_u64() % bound).unwrap_or_default()
+    }
+}
+
+/// A random simulation of a net.
+///
+/// The simulation starts from the current marking of the net, which is left unchanged.
+/// Every marking visited is recorded, starting with the initial one,
+/// so there is always one more marking than transitions in the trace.
+pub struct RandomSimulation<'a> {
+    net: &'a PetriNet,
+    generator: SplitMix64,
+    marking: BTreeMap<PlaceRef, usize>,
+    trace: Vec<TransitionRef>,
+    markings: Vec<BTreeMap<PlaceRef, usize>>,
+}
+
+impl<'a> RandomSimulation<'a> {
+    /// Creates a simulation of the net starting from its current marking,
+    /// whose random choices are determined by the seed.
+    #[must_use]
+    pub fn new(net: &'a PetriNet, seed: u64) -> Self {
+        let marking = net.marking_vector();
+        Self {
+            net,
+            generator: SplitMix64::new(seed),
+            markings: vec![marking.clone()],
+            marking,
+            trace: Vec::new(),
+        }
+    }
+
+    /// Gets the current marking of the simulation.
+    #[must_use]
+    pub const fn marking(&self) -> &BTreeMap<PlaceRef, usize> {
+        &self.marking
+    }
+
+    /// Gets the transitions fired so far, in order.
+    #[must_use]
+    pub fn trace(&self) -> &[TransitionRef] {
+        &self.trace
+    }
+
+    /// Gets the markings visited so far, in order, starting with the initial marking.
+    #[must_use]
+    pub fn markings(&self) -> &[BTreeMap<PlaceRef, usize>] {
+        &self.markings
+    }
+
+    /// Consumes the simulation and returns the transitions fired and the markings visited, in order.
+    #[must_use]
+    pub fn into_trace(self) -> (Vec<TransitionRef>, Vec<BTreeMap<PlaceRef, usize>>) {
+        (self.trace, self.markings)
+    }
+
+    /// Fires a transition chosen at random among the enabled ones.
+    /// Returns the transition fired, or `None` if no transition is enabled.
+    ///
+    /// # Errors
+    ///
+    /// If producing the tokens causes an overflow, then an error is returned.
+    pub fn step(&mut self) -> Result<Option<TransitionRef>, Error> {
+        let enabled = self.net.enabled_transitions_at(&self.marking);
+        if enabled.is_empty() {
+            return Ok(None);
+        }
+        let index = self.generator.next_below(enabled.len());
+        let Some(transition_ref) = enabled.into_iter().nth(index) else {
+            return Ok(None);
+        };
+        self.marking = self.net.fire_at(&transition_ref, &self.marking)?;
+        self.markings.push(self.marking.clone());
+        self.trace.push(transition_ref.clone());
+        Ok(Some(transition_ref))
+    }
+
+    /// Fires transitions with `step` until `stop_when` returns `true` for the current marking,
+    /// `max_steps` transitions have fired or no transition is enabled.
+    /// The predicate is also evaluated on the marking before the first firing.
+    /// Returns the reason why the simulation stopped.
+    ///
+    /// # Errors
+    ///
+    /// If producing the tokens causes an overflow, then an error is returned.
+    pub fn run<F>(&mut self, max_steps: usize, mut stop_when: F) -> Result<RandomStop, Error>
+    where
+        F: FnMut(&BTreeMap<PlaceRef, usize>) -> bool,
+    {
+        let mut steps = 0;
+        loop {
+            if stop_when(&self.marking) {
+                return Ok(RandomStop::PredicateSatisfied);
+            }
+            if steps == max_steps {
+                return Ok(RandomStop::StepLimit);
+            }
+            if self.step()?.is_none() {
+                return Ok(RandomStop::Deadlock);
+            }
+            steps += 1;
+        }
+    }
+}
+
+impl PetriNet {
+    /// Creates a random simulation of the net starting from its current marking.
+    /// The same seed always produces the same sequence of firings.
+    #[must_use]
+    pub fn random_simulation(&self, seed: u64) -> RandomSimulation<'_> {
+        RandomSimulation::new(self, seed)
+    }
+}
+
+#[cfg(test)]
+mod random_tests {
+    use super::*;
+    use crate::net_creator::*;
+
+    fn create_choice_net() -> (PetriNet, PlaceRef, Vec<TransitionRef>) {
+        let (mut net, place_ref, transition_ref) = create_net_loop_topology();
+        let other_ref = net.add_transition("T2");
+        assert!(net.add_arc_place_transition(&place_ref, &other_ref).is_ok());
+        assert!(net.add_arc_transition_place(&other_ref, &place_ref).is_ok());
+        assert!(net.add_token(&place_ref, 1).is_ok());
+        (net, place_ref, vec![transition_ref, other_ref])
+    }
+
+    #[test]
+    fn random_simulation_same_seed_same_trace() {
+        let (net, _, _) = create_choice_net();
+        let mut first = net.random_simulation(42);
+        let mut second = net.random_simulation(42);
+
+        assert_eq!(first.run(50, |_| false).unwrap(), RandomStop::StepLimit);
+        assert_eq!(second.run(50, |_| false).unwrap(), RandomStop::StepLimit);
+        assert_eq!(first.trace().len(), 50);
+        assert_eq!(first.trace(), second.trace());
+    }
+
+    #[test]
+    fn random_simulation_chooses_every_enabled_transition() {
+        let (net, _, transition_refs) = create_choice_net();
+        let mut simulation = net.random_simulation(7);
+        assert!(simulation.run(100, |_| false).is_ok());
+
+        for transition_ref in &transition_refs {
+            assert!(simulation.trace().contains(transition_ref));
+        }
+    }
+
+    #[test]
+    fn random_simulation_stops_at_deadlock() {
+        let (mut net, place_refs, transition_refs) = create_net_chain_topology(3);
+        assert!(net.add_token(&place_refs[0], 1).is_ok());
+        let mut simulation = net.random_simulation(0);
+
+        assert_eq!(simulation.run(10, |_| false).unwrap(), RandomStop::Deadlock);
+        assert_eq!(simulation.trace(), transition_refs.as_slice());
+        let (trace, markings) = simulation.into_trace();
+        assert_eq!(markings.len(), trace.len() + 1);
+        assert_eq!(markings[0], net.marking_vector());
+        assert_eq!(markings[2].get(&place_refs[2]), Some(&1));
+        assert_eq!(net.marking(&place_refs[0]).unwrap(), 1);
+    }
+
+    #[test]
+    fn random_simulation_stops_when_predicate_holds() {
+        let (mut net, place_refs, _) = create_net_chain_topology(3);
+        assert!(net.add_token(&place_refs[0], 1).is_ok());
+        let target_ref = place_refs[1].clone();
+        let mut simulation = net.random_simulation(0);
+        let stop = simulation
+            .run(10, |marking| marking.get(&target_ref) == Some(&1))
+            .unwrap();
+
+        assert_eq!(stop, RandomStop::PredicateSatisfied);
+        assert_eq!(simulation.trace().len(), 1);
+        assert_eq!(simulation.marking().get(&target_ref), Some(&1));
+    }
+
+    #[test]
+    fn random_simulation_predicate_checked_on_initial_marking() {
+        let (net, _, _) = create_choice_net();
+        let mut simulation = net.random_simulation(0);
+
+        assert_eq!(
+            simulation.run(10, |_| true).unwrap(),
+            RandomStop::PredicateSatisfied
+        );
+        assert!(simulation.trace().is_empty());
+        assert_eq!(simulation.markings().len(), 1);
+    }
+
+    #[test]
+    fn random_simulation_zero_steps_returns_step_limit() {
+        let (net, _, _) = create_choice_net();
+        let mut simulation = net.random_simulation(0);
+
+        assert_eq!(simulation.run(0, |_| false).unwrap(), RandomStop::StepLimit);
+        assert!(simulation.step().unwrap().is_some());
+    }
+}
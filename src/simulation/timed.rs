@@ -0,0 +1,426 @@
+//! # Timed simulation
+//!
+//! Discrete-event simulation of time Petri nets in the sense of Merlin and Farber.
+//! Every transition has a static firing interval `[earliest, latest]`, see `FiringInterval`.
+//! A global clock advances in discrete time units and every enabled transition
+//! keeps track of how long it has been enabled.
+//!
+//! A transition may fire once it has been enabled for at least `earliest` time units
+//! and time may not pass beyond the point where an enabled transition exceeds `latest`.
+//! After firing a transition, a transition is newly enabled and its clock restarts
+//! unless it was enabled before and stays enabled in the intermediate marking,
+//! i.e. after removing the consumed tokens. The fired transition always restarts its clock.
+
+use crate::error::Error;
+use crate::petri_net::{ArcKind, FiringInterval, PetriNet, PlaceRef, TransitionRef};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A transition fired during the simulation together with the time at which it fired.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TimedFiring {
+    /// The value of the global clock when the transition fired.
+    pub time: u64,
+    /// The transition that fired.
+    pub transition_ref: TransitionRef,
+}
+
+/// The reason why `TimedSimulation::run` stopped.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TimedStop {
+    /// The maximum number of firings was reached.
+    StepLimit,
+    /// The next firing would happen after the time limit.
+    TimeLimit,
+    /// No transition is enabled, time could pass forever without any firing.
+    Deadlock,
+}
+
+/// A discrete-event simulation of a net with firing intervals.
+///
+/// The simulation starts at time `0` from the current marking of the net,
+/// which is left unchanged. Transitions can be fired one by one with `advance` and `fire`,
+/// or automatically with `step` and `run`.
+pub struct TimedSimulation<'a> {
+    net: &'a PetriNet,
+    marking: BTreeMap<PlaceRef, usize>,
+    clock: u64,
+    enabled_since: BTreeMap<TransitionRef, u64>,
+    trace: Vec<TimedFiring>,
+}
+
+impl<'a> TimedSimulation<'a> {
+    /// Creates a simulation of the net starting from its current marking at time `0`.
+    #[must_use]
+    pub fn new(net: &'a PetriNet) -> Self {
+        let marking = net.marking_vector();
+        let enabled_since = net
+            .enabled_transitions_at(&marking)
+            .into_iter()
+            .map(|transition_ref| (transition_ref, 0))
+            .collect();
+        Self {
+            net,
+            marking,
+            clock: 0,
+            enabled_since,
+            trace: Vec::new(),
+        }
+    }
+
+    /// Gets the current value of the global clock.
+    #[must_use]
+    pub const fn clock(&self) -> u64 {
+        self.clock
+    }
+
+    /// Gets the current marking of the simulation.
+    #[must_use]
+    pub const fn marking(&self) -> &BTreeMap<PlaceRef, usize> {
+        &self.marking
+    }
+
+    /// Gets the transitions fired so far, in order.
+    #[must_use]
+    pub fn trace(&self) -> &[TimedFiring] {
+        &self.trace
+    }
+
+    /// Consumes the simulation and returns the transitions fired, in order.
+    #[must_use]
+    pub fn into_trace(self) -> Vec<TimedFiring> {
+        self.trace
+    }
+
+    /// Gets how long the transition has been enabled,
+    /// or `None` if it is not enabled in the current marking.
+    #[must_use]
+    pub fn enabled_for(&self, transition_ref: &TransitionRef) -> Option<u64> {
+        self.enabled_since
+            .get(transition_ref)
+            .map(|since| self.clock - since)
+    }
+
+    /// Finds the transitions that can fire now,
+    /// i.e. those that have been enabled for at least their earliest firing time.
+    #[must_use]
+    pub fn firable_transitions(&self) -> BTreeSet<TransitionRef> {
+        self.enabled_since
+            .keys()
+            .filter(|transition_ref| {
+                self.remaining_wait(transition_ref)
+                    .is_some_and(|wait| wait == 0)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Gets the longest delay that can elapse before an enabled transition
+    /// exceeds its latest firing time, or `None` if time can pass without limit.
+    #[must_use]
+    pub fn max_delay(&self) -> Option<u64> {
+        self.latest_deadline().map(|(delay, _)| delay)
+    }
+
+    /// Lets `delay` time units pass without firing any transition.
+    ///
+    /// # Errors
+    ///
+    /// If an enabled transition would exceed its latest firing time, then an error is returned
+    /// and the clock is left unchanged.
+    /// If the clock overflows, then an error is returned.
+    pub fn advance(&mut self, delay: u64) -> Result<(), Error> {
+        if let Some((max_delay, transition_ref)) = self.latest_deadline()
+            && delay > max_delay
+        {
+            return Err(Error::DeadlineMissed(transition_ref));
+        }
+        self.clock = self
+            .clock
+            .checked_add(delay)
+            .ok_or(Error::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Fires a transition at the current time and records it in the trace.
+    ///
+    /// # Errors
+    ///
+    /// If the `TransitionRef` is invalid, then an error is returned.
+    /// If the transition is not enabled, then an error is returned.
+    /// If the transition has not been enabled for its earliest firing time, then an error is returned.
+    /// If producing the tokens causes an overflow, then an error is returned.
+    pub fn fire(&mut self, transition_ref: &TransitionRef) -> Result<(), Error> {
+        if !self.net.is_enabled_at(transition_ref, &self.marking)? {
+            return Err(Error::TransitionNotEnabled(transition_ref.clone()));
+        }
+        if self.remaining_wait(transition_ref) != Some(0) {
+            return Err(Error::FiringTooEarly(transition_ref.clone()));
+        }
+        let intermediate_marking = self.intermediate_marking(transition_ref);
+        let next_marking = self.net.fire_at(transition_ref, &self.marking)?;
+
+        let mut enabled_since = BTreeMap::new();
+        for enabled_ref in self.net.enabled_transitions_at(&next_marking) {
+            let since = match self.enabled_since.get(&enabled_ref) {
+                Some(since)
+                    if enabled_ref != *transition_ref
+                        && self
+                            .net
+                            .is_enabled_at(&enabled_ref, &intermediate_marking)
+                            .unwrap_or(false) =>
+                {
+                    *since
+                }
+                _ => self.clock,
+            };
+            enabled_since.insert(enabled_ref, since);
+        }
+
+        self.marking = next_marking;
+        self.enabled_since = enabled_since;
+        self.trace.push(TimedFiring {
+            time: self.clock,
+            transition_ref: transition_ref.clone(),
+        });
+        Ok(())
+    }
+
+    /// Lets the time pass until the earliest moment at which a transition can fire
+    /// and fires it. If several transitions can fire at that moment,
+    /// the first one in the order of the net is chosen.
+    /// Returns the firing, or `None` if no transition is enabled.
+    ///
+    /// # Errors
+    ///
+    /// If producing the tokens causes an overflow, then an error is returned.
+    pub fn step(&mut self) -> Result<Option<TimedFiring>, Error> {
+        let Some(delay) = self.next_firing_delay() else {
+            return Ok(None);
+        };
+        self.advance(delay)?;
+        let Some(transition_ref) = self.firable_transitions().pop_first() else {
+            return Ok(None);
+        };
+        self.fire(&transition_ref)?;
+        Ok(self.trace.last().cloned())
+    }
+
+    /// Fires transitions with `step` until `max_steps` transitions have fired,
+    /// no transition is enabled, or the next firing would happen after `time_limit`.
+    /// Returns the reason why the simulation stopped.
+    ///
+    /// # Errors
+    ///
+    /// If producing the tokens causes an overflow, then an error is returned.
+    pub fn run(&mut self, max_steps: usize, time_limit: Option<u64>) -> Result<TimedStop, Error> {
+        for _ in 0..max_steps {
+            let Some(delay) = self.next_firing_delay() else {
+                return Ok(TimedStop::Deadlock);
+            };
+            let next_time = self.clock.saturating_add(delay);
+            if time_limit.is_some_and(|time_limit| next_time > time_limit) {
+                return Ok(TimedStop::TimeLimit);
+            }
+            self.step()?;
+        }
+        Ok(TimedStop::StepLimit)
+    }
+
+    /// Gets the delay until the first enabled transition can fire,
+    /// or `None` if no transition is enabled.
+    fn next_firing_delay(&self) -> Option<u64> {
+        self.enabled_since
+            .keys()
+            .filter_map(|transition_ref| self.remaining_wait(transition_ref))
+            .min()
+    }
+
+    /// Gets the time an enabled transition still has to wait before it can fire.
+    fn remaining_wait(&self, transition_ref: &TransitionRef) -> Option<u64> {
+        let enabled_for = self.enabled_for(transition_ref)?;
+        Some(
+            self.interval(transition_ref)
+                .earliest()
+                .saturating_sub(enabled_for),
+        )
+    }
+
+    /// Gets the enabled transition that reaches its latest firing time first
+    /// together with the delay until that moment.
+    fn latest_deadline(&self) -> Option<(u64, TransitionRef)> {
+        self.enabled_since
+            .keys()
+            .filter_map(|transition_ref| {
+                let latest = self.interval(transition_ref).latest()?;
+                let enabled_for = self.enabled_for(transition_ref)?;
+                Some((latest.saturating_sub(enabled_for), transition_ref.clone()))
+            })
+            .min()
+    }
+
+    fn interval(&self, transition_ref: &TransitionRef) -> FiringInterval {
+        self.net.firing_interval(transition_ref).unwrap_or_default()
+    }
+
+    /// Computes the marking after removing the tokens consumed by the transition,
+    /// before the tokens are produced.
+    fn intermediate_marking(&self, transition_ref: &TransitionRef) -> BTreeMap<PlaceRef, usize> {
+        let mut marking = self.marking.clone();
+        let Some((_, transition)) = self
+            .net
+            .transitions_iter()
+            .find(|(other_ref, _)| *other_ref == transition_ref)
+        else {
+            return marking;
+        };
+        for (place_ref, weight) in transition.get_preset() {
+            if let Some(tokens) = marking.get_mut(place_ref) {
+                *tokens = tokens.saturating_sub(*weight);
+            }
+        }
+        for (place_ref, (kind, _)) in transition.get_extended_preset() {
+            if *kind == ArcKind::Reset {
+                marking.insert(place_ref.clone(), 0);
+            }
+        }
+        marking
+    }
+}
+
+impl PetriNet {
+    /// Creates a timed simulation of the net starting from its current marking.
+    /// See `TimedSimulation` for the semantics of the firing intervals.
+    #[must_use]
+    pub fn timed_simulation(&self) -> TimedSimulation<'_> {
+        TimedSimulation::new(self)
+    }
+}
+
+#[cfg(test)]
+mod timed_tests {
+    use super::*;
+    use crate::net_creator::*;
+
+    fn create_timed_chain() -> (PetriNet, Vec<PlaceRef>, Vec<TransitionRef>) {
+        let (mut net, place_refs, transition_refs) = create_net_chain_topology(3);
+        assert!(net.add_token(&place_refs[0], 1).is_ok());
+        let interval = FiringInterval::new(2, 4).unwrap();
+        assert!(
+            net.set_firing_interval(&transition_refs[0], interval)
+                .is_ok()
+        );
+        let interval = FiringInterval::new(3, 3).unwrap();
+        assert!(
+            net.set_firing_interval(&transition_refs[1], interval)
+                .is_ok()
+        );
+        (net, place_refs, transition_refs)
+    }
+
+    #[test]
+    fn timed_simulation_run_produces_timestamped_trace() {
+        let (net, place_refs, transition_refs) = create_timed_chain();
+        let mut simulation = net.timed_simulation();
+
+        assert_eq!(simulation.run(10, None).unwrap(), TimedStop::Deadlock);
+        assert_eq!(
+            simulation.trace(),
+            [
+                TimedFiring {
+                    time: 2,
+                    transition_ref: transition_refs[0].clone()
+                },
+                TimedFiring {
+                    time: 5,
+                    transition_ref: transition_refs[1].clone()
+                }
+            ]
+        );
+        assert_eq!(simulation.marking().get(&place_refs[2]), Some(&1));
+        assert_eq!(net.marking(&place_refs[0]).unwrap(), 1);
+    }
+
+    #[test]
+    fn timed_simulation_fire_before_earliest_returns_err() {
+        let (net, _, transition_refs) = create_timed_chain();
+        let mut simulation = net.timed_simulation();
+
+        assert!(simulation.firable_transitions().is_empty());
+        assert!(matches!(
+            simulation.fire(&transition_refs[0]),
+            Err(Error::FiringTooEarly(_))
+        ));
+        assert!(matches!(
+            simulation.fire(&transition_refs[1]),
+            Err(Error::TransitionNotEnabled(_))
+        ));
+        assert!(simulation.advance(2).is_ok());
+        assert_eq!(simulation.enabled_for(&transition_refs[0]), Some(2));
+        assert!(simulation.fire(&transition_refs[0]).is_ok());
+    }
+
+    #[test]
+    fn timed_simulation_advance_past_latest_returns_err() {
+        let (net, _, transition_refs) = create_timed_chain();
+        let mut simulation = net.timed_simulation();
+
+        assert_eq!(simulation.max_delay(), Some(4));
+        assert!(simulation.advance(3).is_ok());
+        assert!(matches!(
+            simulation.advance(2),
+            Err(Error::DeadlineMissed(transition_ref)) if transition_ref == transition_refs[0]
+        ));
+        assert_eq!(simulation.clock(), 3);
+    }
+
+    #[test]
+    fn timed_simulation_run_stops_at_limits() {
+        let (net, _, _) = create_timed_chain();
+        let mut simulation = net.timed_simulation();
+        assert_eq!(simulation.run(1, None).unwrap(), TimedStop::StepLimit);
+        assert_eq!(simulation.trace().len(), 1);
+
+        let mut simulation = net.timed_simulation();
+        assert_eq!(simulation.run(10, Some(4)).unwrap(), TimedStop::TimeLimit);
+        assert_eq!(simulation.trace().len(), 1);
+        assert_eq!(simulation.clock(), 2);
+    }
+
+    #[test]
+    fn timed_simulation_persistent_transition_keeps_its_clock() {
+        let mut net = PetriNet::new();
+        let slow_place = net.add_place("Slow");
+        let fast_place = net.add_place("Fast");
+        let slow = net.add_transition("Slow");
+        let fast = net.add_transition("Fast");
+        assert!(net.add_arc_place_transition(&slow_place, &slow).is_ok());
+        assert!(net.add_arc_place_transition(&fast_place, &fast).is_ok());
+        assert!(net.add_arc_transition_place(&fast, &fast_place).is_ok());
+        assert!(net.add_token(&slow_place, 1).is_ok());
+        assert!(net.add_token(&fast_place, 1).is_ok());
+        let interval = FiringInterval::new(5, 5).unwrap();
+        assert!(net.set_firing_interval(&slow, interval).is_ok());
+        let interval = FiringInterval::new(2, 2).unwrap();
+        assert!(net.set_firing_interval(&fast, interval).is_ok());
+        let mut simulation = net.timed_simulation();
+
+        assert_eq!(simulation.run(3, None).unwrap(), TimedStop::StepLimit);
+        let times: Vec<(u64, &TransitionRef)> = simulation
+            .trace()
+            .iter()
+            .map(|firing| (firing.time, &firing.transition_ref))
+            .collect();
+        assert_eq!(times, vec![(2, &fast), (4, &fast), (5, &slow)]);
+    }
+
+    #[test]
+    fn timed_simulation_untimed_net_fires_at_time_zero() {
+        let (mut net, place_refs, _) = create_net_chain_topology(3);
+        assert!(net.add_token(&place_refs[0], 1).is_ok());
+        let mut simulation = net.timed_simulation();
+
+        assert_eq!(simulation.run(10, None).unwrap(), TimedStop::Deadlock);
+        assert_eq!(simulation.trace().len(), 2);
+        assert!(simulation.trace().iter().all(|firing| firing.time == 0));
+    }
+}